@@ -0,0 +1,43 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use rfc6381_codec::Codec;
+
+/// A mix of values resembling the `CODECS` attributes of a large collection of HLS playlists
+fn corpus() -> Vec<&'static str> {
+    let common = [
+        "avc1.640028",
+        "mp4a.40.2",
+        "avc1.4D401F",
+        "avc1.42E01E",
+        "ec-3",
+        "hvc1.2.4.L150.B0",
+        "mp4a.40.5",
+        "av01.0.08M.10",
+        "Opus",
+        "wvtt",
+    ];
+    common.iter().copied().cycle().take(10_000).collect()
+}
+
+fn parse(c: &mut Criterion) {
+    let corpus = corpus();
+    let mut group = c.benchmark_group("parse");
+    group.throughput(Throughput::Elements(corpus.len() as u64));
+    group.bench_function("from_str", |b| {
+        b.iter(|| {
+            for value in &corpus {
+                let _ = black_box(value.parse::<Codec>());
+            }
+        })
+    });
+    group.bench_function("parse_many", |b| {
+        b.iter(|| {
+            for result in Codec::parse_many(corpus.iter().copied()) {
+                let _ = black_box(result);
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, parse);
+criterion_main!(benches);
@@ -0,0 +1,9 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use rfc6381_codec::Codec;
+use std::str::FromStr;
+
+fuzz_target!(|codec: Codec| {
+    let s = codec.to_string();
+    assert_eq!(Codec::from_str(&s).as_ref(), Ok(&codec), "{}", s);
+});
@@ -0,0 +1,128 @@
+//! Support for APV (Advanced Professional Video) codec strings.
+
+use crate::CodecError;
+use four_cc::FourCC;
+use mp4ra_rust::SampleEntryCode;
+use std::fmt;
+use std::str::FromStr;
+
+pub(crate) const APV1: SampleEntryCode = SampleEntryCode(FourCC(*b"apv1"));
+
+/// The `apv1` codec parameter.
+///
+/// Takes the form `apv1.apvf<profile>.apvl<level>.apvb<band>` where each field is a decimal
+/// number, for example `apv1.apvf33.apvl123.apvb2`.  Trailing fields may be omitted, in which
+/// case the corresponding accessor returns `None`.
+///
+/// Note that the level field holds the `level_idc` value, which is thirty times the level
+/// number (so level 4.1 is given as `apvl123`).
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Apv1 {
+    pub(crate) profile: Option<u8>,
+    pub(crate) level: Option<u8>,
+    pub(crate) band: Option<u8>,
+}
+impl Apv1 {
+    pub const fn new(profile: u8, level: u8, band: u8) -> Apv1 {
+        Apv1 {
+            profile: Some(profile),
+            level: Some(level),
+            band: Some(band),
+        }
+    }
+    /// The `profile_idc` value
+    pub fn profile(&self) -> Option<u8> {
+        self.profile
+    }
+    /// The `level_idc` value
+    pub fn level(&self) -> Option<u8> {
+        self.level
+    }
+    /// The `band_idc` value
+    pub fn band(&self) -> Option<u8> {
+        self.band
+    }
+}
+impl FromStr for Apv1 {
+    type Err = CodecError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut result = Apv1::default();
+        let mut i = value.split('.');
+        result.profile = Some(parse_field(i.next().unwrap(), "apvf")?);
+        result.level = i.next().map(|v| parse_field(v, "apvl")).transpose()?;
+        result.band = i.next().map(|v| parse_field(v, "apvb")).transpose()?;
+        if let Some(extra) = i.next() {
+            return Err(CodecError::InvalidComponent(extra.into()));
+        }
+        Ok(result)
+    }
+}
+impl fmt::Display for Apv1 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let fields = [
+            ("apvf", self.profile),
+            ("apvl", self.level),
+            ("apvb", self.band),
+        ];
+        for (prefix, value) in fields.iter() {
+            match value {
+                Some(value) => write!(f, ".{}{}", prefix, value)?,
+                None => break,
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse_field(value: &str, prefix: &str) -> Result<u8, CodecError> {
+    let digits = value
+        .strip_prefix(prefix)
+        .ok_or_else(|| CodecError::InvalidComponent(value.into()))?;
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(CodecError::InvalidComponent(value.into()));
+    }
+    u8::from_str(digits).map_err(|_| CodecError::InvalidComponent(value.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Codec;
+    use assert_matches::*;
+
+    #[test]
+    fn apv1() {
+        assert_matches!(
+            Codec::from_str("apv1.apvf33.apvl123.apvb2"),
+            Ok(Codec::Apv1(Apv1 {
+                profile: Some(33),
+                level: Some(123),
+                band: Some(2)
+            }))
+        );
+        assert_matches!(
+            Codec::from_str("apv1.apvf33"),
+            Ok(Codec::Apv1(Apv1 {
+                profile: Some(33),
+                level: None,
+                band: None
+            }))
+        );
+        assert_matches!(
+            Codec::from_str("apv1"),
+            Ok(Codec::Apv1(Apv1 { profile: None, .. }))
+        );
+        for s in &["apv1", "apv1.apvf33.apvl123", "apv1.apvf44.apvl153.apvb0"] {
+            assert_eq!(*s, Codec::from_str(s).unwrap().to_string());
+        }
+    }
+
+    #[test]
+    fn bad_apv1() {
+        assert_matches!(Codec::from_str("apv1.apvl123"), Err(_));
+        assert_matches!(Codec::from_str("apv1.apvf"), Err(_));
+        assert_matches!(Codec::from_str("apv1.apvf+3"), Err(_));
+        assert_matches!(Codec::from_str("apv1.apvf33.apvl123.apvb2.x"), Err(_));
+    }
+}
@@ -0,0 +1,340 @@
+//! `Arbitrary` support, enabled by the `arbitrary` cargo feature.
+//!
+//! The generated values are limited to those which have a codec string form, so that a
+//! structure-aware fuzz target can check that parsing the `Display` output of any generated
+//! [`Codec`] gives back the same value.  For example, the decimal elements of `av01` are kept
+//! within the two digits the parser accepts, and the optional elements of `vp09` are either all
+//! present or all absent.
+
+#[cfg(feature = "dolby")]
+use crate::Ac4;
+#[cfg(feature = "dts")]
+use crate::Dts;
+#[cfg(feature = "text")]
+use crate::Stpp;
+use crate::{Apv1, Avc1, Avs3, Codec, Lvc1, Mp4a, Mpeg2AacProfile, Pcm, Uncv};
+#[cfg(feature = "av1")]
+use crate::{Av01, Av1ChromaSamplePosition, Av1ChromaSubsampling, Av1ColorConfig, Av1Tier};
+#[cfg(feature = "hevc")]
+use crate::{Hevc, HevcSampleEntry, HevcTier};
+#[cfg(feature = "mpegh")]
+use crate::{Mpegh, MpeghSampleEntry};
+#[cfg(feature = "vp9")]
+use crate::{Vp09, Vp9ChromaSubsampling};
+use arbitrary::{Arbitrary, Result, Unstructured};
+use four_cc::FourCC;
+use mp4ra_rust::ObjectTypeIdentifier;
+use mpeg4_audio_const::AudioObjectType;
+use std::convert::TryFrom;
+
+const ALPHANUMERIC: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// Implements `Arbitrary` for a field-less enum by choosing one of the given variants
+macro_rules! arbitrary_choice {
+    ($t:ident, [$($variant:ident),* $(,)?]) => {
+        impl<'a> Arbitrary<'a> for $t {
+            fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+                Ok(*u.choose(&[$($t::$variant),*])?)
+            }
+        }
+    };
+}
+
+#[cfg(feature = "hevc")]
+arbitrary_choice!(HevcSampleEntry, [Hvc1, Hev1]);
+#[cfg(feature = "hevc")]
+arbitrary_choice!(HevcTier, [Main, High]);
+#[cfg(feature = "av1")]
+arbitrary_choice!(Av1Tier, [Main, High]);
+#[cfg(feature = "av1")]
+arbitrary_choice!(Av1ChromaSubsampling, [Monochrome, Yuv420, Yuv422, Yuv444]);
+#[cfg(feature = "av1")]
+arbitrary_choice!(
+    Av1ChromaSamplePosition,
+    [Unknown, Vertical, Colocated, Reserved]
+);
+#[cfg(feature = "vp9")]
+arbitrary_choice!(
+    Vp9ChromaSubsampling,
+    [Yuv420Vertical, Yuv420Colocated, Yuv422, Yuv444]
+);
+arbitrary_choice!(Mpeg2AacProfile, [Main, LowComplexity, ScalableSamplingRate]);
+#[cfg(feature = "mpegh")]
+arbitrary_choice!(MpeghSampleEntry, [Mha1, Mha2, Mhm1, Mhm2]);
+#[cfg(feature = "dts")]
+arbitrary_choice!(Dts, [Dtsc, Dtsh, Dtsl, Dtse, Dtsx, Dtsy]);
+arbitrary_choice!(Pcm, [Integer, FloatingPoint]);
+
+/// A value for one of the elements written as two decimal digits
+#[cfg(any(feature = "av1", feature = "vp9"))]
+fn two_digits(u: &mut Unstructured<'_>) -> Result<u8> {
+    u.int_in_range(0..=99)
+}
+
+/// A non-empty string of ASCII letters and digits, of at most the given length
+fn alphanumeric(u: &mut Unstructured<'_>, max_len: usize) -> Result<String> {
+    let len = u.int_in_range(1..=max_len)?;
+    (0..len)
+        .map(|_| u.choose(ALPHANUMERIC).map(|&b| char::from(b)))
+        .collect()
+}
+
+impl<'a> Arbitrary<'a> for Avc1 {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Avc1 {
+            profile: u.arbitrary()?,
+            constraints: u.arbitrary()?,
+            level: u.arbitrary()?,
+        })
+    }
+}
+
+#[cfg(feature = "hevc")]
+impl<'a> Arbitrary<'a> for Hevc {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Hevc {
+            sample_entry: u.arbitrary()?,
+            profile_space: u.int_in_range(0..=3)?,
+            profile_idc: u.int_in_range(0..=31)?,
+            profile_compatibility_flags: u.arbitrary()?,
+            tier: u.arbitrary()?,
+            level_idc: u.arbitrary()?,
+            constraint_indicator_flags: u.arbitrary()?,
+        })
+    }
+}
+
+#[cfg(feature = "av1")]
+impl<'a> Arbitrary<'a> for Av1ColorConfig {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Av1ColorConfig {
+            chroma_subsampling: u.arbitrary()?,
+            chroma_sample_position: u.arbitrary()?,
+            color_primaries: two_digits(u)?.into(),
+            transfer_characteristics: two_digits(u)?.into(),
+            matrix_coefficients: two_digits(u)?.into(),
+            video_full_range_flag: u.arbitrary()?,
+        })
+    }
+}
+
+#[cfg(feature = "av1")]
+impl<'a> Arbitrary<'a> for Av01 {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Av01 {
+            profile: u.int_in_range(0..=9)?,
+            level: two_digits(u)?,
+            tier: u.arbitrary()?,
+            bit_depth: two_digits(u)?,
+            color: u.arbitrary()?,
+        })
+    }
+}
+
+#[cfg(feature = "vp9")]
+impl<'a> Arbitrary<'a> for Vp09 {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut vp09 = Vp09::new(two_digits(u)?, two_digits(u)?, two_digits(u)?);
+        // the optional elements must either all be present or all be absent
+        if u.arbitrary()? {
+            vp09.chroma_subsampling = Some(u.arbitrary()?);
+            vp09.color_primaries = Some(two_digits(u)?.into());
+            vp09.transfer_characteristics = Some(two_digits(u)?.into());
+            vp09.matrix_coefficients = Some(two_digits(u)?.into());
+            vp09.video_full_range_flag = Some(u.arbitrary()?);
+        }
+        Ok(vp09)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Mp4a {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(match u.int_in_range(0..=4)? {
+            0 => Mp4a::Mpeg4Audio {
+                audio_object_type: match u.arbitrary::<Option<u8>>()? {
+                    Some(aoti) => AudioObjectType::try_from(aoti).ok(),
+                    None => None,
+                },
+            },
+            1 => Mp4a::Mpeg2Aac {
+                profile: u.arbitrary()?,
+                audio_object_type_indication: u.arbitrary()?,
+            },
+            2 => Mp4a::Mpeg1Audio,
+            3 => Mp4a::Mpeg2Audio,
+            _ => {
+                let aoti: Option<u8> = u.arbitrary()?;
+                // object-type-indications which the parser gives a more specific variant for
+                let known: &[u8] = match aoti {
+                    Some(_) => &[0x40, 0x66, 0x67, 0x68],
+                    None => &[0x40, 0x66, 0x67, 0x68, 0x69, 0x6B],
+                };
+                let mut oti: u8 = u.arbitrary()?;
+                if known.contains(&oti) {
+                    oti = 0xFF;
+                }
+                Mp4a::Unknown {
+                    object_type_indication: ObjectTypeIdentifier::from(oti),
+                    audio_object_type_indication: aoti,
+                }
+            }
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Lvc1 {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Lvc1::new(u.arbitrary()?, u.arbitrary()?))
+    }
+}
+
+impl<'a> Arbitrary<'a> for Uncv {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let profile = if u.arbitrary()? {
+            let mut code = [0; 4];
+            for b in code.iter_mut() {
+                *b = *u.choose(ALPHANUMERIC)?;
+            }
+            Some(FourCC(code))
+        } else {
+            None
+        };
+        Ok(Uncv::new(profile))
+    }
+}
+
+impl<'a> Arbitrary<'a> for Avs3 {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Avs3::new(u.arbitrary()?, u.arbitrary()?))
+    }
+}
+
+impl<'a> Arbitrary<'a> for Apv1 {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        // the optional elements can only be omitted from the end
+        let mut apv1 = Apv1::default();
+        let count = u.int_in_range(0..=3)?;
+        if count > 0 {
+            apv1.profile = Some(u.arbitrary()?);
+        }
+        if count > 1 {
+            apv1.level = Some(u.arbitrary()?);
+        }
+        if count > 2 {
+            apv1.band = Some(u.arbitrary()?);
+        }
+        Ok(apv1)
+    }
+}
+
+#[cfg(feature = "dolby")]
+impl<'a> Arbitrary<'a> for Ac4 {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Ac4::new(u.arbitrary()?, u.arbitrary()?, u.arbitrary()?))
+    }
+}
+
+#[cfg(feature = "mpegh")]
+impl<'a> Arbitrary<'a> for Mpegh {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Mpegh {
+            sample_entry: u.arbitrary()?,
+            profile_level_indication: u.arbitrary()?,
+        })
+    }
+}
+
+#[cfg(feature = "text")]
+impl<'a> Arbitrary<'a> for Stpp {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        // profiles can only be given following a namespace
+        if !u.arbitrary()? {
+            return Ok(Stpp::default());
+        }
+        let namespace = alphanumeric(u, 8)?;
+        let profile_count = u.int_in_range(0..=3)?;
+        let profiles = (0..profile_count)
+            .map(|_| alphanumeric(u, 8))
+            .collect::<Result<_>>()?;
+        Ok(Stpp {
+            namespace: Some(namespace),
+            profiles,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Codec {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(match u.int_in_range(0..=26)? {
+            0 => Codec::Avc1(u.arbitrary()?),
+            #[cfg(feature = "hevc")]
+            1 => Codec::Hevc(u.arbitrary()?),
+            #[cfg(feature = "av1")]
+            2 => Codec::Av01(u.arbitrary()?),
+            #[cfg(feature = "vp9")]
+            3 => Codec::Vp09(u.arbitrary()?),
+            4 => Codec::Mp4a(u.arbitrary()?),
+            5 => Codec::Lvc1(u.arbitrary()?),
+            6 => Codec::Uncv(u.arbitrary()?),
+            7 => Codec::Avs3(u.arbitrary()?),
+            8 => Codec::Av3a,
+            9 => Codec::Apv1(u.arbitrary()?),
+            10 => Codec::Opus,
+            11 => Codec::Flac,
+            12 => Codec::Alac,
+            #[cfg(feature = "dolby")]
+            13 => Codec::Ac3,
+            #[cfg(feature = "dolby")]
+            14 => Codec::Eac3,
+            #[cfg(feature = "dolby")]
+            15 => Codec::Ac4(u.arbitrary()?),
+            #[cfg(feature = "dts")]
+            16 => Codec::Dts(u.arbitrary()?),
+            #[cfg(feature = "mpegh")]
+            17 => Codec::Mpegh(u.arbitrary()?),
+            18 => Codec::Mp3,
+            19 => Codec::Dra1,
+            20 => Codec::Pcm(u.arbitrary()?),
+            #[cfg(feature = "text")]
+            21 => Codec::Wvtt,
+            #[cfg(feature = "text")]
+            22 => Codec::Stpp(u.arbitrary()?),
+            #[cfg(feature = "text")]
+            23 => Codec::Tx3g,
+            // a leading `zz` keeps the value clear of all registered sample entry codes
+            _ => {
+                let mut value = format!("zz{}", alphanumeric(u, 6)?);
+                if u.arbitrary()? {
+                    value.push('.');
+                    value.push_str(&alphanumeric(u, 6)?);
+                }
+                Codec::Other(value.into())
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn round_trip() {
+        // a simple xorshift generator, so that the test covers the same values on each run
+        let mut state: u64 = 0x2545_F491_4F6C_DD1D;
+        let mut data = vec![0; 256];
+        for _ in 0..5000 {
+            for b in data.iter_mut() {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                *b = state as u8;
+            }
+            let mut u = Unstructured::new(&data);
+            let codec = Codec::arbitrary(&mut u).unwrap();
+            let value = codec.to_string();
+            assert_eq!(Codec::from_str(&value).as_ref(), Ok(&codec), "{}", value);
+        }
+    }
+}
@@ -0,0 +1,660 @@
+//! Support for AV1 codec strings, as specified by the _AV1 Codec ISO Media File Format Binding_.
+
+use crate::{parse_decimal_element, CodecError};
+use crate::{ColorPrimaries, MatrixCoefficients, TransferCharacteristics};
+use std::fmt;
+use std::str::FromStr;
+
+/// The `av01` codec parameter, like `av01.0.04M.10.0.110.09.16.09.0`.
+///
+/// The mandatory elements give `seq_profile`, `seq_level_idx` and tier, and bit depth.  These
+/// may be followed by the optional colour elements (see [`Av1ColorConfig`]), which must either
+/// all be present or all be absent.
+///
+/// ```rust
+/// # use rfc6381_codec::{Codec, Av1Profile, Av1Level, Av1ChromaSubsampling};
+/// # use std::str::FromStr;
+/// if let Ok(Codec::Av01(av01)) = Codec::from_str("av01.0.04M.10.0.110.09.16.09.0") {
+///     assert_eq!(av01.profile_enum(), Av1Profile::Main);
+///     assert_eq!(av01.level_enum(), Some(Av1Level::L3_0));
+///     assert_eq!(av01.bit_depth(), 10);
+///     assert_eq!(av01.chroma_subsampling(), Av1ChromaSubsampling::Yuv420);
+///     assert!(av01.transfer_characteristics().is_hdr());
+/// } else {
+///     panic!("unexpected codec type");
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Av01 {
+    pub(crate) profile: u8,
+    pub(crate) level: u8,
+    pub(crate) tier: Av1Tier,
+    pub(crate) bit_depth: u8,
+    pub(crate) color: Option<Av1ColorConfig>,
+}
+impl Av01 {
+    pub const fn new(
+        profile: u8,
+        level: u8,
+        tier: Av1Tier,
+        bit_depth: u8,
+        color: Option<Av1ColorConfig>,
+    ) -> Av01 {
+        Av01 {
+            profile,
+            level,
+            tier,
+            bit_depth,
+            color,
+        }
+    }
+    /// Creates a value from the payload of an `av1C` box (i.e. the
+    /// `AV1CodecConfigurationRecord`, without the box header).
+    ///
+    /// The record does not give the colour description of the stream, so where the monochrome
+    /// and chroma values differ from the defaults, the colour elements are included giving
+    /// `Unspecified` for the colour primaries, transfer characteristics and matrix
+    /// coefficients.  Otherwise the optional colour elements are omitted.
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::{Av01, Codec};
+    /// let av1c = [0x81, 0x08, 0x4c, 0x00];
+    /// let av01 = Av01::from_av1c(&av1c).unwrap();
+    /// assert_eq!(Codec::Av01(av01).to_string(), "av01.0.08M.10");
+    /// ```
+    pub fn from_av1c(av1c: &[u8]) -> Result<Av01, CodecError> {
+        if av1c.len() < 4 {
+            return Err(CodecError::ConfigurationRecordTooShort {
+                expected: 4,
+                got: av1c.len(),
+            });
+        }
+        if av1c[0] != 0x81 {
+            // the marker bit, followed by version 1
+            return Err(CodecError::UnsupportedConfigurationVersion(av1c[0] & 0x7f));
+        }
+        let flags = av1c[2];
+        let bit = |n: u8| flags & (0x80 >> n) != 0;
+        let bit_depth = match (bit(1), bit(2)) {
+            (false, _) => 8,
+            (true, false) => 10,
+            (true, true) => 12,
+        };
+        let chroma_subsampling = match (bit(3), bit(4), bit(5)) {
+            (true, _, _) => Av1ChromaSubsampling::Monochrome,
+            (false, true, true) => Av1ChromaSubsampling::Yuv420,
+            (false, true, false) => Av1ChromaSubsampling::Yuv422,
+            (false, false, false) => Av1ChromaSubsampling::Yuv444,
+            (false, false, true) => {
+                return Err(CodecError::InvalidComponent(
+                    format_args!("{:02x}", flags).into(),
+                ))
+            }
+        };
+        let chroma_sample_position = match flags & 0b11 {
+            0 => Av1ChromaSamplePosition::Unknown,
+            1 => Av1ChromaSamplePosition::Vertical,
+            2 => Av1ChromaSamplePosition::Colocated,
+            _ => Av1ChromaSamplePosition::Reserved,
+        };
+        let default = Av1ColorConfig::default();
+        let color = if chroma_subsampling == default.chroma_subsampling
+            && chroma_sample_position == default.chroma_sample_position
+        {
+            None
+        } else {
+            Some(Av1ColorConfig {
+                chroma_subsampling,
+                chroma_sample_position,
+                color_primaries: ColorPrimaries::Unspecified,
+                transfer_characteristics: TransferCharacteristics::Unspecified,
+                matrix_coefficients: MatrixCoefficients::Unspecified,
+                video_full_range_flag: false,
+            })
+        };
+        Ok(Av01 {
+            profile: av1c[1] >> 5,
+            level: av1c[1] & 0b1_1111,
+            tier: if bit(0) { Av1Tier::High } else { Av1Tier::Main },
+            bit_depth,
+            color,
+        })
+    }
+    /// The `seq_profile` value
+    pub fn profile(&self) -> u8 {
+        self.profile
+    }
+    /// The `seq_level_idx` value
+    pub fn level(&self) -> u8 {
+        self.level
+    }
+    pub fn tier(&self) -> Av1Tier {
+        self.tier
+    }
+    pub fn bit_depth(&self) -> u8 {
+        self.bit_depth
+    }
+    /// The optional colour elements, if they were present in the codec string.
+    ///
+    /// The accessors like [`transfer_characteristics()`](Self::transfer_characteristics) apply
+    /// the default values when these elements are absent, so only use this method when you
+    /// need to know whether the values were explicitly given.
+    pub fn color_config(&self) -> Option<&Av1ColorConfig> {
+        self.color.as_ref()
+    }
+    pub fn profile_enum(&self) -> Av1Profile {
+        Av1Profile::from(self.profile)
+    }
+    /// Interprets the `seq_level_idx` value, returning `None` for values which the AV1 spec does
+    /// not define.
+    pub fn level_enum(&self) -> Option<Av1Level> {
+        Av1Level::ALL
+            .iter()
+            .find(|l| l.seq_level_idx() == self.level)
+            .copied()
+    }
+
+    fn effective_color(&self) -> Av1ColorConfig {
+        self.color.unwrap_or_default()
+    }
+    pub fn chroma_subsampling(&self) -> Av1ChromaSubsampling {
+        self.effective_color().chroma_subsampling
+    }
+    pub fn chroma_sample_position(&self) -> Av1ChromaSamplePosition {
+        self.effective_color().chroma_sample_position
+    }
+    pub fn color_primaries(&self) -> ColorPrimaries {
+        self.effective_color().color_primaries
+    }
+    pub fn transfer_characteristics(&self) -> TransferCharacteristics {
+        self.effective_color().transfer_characteristics
+    }
+    pub fn matrix_coefficients(&self) -> MatrixCoefficients {
+        self.effective_color().matrix_coefficients
+    }
+    pub fn video_full_range_flag(&self) -> bool {
+        self.effective_color().video_full_range_flag
+    }
+}
+impl FromStr for Av01 {
+    type Err = CodecError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut i = value.split('.');
+        let profile = parse_decimal_element(i.next().unwrap(), 1)?;
+        let level_tier = i
+            .next()
+            .ok_or_else(|| CodecError::ExpectedHierarchySeparator(value.into()))?;
+        let (level, tier) = match level_tier.as_bytes() {
+            [.., b'M'] => (&level_tier[..level_tier.len() - 1], Av1Tier::Main),
+            [.., b'H'] => (&level_tier[..level_tier.len() - 1], Av1Tier::High),
+            _ => return Err(CodecError::InvalidComponent(level_tier.into())),
+        };
+        let level = parse_decimal_element(level, 2)?;
+        let bit_depth = i
+            .next()
+            .ok_or_else(|| CodecError::ExpectedHierarchySeparator(value.into()))?;
+        let bit_depth = parse_decimal_element(bit_depth, 2)?;
+        let color = match i.next() {
+            None => None,
+            Some(monochrome) => Some(Av1ColorConfig::parse(value, monochrome, &mut i)?),
+        };
+        if let Some(extra) = i.next() {
+            return Err(CodecError::InvalidComponent(extra.into()));
+        }
+        Ok(Av01 {
+            profile,
+            level,
+            tier,
+            bit_depth,
+            color,
+        })
+    }
+}
+impl fmt::Display for Av01 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}.{:02}{}.{:02}",
+            self.profile, self.level, self.tier, self.bit_depth
+        )?;
+        if let Some(ref color) = self.color {
+            write!(f, ".{}", color)?;
+        }
+        Ok(())
+    }
+}
+
+/// The optional colour elements of an `av01` codec string.
+///
+/// When these elements are omitted from the codec string, the values given by `default()`
+/// apply: 4:2:0 subsampling with unknown chroma sample position, BT.709 colour and
+/// limited range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Av1ColorConfig {
+    pub(crate) chroma_subsampling: Av1ChromaSubsampling,
+    pub(crate) chroma_sample_position: Av1ChromaSamplePosition,
+    pub(crate) color_primaries: ColorPrimaries,
+    pub(crate) transfer_characteristics: TransferCharacteristics,
+    pub(crate) matrix_coefficients: MatrixCoefficients,
+    pub(crate) video_full_range_flag: bool,
+}
+impl Av1ColorConfig {
+    pub const fn new(
+        chroma_subsampling: Av1ChromaSubsampling,
+        chroma_sample_position: Av1ChromaSamplePosition,
+        color_primaries: ColorPrimaries,
+        transfer_characteristics: TransferCharacteristics,
+        matrix_coefficients: MatrixCoefficients,
+        video_full_range_flag: bool,
+    ) -> Av1ColorConfig {
+        Av1ColorConfig {
+            chroma_subsampling,
+            chroma_sample_position,
+            color_primaries,
+            transfer_characteristics,
+            matrix_coefficients,
+            video_full_range_flag,
+        }
+    }
+    pub fn chroma_subsampling(&self) -> Av1ChromaSubsampling {
+        self.chroma_subsampling
+    }
+    pub fn chroma_sample_position(&self) -> Av1ChromaSamplePosition {
+        self.chroma_sample_position
+    }
+    pub fn color_primaries(&self) -> ColorPrimaries {
+        self.color_primaries
+    }
+    pub fn transfer_characteristics(&self) -> TransferCharacteristics {
+        self.transfer_characteristics
+    }
+    pub fn matrix_coefficients(&self) -> MatrixCoefficients {
+        self.matrix_coefficients
+    }
+    pub fn video_full_range_flag(&self) -> bool {
+        self.video_full_range_flag
+    }
+
+    fn parse<'a>(
+        value: &str,
+        monochrome: &str,
+        i: &mut impl Iterator<Item = &'a str>,
+    ) -> Result<Av1ColorConfig, CodecError> {
+        let mut next = || {
+            i.next()
+                .ok_or_else(|| CodecError::ExpectedHierarchySeparator(value.into()))
+        };
+        let monochrome = parse_flag(monochrome)?;
+        let chroma = next()?;
+        if chroma.len() != 3 {
+            return Err(CodecError::UnexpectedLength {
+                expected: 3,
+                got: chroma.into(),
+            });
+        }
+        if !chroma.is_ascii() {
+            return Err(CodecError::InvalidComponent(chroma.into()));
+        }
+        let subsampling_x = parse_flag(&chroma[0..1])?;
+        let subsampling_y = parse_flag(&chroma[1..2])?;
+        let chroma_sample_position = match parse_decimal_element(&chroma[2..3], 1)? {
+            0 => Av1ChromaSamplePosition::Unknown,
+            1 => Av1ChromaSamplePosition::Vertical,
+            2 => Av1ChromaSamplePosition::Colocated,
+            3 => Av1ChromaSamplePosition::Reserved,
+            _ => return Err(CodecError::InvalidComponent(chroma.into())),
+        };
+        let chroma_subsampling = match (monochrome, subsampling_x, subsampling_y) {
+            (true, true, true) => Av1ChromaSubsampling::Monochrome,
+            (false, true, true) => Av1ChromaSubsampling::Yuv420,
+            (false, true, false) => Av1ChromaSubsampling::Yuv422,
+            (false, false, false) => Av1ChromaSubsampling::Yuv444,
+            _ => return Err(CodecError::InvalidComponent(chroma.into())),
+        };
+        let color_primaries = parse_decimal_element(next()?, 2)?.into();
+        let transfer_characteristics = parse_decimal_element(next()?, 2)?.into();
+        let matrix_coefficients = parse_decimal_element(next()?, 2)?.into();
+        let video_full_range_flag = parse_flag(next()?)?;
+        Ok(Av1ColorConfig {
+            chroma_subsampling,
+            chroma_sample_position,
+            color_primaries,
+            transfer_characteristics,
+            matrix_coefficients,
+            video_full_range_flag,
+        })
+    }
+}
+impl Default for Av1ColorConfig {
+    fn default() -> Self {
+        Av1ColorConfig {
+            chroma_subsampling: Av1ChromaSubsampling::Yuv420,
+            chroma_sample_position: Av1ChromaSamplePosition::Unknown,
+            color_primaries: ColorPrimaries::Bt709,
+            transfer_characteristics: TransferCharacteristics::Bt709,
+            matrix_coefficients: MatrixCoefficients::Bt709,
+            video_full_range_flag: false,
+        }
+    }
+}
+impl fmt::Display for Av1ColorConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (monochrome, x, y) = match self.chroma_subsampling {
+            Av1ChromaSubsampling::Monochrome => (1, 1, 1),
+            Av1ChromaSubsampling::Yuv420 => (0, 1, 1),
+            Av1ChromaSubsampling::Yuv422 => (0, 1, 0),
+            Av1ChromaSubsampling::Yuv444 => (0, 0, 0),
+        };
+        let position = match self.chroma_sample_position {
+            Av1ChromaSamplePosition::Unknown => 0,
+            Av1ChromaSamplePosition::Vertical => 1,
+            Av1ChromaSamplePosition::Colocated => 2,
+            Av1ChromaSamplePosition::Reserved => 3,
+        };
+        write!(
+            f,
+            "{}.{}{}{}.{:02}.{:02}.{:02}.{}",
+            monochrome,
+            x,
+            y,
+            position,
+            u8::from(self.color_primaries),
+            u8::from(self.transfer_characteristics),
+            u8::from(self.matrix_coefficients),
+            self.video_full_range_flag as u8
+        )
+    }
+}
+
+fn parse_flag(value: &str) -> Result<bool, CodecError> {
+    match value {
+        "0" => Ok(false),
+        "1" => Ok(true),
+        _ => Err(CodecError::InvalidComponent(value.into())),
+    }
+}
+
+/// AV1 profiles, as identified by `seq_profile`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Av1Profile {
+    /// 8 and 10-bit 4:2:0 and monochrome
+    Main,
+    /// 8 and 10-bit, adding 4:4:4
+    High,
+    /// Up to 12-bit, adding 4:2:2
+    Professional,
+    /// A `seq_profile` value reserved for future use
+    Reserved(u8),
+}
+impl From<u8> for Av1Profile {
+    fn from(seq_profile: u8) -> Self {
+        match seq_profile {
+            0 => Av1Profile::Main,
+            1 => Av1Profile::High,
+            2 => Av1Profile::Professional,
+            _ => Av1Profile::Reserved(seq_profile),
+        }
+    }
+}
+impl From<Av1Profile> for u8 {
+    fn from(profile: Av1Profile) -> Self {
+        match profile {
+            Av1Profile::Main => 0,
+            Av1Profile::High => 1,
+            Av1Profile::Professional => 2,
+            Av1Profile::Reserved(seq_profile) => seq_profile,
+        }
+    }
+}
+/// Displays the profile name, like `"Main"`
+impl fmt::Display for Av1Profile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Av1Profile::Main => f.write_str("Main"),
+            Av1Profile::High => f.write_str("High"),
+            Av1Profile::Professional => f.write_str("Professional"),
+            Av1Profile::Reserved(seq_profile) => write!(f, "seq_profile {}", seq_profile),
+        }
+    }
+}
+
+/// The AV1 tier, signalled by `seq_tier`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Av1Tier {
+    Main,
+    High,
+}
+/// Displays the tier as in the codec string, `"M"` for the Main tier and `"H"` for High tier
+impl fmt::Display for Av1Tier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Av1Tier::Main => f.write_str("M"),
+            Av1Tier::High => f.write_str("H"),
+        }
+    }
+}
+
+/// AV1 levels defined by the AV1 spec.
+///
+/// Levels are ordered by `seq_level_idx`, skipping the index values the spec reserves.  The
+/// `Max` level (`seq_level_idx` 31, which imposes no limits at all) sorts after every other
+/// level.
+///
+/// ```rust
+/// # use rfc6381_codec::Av1Level;
+/// assert!(Av1Level::L5_1 > Av1Level::L4_1);
+/// assert!(Av1Level::Max > Av1Level::L6_3);
+/// assert_eq!(Av1Level::L5_1.to_string(), "5.1");
+/// assert_eq!(Av1Level::L5_1.seq_level_idx(), 13);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Av1Level {
+    L2_0,
+    L2_1,
+    L3_0,
+    L3_1,
+    L4_0,
+    L4_1,
+    L5_0,
+    L5_1,
+    L5_2,
+    L5_3,
+    L6_0,
+    L6_1,
+    L6_2,
+    L6_3,
+    Max,
+}
+impl Av1Level {
+    /// All the defined AV1 levels, from level 2.0 to level 6.3, and then `Max`
+    pub const ALL: [Av1Level; 15] = [
+        Av1Level::L2_0,
+        Av1Level::L2_1,
+        Av1Level::L3_0,
+        Av1Level::L3_1,
+        Av1Level::L4_0,
+        Av1Level::L4_1,
+        Av1Level::L5_0,
+        Av1Level::L5_1,
+        Av1Level::L5_2,
+        Av1Level::L5_3,
+        Av1Level::L6_0,
+        Av1Level::L6_1,
+        Av1Level::L6_2,
+        Av1Level::L6_3,
+        Av1Level::Max,
+    ];
+
+    pub fn seq_level_idx(&self) -> u8 {
+        match self {
+            Av1Level::L2_0 => 0,
+            Av1Level::L2_1 => 1,
+            Av1Level::L3_0 => 4,
+            Av1Level::L3_1 => 5,
+            Av1Level::L4_0 => 8,
+            Av1Level::L4_1 => 9,
+            Av1Level::L5_0 => 12,
+            Av1Level::L5_1 => 13,
+            Av1Level::L5_2 => 14,
+            Av1Level::L5_3 => 15,
+            Av1Level::L6_0 => 16,
+            Av1Level::L6_1 => 17,
+            Av1Level::L6_2 => 18,
+            Av1Level::L6_3 => 19,
+            Av1Level::Max => 31,
+        }
+    }
+}
+/// Displays the level number as used in the AV1 spec, like `"5.1"`, or `"Max"`
+impl fmt::Display for Av1Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Av1Level::Max => f.write_str("Max"),
+            _ => {
+                let idx = self.seq_level_idx();
+                write!(f, "{}.{}", 2 + (idx >> 2), idx & 3)
+            }
+        }
+    }
+}
+
+/// Chroma subsampling, derived from the `mono_chrome`, `subsampling_x` and `subsampling_y`
+/// values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Av1ChromaSubsampling {
+    Monochrome,
+    Yuv420,
+    Yuv422,
+    Yuv444,
+}
+
+/// The `chroma_sample_position` value, which is only meaningful for 4:2:0 subsampling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Av1ChromaSamplePosition {
+    Unknown,
+    /// Horizontally co-located with luma samples, and vertically between two luma samples
+    Vertical,
+    /// Co-located with the top-left luma sample
+    Colocated,
+    Reserved,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Codec;
+    use assert_matches::*;
+
+    fn av01(codec: &str) -> Av01 {
+        match Codec::from_str(codec) {
+            Ok(Codec::Av01(av01)) => av01,
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parse() {
+        let a = av01("av01.0.04M.08");
+        assert_eq!(a.profile(), 0);
+        assert_eq!(a.level(), 4);
+        assert_eq!(a.tier(), Av1Tier::Main);
+        assert_eq!(a.bit_depth(), 8);
+        assert!(a.color_config().is_none());
+        assert_eq!(a.chroma_subsampling(), Av1ChromaSubsampling::Yuv420);
+        assert_eq!(a.color_primaries(), ColorPrimaries::Bt709);
+        assert!(!a.video_full_range_flag());
+
+        let a = av01("av01.2.19H.12.0.000.01.13.00.1");
+        assert_eq!(a.profile_enum(), Av1Profile::Professional);
+        assert_eq!(a.level_enum(), Some(Av1Level::L6_3));
+        assert_eq!(a.tier(), Av1Tier::High);
+        assert_eq!(a.chroma_subsampling(), Av1ChromaSubsampling::Yuv444);
+        assert_eq!(a.transfer_characteristics(), TransferCharacteristics::Srgb);
+        assert_eq!(a.matrix_coefficients(), MatrixCoefficients::Identity);
+        assert!(a.video_full_range_flag());
+
+        let a = av01("av01.0.31M.10.1.112.09.18.09.0");
+        assert_eq!(a.level_enum(), Some(Av1Level::Max));
+        assert_eq!(a.chroma_subsampling(), Av1ChromaSubsampling::Monochrome);
+        assert_eq!(
+            a.chroma_sample_position(),
+            Av1ChromaSamplePosition::Colocated
+        );
+        assert_eq!(a.transfer_characteristics(), TransferCharacteristics::Hlg);
+        assert_eq!(av01("av01.0.02M.08").level_enum(), None);
+    }
+
+    #[test]
+    fn roundtrip() {
+        for s in &[
+            "av01.0.04M.08",
+            "av01.1.13H.10",
+            "av01.0.04M.10.0.110.09.16.09.0",
+            "av01.2.19H.12.0.100.01.01.01.1",
+            "av01.0.31M.10.1.112.09.18.09.0",
+        ] {
+            assert_eq!(*s, Codec::from_str(s).unwrap().to_string());
+        }
+    }
+
+    #[test]
+    fn bad_av01() {
+        for s in &[
+            "av01",
+            "av01.0",
+            "av01.0.04M",
+            "av01.00.04M.08",
+            "av01.0.4M.08",
+            "av01.0.04X.08",
+            "av01.0.04M.8",
+            "av01.0.04M.08.0",
+            "av01.0.04M.08.0.110.09.16.09",
+            "av01.0.04M.08.0.110.09.16.09.0.0",
+            "av01.0.04M.08.2.110.09.16.09.0",
+            "av01.0.04M.08.0.011.09.16.09.0",
+            "av01.0.04M.08.1.000.09.16.09.0",
+            "av01.0.04M.08.0.114.09.16.09.0",
+            "av01.0.04M.08.0.110.9.16.09.0",
+            "av01.0.+4M.08",
+            "av01.0.04M.08.0.é1.09.16.09.0",
+        ] {
+            assert_matches!(Codec::from_str(s), Err(_), "{}", s);
+        }
+    }
+
+    #[test]
+    fn from_av1c() {
+        let to_string = |av1c: &[u8]| Codec::Av01(Av01::from_av1c(av1c).unwrap()).to_string();
+        assert_eq!(to_string(&[0x81, 0x04, 0x0c, 0x00]), "av01.0.04M.08");
+        assert_eq!(
+            to_string(&[0x81, 0x2d, 0xc0, 0x00]),
+            "av01.1.13H.10.0.000.02.02.02.0"
+        );
+        assert_eq!(
+            to_string(&[0x81, 0x48, 0x7d, 0x00]),
+            "av01.2.08M.12.1.111.02.02.02.0"
+        );
+        assert_matches!(
+            Av01::from_av1c(&[0x81, 0x04, 0x0c]),
+            Err(CodecError::ConfigurationRecordTooShort {
+                expected: 4,
+                got: 3
+            })
+        );
+        assert_matches!(
+            Av01::from_av1c(&[0x82, 0x04, 0x0c, 0x00]),
+            Err(CodecError::UnsupportedConfigurationVersion(2))
+        );
+    }
+
+    #[test]
+    fn levels() {
+        let mut sorted = Av1Level::ALL;
+        sorted.sort();
+        assert_eq!(sorted, Av1Level::ALL);
+        assert_eq!(Av1Level::L2_0.to_string(), "2.0");
+        assert_eq!(Av1Level::L6_3.to_string(), "6.3");
+        assert_eq!(Av1Level::Max.to_string(), "Max");
+    }
+}
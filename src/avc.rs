@@ -0,0 +1,780 @@
+//! Support for H.264 / AVC codec strings.
+
+use crate::CodecError;
+use std::fmt;
+use std::str::FromStr;
+
+/// The `nal_unit_type` of a sequence parameter set
+const SPS_NAL_UNIT_TYPE: u8 = 7;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Avc1 {
+    pub(crate) profile: u8,
+    pub(crate) constraints: u8,
+    pub(crate) level: u8,
+}
+impl Avc1 {
+    /// Creates a value for the given profile and level.
+    ///
+    /// Any constraint flags implied by the given profile (for instance `constraint_set1_flag`
+    /// for `AvcProfile::ConstrainedBaseline`) or level (`constraint_set3_flag` for
+    /// `AvcLevel::L1b` in the Baseline, Main and Extended profiles) are set in addition to
+    /// those given in `constraints`.
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::{Avc1, AvcLevel, AvcProfile, Codec};
+    /// let avc1 = Avc1::new(AvcProfile::ConstrainedBaseline, 0xa0, AvcLevel::L3_0);
+    /// assert_eq!(Codec::Avc1(avc1).to_string(), "avc1.42E01E");
+    /// ```
+    pub fn new(profile: AvcProfile, constraints: u8, level: AvcLevel) -> Avc1 {
+        let mut constraints = constraints
+            | match profile {
+                AvcProfile::ConstrainedBaseline => 0b0100_0000,
+                AvcProfile::ProgressiveHigh => 0b0000_1000,
+                AvcProfile::ConstrainedHigh => 0b0000_1100,
+                AvcProfile::High10Intra | AvcProfile::High422Intra | AvcProfile::High444Intra => {
+                    0b0001_0000
+                }
+                _ => 0,
+            };
+        let profile = u8::from(profile);
+        let level = if level == AvcLevel::L1b {
+            if is_level_1b_via_constraint(profile) {
+                constraints |= 0b0001_0000;
+                11
+            } else {
+                9
+            }
+        } else {
+            level.level_idc()
+        };
+        Avc1 {
+            profile,
+            constraints,
+            level,
+        }
+    }
+    /// Creates a value from the payload of an `avcC` box (i.e. the
+    /// `AVCDecoderConfigurationRecord` defined in ISO/IEC 14496-15, without the box header).
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::{Avc1, Codec};
+    /// let avcc = [0x01, 0x64, 0x00, 0x1f, 0xff, 0xe1, 0x00, 0x19];
+    /// let avc1 = Avc1::from_avcc(&avcc).unwrap();
+    /// assert_eq!(Codec::Avc1(avc1).to_string(), "avc1.64001F");
+    /// ```
+    pub fn from_avcc(avcc: &[u8]) -> Result<Avc1, CodecError> {
+        if avcc.len() < 4 {
+            return Err(CodecError::ConfigurationRecordTooShort {
+                expected: 4,
+                got: avcc.len(),
+            });
+        }
+        if avcc[0] != 1 {
+            return Err(CodecError::UnsupportedConfigurationVersion(avcc[0]));
+        }
+        Ok(Avc1 {
+            profile: avcc[1],
+            constraints: avcc[2],
+            level: avcc[3],
+        })
+    }
+    /// Creates a value from a sequence parameter set NAL unit, starting with the NAL header
+    /// byte (and without any Annex B start code).
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::{Avc1, Codec};
+    /// let sps = [0x67, 0x64, 0x00, 0x0a, 0xac, 0x72, 0x84, 0x44, 0x26, 0x84];
+    /// let avc1 = Avc1::from_sps_nal(&sps).unwrap();
+    /// assert_eq!(Codec::Avc1(avc1).to_string(), "avc1.64000A");
+    /// ```
+    pub fn from_sps_nal(nal: &[u8]) -> Result<Avc1, CodecError> {
+        if nal.len() < 4 {
+            return Err(CodecError::ConfigurationRecordTooShort {
+                expected: 4,
+                got: nal.len(),
+            });
+        }
+        let nal_unit_type = nal[0] & 0b1_1111;
+        if nal_unit_type != SPS_NAL_UNIT_TYPE {
+            return Err(CodecError::UnexpectedNalUnitType(nal_unit_type));
+        }
+        // profile_idc and level_idc are never zero, so there can be no emulation prevention
+        // bytes among these first three bytes of the RBSP
+        Ok(Avc1 {
+            profile: nal[1],
+            constraints: nal[2],
+            level: nal[3],
+        })
+    }
+    /// Creates a value from the first sequence parameter set found in the given H.264 Annex B
+    /// byte stream (i.e. NAL units each preceded by a `00 00 01` or `00 00 00 01` start code),
+    /// such as the payload of an MPEG-TS video elementary stream.
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::{Avc1, Codec};
+    /// let stream = [
+    ///     0x00, 0x00, 0x00, 0x01, 0x09, 0xf0, // access unit delimiter
+    ///     0x00, 0x00, 0x00, 0x01, 0x67, 0x4d, 0x40, 0x1f, 0x9a, 0x66, 0x02, 0x80,
+    ///     0x00, 0x00, 0x01, 0x68, 0xee, 0x3c, 0x80,
+    /// ];
+    /// let avc1 = Avc1::from_annex_b(&stream).unwrap();
+    /// assert_eq!(Codec::Avc1(avc1).to_string(), "avc1.4D401F");
+    /// ```
+    pub fn from_annex_b(stream: &[u8]) -> Result<Avc1, CodecError> {
+        let mut rest = stream;
+        while let Some(pos) = rest.windows(3).position(|w| w == [0, 0, 1]) {
+            rest = &rest[pos + 3..];
+            if rest.first().map(|b| b & 0b1_1111) == Some(SPS_NAL_UNIT_TYPE) {
+                // the SPS ends at the next start code, if there is one
+                let end = rest.windows(3).position(|w| w == [0, 0, 1]);
+                return Avc1::from_sps_nal(&rest[..end.unwrap_or(rest.len())]);
+            }
+        }
+        Err(CodecError::MissingNalUnit(SPS_NAL_UNIT_TYPE))
+    }
+    pub fn profile(&self) -> u8 {
+        self.profile
+    }
+    pub fn constraints(&self) -> u8 {
+        self.constraints
+    }
+    pub fn level(&self) -> u8 {
+        self.level
+    }
+
+    pub fn constraint_set0_flag(&self) -> bool {
+        self.constraints & 0b1000_0000 != 0
+    }
+    pub fn constraint_set1_flag(&self) -> bool {
+        self.constraints & 0b0100_0000 != 0
+    }
+    pub fn constraint_set2_flag(&self) -> bool {
+        self.constraints & 0b0010_0000 != 0
+    }
+    pub fn constraint_set3_flag(&self) -> bool {
+        self.constraints & 0b0001_0000 != 0
+    }
+    pub fn constraint_set4_flag(&self) -> bool {
+        self.constraints & 0b0000_1000 != 0
+    }
+    pub fn constraint_set5_flag(&self) -> bool {
+        self.constraints & 0b0000_0100 != 0
+    }
+    /// The two least-significant bits of the constraints byte, which the H.264 spec requires to
+    /// be zero
+    pub fn reserved_zero_2bits(&self) -> u8 {
+        self.constraints & 0b0000_0011
+    }
+    /// Returns `true` if the reserved bits of the constraints byte are zero, as required by the
+    /// spec.
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::Codec;
+    /// # use std::str::FromStr;
+    /// if let Ok(Codec::Avc1(avc1)) = Codec::from_str("avc1.4D4101") {
+    ///     assert!(!avc1.reserved_zero_2bits_valid());
+    /// }
+    /// ```
+    pub fn reserved_zero_2bits_valid(&self) -> bool {
+        self.reserved_zero_2bits() == 0
+    }
+
+    /// Interprets the `profile_idc` value, also taking account of the constraint flags which
+    /// distinguish profiles sharing the same `profile_idc` (for example _Constrained Baseline_
+    /// from _Baseline_).
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::{Codec, AvcProfile};
+    /// # use std::str::FromStr;
+    /// if let Ok(Codec::Avc1(avc1)) = Codec::from_str("avc1.42E01E") {
+    ///     assert_eq!(avc1.profile_enum(), AvcProfile::ConstrainedBaseline);
+    /// }
+    /// ```
+    pub fn profile_enum(&self) -> AvcProfile {
+        let set1 = self.constraint_set1_flag();
+        let set3 = self.constraint_set3_flag();
+        let set4 = self.constraint_set4_flag();
+        let set5 = self.constraint_set5_flag();
+        match AvcProfile::from(self.profile) {
+            AvcProfile::Baseline if set1 => AvcProfile::ConstrainedBaseline,
+            AvcProfile::High if set4 && set5 => AvcProfile::ConstrainedHigh,
+            AvcProfile::High if set4 => AvcProfile::ProgressiveHigh,
+            AvcProfile::High10 if set3 => AvcProfile::High10Intra,
+            AvcProfile::High422 if set3 => AvcProfile::High422Intra,
+            AvcProfile::High444Predictive if set3 => AvcProfile::High444Intra,
+            profile => profile,
+        }
+    }
+
+    /// Interprets the `level_idc` value, also taking account of the `constraint_set3_flag` which
+    /// is used to signal level 1b in some profiles.  Returns `None` if the `level_idc` value is
+    /// not one defined by the H.264 spec.
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::{Codec, AvcLevel};
+    /// # use std::str::FromStr;
+    /// if let Ok(Codec::Avc1(avc1)) = Codec::from_str("avc1.640029") {
+    ///     assert_eq!(avc1.level_enum(), Some(AvcLevel::L4_1));
+    ///     assert!(avc1.level_enum().unwrap() > AvcLevel::L3_1);
+    /// }
+    /// ```
+    pub fn level_enum(&self) -> Option<AvcLevel> {
+        match self.level {
+            11 if self.constraint_set3_flag() && is_level_1b_via_constraint(self.profile) => {
+                Some(AvcLevel::L1b)
+            }
+            9 => Some(AvcLevel::L1b),
+            level_idc => AvcLevel::ALL
+                .iter()
+                .find(|l| **l != AvcLevel::L1b && l.level_idc() == level_idc)
+                .copied(),
+        }
+    }
+}
+
+/// In the Baseline, Main and Extended profiles, level 1b is signalled using `level_idc` 11 along
+/// with `constraint_set3_flag`, rather than with `level_idc` 9
+fn is_level_1b_via_constraint(profile_idc: u8) -> bool {
+    matches!(profile_idc, 66 | 77 | 88)
+}
+impl FromStr for Avc1 {
+    type Err = CodecError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if value.len() != 6 {
+            return Err(CodecError::UnexpectedLength {
+                expected: 6,
+                got: value.into(),
+            });
+        }
+        if !value.is_ascii() {
+            return Err(CodecError::InvalidComponent(value.into()));
+        }
+
+        let profile = u8::from_str_radix(&value[0..2], 16)
+            .map_err(|_| CodecError::InvalidComponent(value.into()))?;
+
+        let constraints = u8::from_str_radix(&value[2..4], 16)
+            .map_err(|_| CodecError::InvalidComponent(value.into()))?;
+
+        let level = u8::from_str_radix(&value[4..6], 16)
+            .map_err(|_| CodecError::InvalidComponent(value.into()))?;
+
+        Ok(Avc1 {
+            profile,
+            constraints,
+            level,
+        })
+    }
+}
+
+/// H.264 profiles, as identified by the `profile_idc` value.
+///
+/// Some profiles share a `profile_idc` value and are distinguished by the constraint flags, so
+/// conversion from `u8` will only produce the unconstrained variant (e.g. `Baseline` rather than
+/// `ConstrainedBaseline`).  Use [`Avc1::profile_enum()`] to take the constraint flags into
+/// account.
+///
+/// ```rust
+/// # use rfc6381_codec::AvcProfile;
+/// assert_eq!(AvcProfile::from(0x64), AvcProfile::High);
+/// assert_eq!(u8::from(AvcProfile::ConstrainedBaseline), 66);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum AvcProfile {
+    Baseline,
+    ConstrainedBaseline,
+    Main,
+    Extended,
+    High,
+    ProgressiveHigh,
+    ConstrainedHigh,
+    High10,
+    High10Intra,
+    High422,
+    High422Intra,
+    High444Predictive,
+    High444Intra,
+    Cavlc444Intra,
+    ScalableBaseline,
+    ScalableHigh,
+    MultiviewHigh,
+    StereoHigh,
+    /// A `profile_idc` value not covered by the other variants
+    Unknown(u8),
+}
+impl From<u8> for AvcProfile {
+    fn from(profile_idc: u8) -> Self {
+        match profile_idc {
+            66 => AvcProfile::Baseline,
+            77 => AvcProfile::Main,
+            88 => AvcProfile::Extended,
+            100 => AvcProfile::High,
+            110 => AvcProfile::High10,
+            122 => AvcProfile::High422,
+            244 => AvcProfile::High444Predictive,
+            44 => AvcProfile::Cavlc444Intra,
+            83 => AvcProfile::ScalableBaseline,
+            86 => AvcProfile::ScalableHigh,
+            118 => AvcProfile::MultiviewHigh,
+            128 => AvcProfile::StereoHigh,
+            _ => AvcProfile::Unknown(profile_idc),
+        }
+    }
+}
+impl From<AvcProfile> for u8 {
+    fn from(profile: AvcProfile) -> Self {
+        match profile {
+            AvcProfile::Baseline | AvcProfile::ConstrainedBaseline => 66,
+            AvcProfile::Main => 77,
+            AvcProfile::Extended => 88,
+            AvcProfile::High | AvcProfile::ProgressiveHigh | AvcProfile::ConstrainedHigh => 100,
+            AvcProfile::High10 | AvcProfile::High10Intra => 110,
+            AvcProfile::High422 | AvcProfile::High422Intra => 122,
+            AvcProfile::High444Predictive | AvcProfile::High444Intra => 244,
+            AvcProfile::Cavlc444Intra => 44,
+            AvcProfile::ScalableBaseline => 83,
+            AvcProfile::ScalableHigh => 86,
+            AvcProfile::MultiviewHigh => 118,
+            AvcProfile::StereoHigh => 128,
+            AvcProfile::Unknown(profile_idc) => profile_idc,
+        }
+    }
+}
+
+/// Displays the profile name as used in the H.264 spec, like `"Constrained Baseline"` or
+/// `"High 4:2:2"`
+impl fmt::Display for AvcProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            AvcProfile::Baseline => "Baseline",
+            AvcProfile::ConstrainedBaseline => "Constrained Baseline",
+            AvcProfile::Main => "Main",
+            AvcProfile::Extended => "Extended",
+            AvcProfile::High => "High",
+            AvcProfile::ProgressiveHigh => "Progressive High",
+            AvcProfile::ConstrainedHigh => "Constrained High",
+            AvcProfile::High10 => "High 10",
+            AvcProfile::High10Intra => "High 10 Intra",
+            AvcProfile::High422 => "High 4:2:2",
+            AvcProfile::High422Intra => "High 4:2:2 Intra",
+            AvcProfile::High444Predictive => "High 4:4:4 Predictive",
+            AvcProfile::High444Intra => "High 4:4:4 Intra",
+            AvcProfile::Cavlc444Intra => "CAVLC 4:4:4 Intra",
+            AvcProfile::ScalableBaseline => "Scalable Baseline",
+            AvcProfile::ScalableHigh => "Scalable High",
+            AvcProfile::MultiviewHigh => "Multiview High",
+            AvcProfile::StereoHigh => "Stereo High",
+            AvcProfile::Unknown(profile_idc) => return write!(f, "profile_idc {}", profile_idc),
+        };
+        f.write_str(name)
+    }
+}
+
+/// H.264 levels.
+///
+/// Comparing two levels tells you which imposes the higher limits.  Level 1b sorts between
+/// levels 1 and 1.1, which doesn't follow from its `level_idc` of `9` (or `11` with
+/// `constraint_set3_flag`, in the Baseline, Main and Extended profiles):
+///
+/// ```rust
+/// # use rfc6381_codec::AvcLevel;
+/// assert!(AvcLevel::L1b > AvcLevel::L1);
+/// assert!(AvcLevel::L1b < AvcLevel::L1_1);
+/// assert_eq!(AvcLevel::L4_1.to_string(), "4.1");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AvcLevel {
+    L1,
+    L1b,
+    L1_1,
+    L1_2,
+    L1_3,
+    L2_0,
+    L2_1,
+    L2_2,
+    L3_0,
+    L3_1,
+    L3_2,
+    L4_0,
+    L4_1,
+    L4_2,
+    L5_0,
+    L5_1,
+    L5_2,
+    L6_0,
+    L6_1,
+    L6_2,
+}
+impl AvcLevel {
+    /// All H.264 levels, from level 1 to level 6.2, with level 1b just after level 1
+    pub const ALL: [AvcLevel; 20] = [
+        AvcLevel::L1,
+        AvcLevel::L1b,
+        AvcLevel::L1_1,
+        AvcLevel::L1_2,
+        AvcLevel::L1_3,
+        AvcLevel::L2_0,
+        AvcLevel::L2_1,
+        AvcLevel::L2_2,
+        AvcLevel::L3_0,
+        AvcLevel::L3_1,
+        AvcLevel::L3_2,
+        AvcLevel::L4_0,
+        AvcLevel::L4_1,
+        AvcLevel::L4_2,
+        AvcLevel::L5_0,
+        AvcLevel::L5_1,
+        AvcLevel::L5_2,
+        AvcLevel::L6_0,
+        AvcLevel::L6_1,
+        AvcLevel::L6_2,
+    ];
+
+    /// The `level_idc` value for this level.
+    ///
+    /// Level 1b has two possible encodings depending on profile; this method gives the value `9`
+    /// used outside the Baseline, Main and Extended profiles.  [`Avc1::new()`] will choose the
+    /// appropriate encoding for the profile.
+    pub fn level_idc(&self) -> u8 {
+        match self {
+            AvcLevel::L1 => 10,
+            AvcLevel::L1b => 9,
+            AvcLevel::L1_1 => 11,
+            AvcLevel::L1_2 => 12,
+            AvcLevel::L1_3 => 13,
+            AvcLevel::L2_0 => 20,
+            AvcLevel::L2_1 => 21,
+            AvcLevel::L2_2 => 22,
+            AvcLevel::L3_0 => 30,
+            AvcLevel::L3_1 => 31,
+            AvcLevel::L3_2 => 32,
+            AvcLevel::L4_0 => 40,
+            AvcLevel::L4_1 => 41,
+            AvcLevel::L4_2 => 42,
+            AvcLevel::L5_0 => 50,
+            AvcLevel::L5_1 => 51,
+            AvcLevel::L5_2 => 52,
+            AvcLevel::L6_0 => 60,
+            AvcLevel::L6_1 => 61,
+            AvcLevel::L6_2 => 62,
+        }
+    }
+}
+impl AvcLevel {
+    /// The limits which the H.264 spec (Table A-1) imposes on streams at this level.
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::{AvcLevel, AvcProfile};
+    /// let limits = AvcLevel::L3_1.limits();
+    /// assert!(limits.supports_frame(1280, 720, 30.0));
+    /// assert!(!limits.supports_frame(1920, 1080, 30.0));
+    /// assert_eq!(limits.max_bitrate(AvcProfile::High), 17_500_000);
+    /// ```
+    pub fn limits(&self) -> AvcLevelLimits {
+        let (max_mbps, max_fs, max_dpb_mbs, max_br, max_cpb) = match self {
+            AvcLevel::L1 => (1485, 99, 396, 64, 175),
+            AvcLevel::L1b => (1485, 99, 396, 128, 350),
+            AvcLevel::L1_1 => (3000, 396, 900, 192, 500),
+            AvcLevel::L1_2 => (6000, 396, 2376, 384, 1000),
+            AvcLevel::L1_3 => (11880, 396, 2376, 768, 2000),
+            AvcLevel::L2_0 => (11880, 396, 2376, 2000, 2000),
+            AvcLevel::L2_1 => (19800, 792, 4752, 4000, 4000),
+            AvcLevel::L2_2 => (20250, 1620, 8100, 4000, 4000),
+            AvcLevel::L3_0 => (40500, 1620, 8100, 10000, 10000),
+            AvcLevel::L3_1 => (108000, 3600, 18000, 14000, 14000),
+            AvcLevel::L3_2 => (216000, 5120, 20480, 20000, 20000),
+            AvcLevel::L4_0 => (245760, 8192, 32768, 20000, 25000),
+            AvcLevel::L4_1 => (245760, 8192, 32768, 50000, 62500),
+            AvcLevel::L4_2 => (522240, 8704, 34816, 50000, 62500),
+            AvcLevel::L5_0 => (589824, 22080, 110400, 135000, 135000),
+            AvcLevel::L5_1 => (983040, 36864, 184320, 240000, 240000),
+            AvcLevel::L5_2 => (2073600, 36864, 184320, 240000, 240000),
+            AvcLevel::L6_0 => (4177920, 139264, 696320, 240000, 240000),
+            AvcLevel::L6_1 => (8355840, 139264, 696320, 480000, 480000),
+            AvcLevel::L6_2 => (16711680, 139264, 696320, 800000, 800000),
+        };
+        AvcLevelLimits {
+            max_mbps,
+            max_fs,
+            max_dpb_mbs,
+            max_br,
+            max_cpb,
+        }
+    }
+}
+
+/// Limits on stream parameters for a particular H.264 level, as given by
+/// [`AvcLevel::limits()`].
+///
+/// Values are expressed in macroblocks (16x16 luma samples), as in the H.264 spec.  The bitrate
+/// and buffer limits depend on the profile in use, so they are given by methods taking the
+/// profile as an argument.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AvcLevelLimits {
+    max_mbps: u32,
+    max_fs: u32,
+    max_dpb_mbs: u32,
+    max_br: u32,
+    max_cpb: u32,
+}
+impl AvcLevelLimits {
+    /// Maximum macroblock processing rate (`MaxMBPS`), in macroblocks per second
+    pub fn max_macroblocks_per_second(&self) -> u32 {
+        self.max_mbps
+    }
+    /// Maximum frame size (`MaxFS`), in macroblocks
+    pub fn max_frame_size(&self) -> u32 {
+        self.max_fs
+    }
+    /// Maximum decoded picture buffer size (`MaxDpbMbs`), in macroblocks
+    pub fn max_dpb_macroblocks(&self) -> u32 {
+        self.max_dpb_mbs
+    }
+    /// Maximum video bitrate for the VCL of a stream in the given profile, in bits per second
+    /// (i.e. `MaxBR` scaled by the profile's `cpbBrVclFactor`)
+    pub fn max_bitrate(&self, profile: AvcProfile) -> u64 {
+        u64::from(self.max_br) * u64::from(cpb_br_vcl_factor(profile))
+    }
+    /// Maximum coded picture buffer size for the VCL of a stream in the given profile, in bits
+    /// (i.e. `MaxCPB` scaled by the profile's `cpbBrVclFactor`)
+    pub fn max_cpb_size(&self, profile: AvcProfile) -> u64 {
+        u64::from(self.max_cpb) * u64::from(cpb_br_vcl_factor(profile))
+    }
+    /// Checks that frames of the given dimensions (in luma samples) and frame rate are within the
+    /// frame size and macroblock processing rate limits of this level.
+    ///
+    /// This also applies the spec's constraint that neither the frame width nor height, in
+    /// macroblocks, may exceed `Sqrt(MaxFS * 8)`.
+    pub fn supports_frame(&self, width: u32, height: u32, frame_rate: f64) -> bool {
+        let width_mbs = u64::from(width.div_ceil(16));
+        let height_mbs = u64::from(height.div_ceil(16));
+        let frame_mbs = width_mbs * height_mbs;
+        let max_dimension = u64::from(self.max_fs) * 8;
+        frame_mbs <= u64::from(self.max_fs)
+            && width_mbs * width_mbs <= max_dimension
+            && height_mbs * height_mbs <= max_dimension
+            && frame_mbs as f64 * frame_rate <= f64::from(self.max_mbps)
+    }
+}
+
+/// The `cpbBrVclFactor` from H.264 Table A-2
+fn cpb_br_vcl_factor(profile: AvcProfile) -> u32 {
+    match profile {
+        AvcProfile::High
+        | AvcProfile::ProgressiveHigh
+        | AvcProfile::ConstrainedHigh
+        | AvcProfile::MultiviewHigh
+        | AvcProfile::StereoHigh
+        | AvcProfile::ScalableHigh => 1250,
+        AvcProfile::High10 | AvcProfile::High10Intra => 3000,
+        AvcProfile::High422
+        | AvcProfile::High422Intra
+        | AvcProfile::High444Predictive
+        | AvcProfile::High444Intra
+        | AvcProfile::Cavlc444Intra => 4000,
+        _ => 1000,
+    }
+}
+
+/// Displays the level number as used in the H.264 spec, like `"1b"`, `"3.0"` or `"4.1"`
+impl fmt::Display for AvcLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AvcLevel::L1 => f.write_str("1"),
+            AvcLevel::L1b => f.write_str("1b"),
+            _ => {
+                let idc = self.level_idc();
+                write!(f, "{}.{}", idc / 10, idc % 10)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Codec;
+    use assert_matches::*;
+
+    fn profile(codec: &str) -> AvcProfile {
+        match Codec::from_str(codec) {
+            Ok(Codec::Avc1(avc1)) => avc1.profile_enum(),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn profile_enum() {
+        assert_eq!(profile("avc1.42001E"), AvcProfile::Baseline);
+        assert_eq!(profile("avc1.42E01E"), AvcProfile::ConstrainedBaseline);
+        assert_eq!(profile("avc1.4D401E"), AvcProfile::Main);
+        assert_eq!(profile("avc1.640028"), AvcProfile::High);
+        assert_eq!(profile("avc1.640828"), AvcProfile::ProgressiveHigh);
+        assert_eq!(profile("avc1.640C28"), AvcProfile::ConstrainedHigh);
+        assert_eq!(profile("avc1.6E0028"), AvcProfile::High10);
+        assert_eq!(profile("avc1.6E1028"), AvcProfile::High10Intra);
+        assert_eq!(profile("avc1.FF0028"), AvcProfile::Unknown(0xff));
+    }
+
+    #[test]
+    fn from_avcc() {
+        let avc1 = Avc1::from_avcc(&[0x01, 0x42, 0xc0, 0x1e]).unwrap();
+        assert_eq!(avc1.profile_enum(), AvcProfile::ConstrainedBaseline);
+        assert_eq!(avc1.level_enum(), Some(AvcLevel::L3_0));
+        assert_matches!(
+            Avc1::from_avcc(&[0x01, 0x42, 0xc0]),
+            Err(CodecError::ConfigurationRecordTooShort {
+                expected: 4,
+                got: 3
+            })
+        );
+        assert_matches!(
+            Avc1::from_avcc(&[0x00, 0x42, 0xc0, 0x1e]),
+            Err(CodecError::UnsupportedConfigurationVersion(0))
+        );
+    }
+
+    #[test]
+    fn from_sps_nal() {
+        let avc1 = Avc1::from_sps_nal(&[0x27, 0x4d, 0x40, 0x1e, 0x9a]).unwrap();
+        assert_eq!(avc1.profile_enum(), AvcProfile::Main);
+        assert_eq!(avc1.level_enum(), Some(AvcLevel::L3_0));
+        assert_matches!(
+            Avc1::from_sps_nal(&[0x68, 0xeb, 0xe3, 0xcb]),
+            Err(CodecError::UnexpectedNalUnitType(8))
+        );
+        assert_matches!(
+            Avc1::from_sps_nal(&[0x67, 0x4d]),
+            Err(CodecError::ConfigurationRecordTooShort { .. })
+        );
+    }
+
+    #[test]
+    fn from_annex_b() {
+        let sps = [0x67, 0x64, 0x00, 0x28, 0xac, 0xd9];
+        let avc1 = Avc1::from_annex_b(&[&[0, 0, 1][..], &sps].concat()).unwrap();
+        assert_eq!(avc1.profile_enum(), AvcProfile::High);
+        // a slice NAL unit containing what looks like SPS data, after an emulation
+        // prevention byte
+        let stream = [
+            0, 0, 1, 0x65, 0x88, 0, 0, 3, 0x67, 0x42, 0, 0, 0, 1, 0x67, 0x42, 0xc0, 0x1e,
+        ];
+        let avc1 = Avc1::from_annex_b(&stream).unwrap();
+        assert_eq!(Codec::Avc1(avc1).to_string(), "avc1.42C01E");
+        assert_matches!(
+            Avc1::from_annex_b(&[0, 0, 1, 0x68, 0xee, 0x3c, 0x80]),
+            Err(CodecError::MissingNalUnit(7))
+        );
+        assert_matches!(
+            Avc1::from_annex_b(&[0, 0, 1, 0x67, 0x64]),
+            Err(CodecError::ConfigurationRecordTooShort { .. })
+        );
+    }
+
+    #[test]
+    fn profile_roundtrip() {
+        for v in 0..=255u8 {
+            assert_eq!(v, u8::from(AvcProfile::from(v)));
+        }
+    }
+
+    fn level(codec: &str) -> Option<AvcLevel> {
+        match Codec::from_str(codec) {
+            Ok(Codec::Avc1(avc1)) => avc1.level_enum(),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn level_enum() {
+        assert_eq!(level("avc1.42E00A"), Some(AvcLevel::L1));
+        assert_eq!(level("avc1.42E00B"), Some(AvcLevel::L1_1));
+        assert_eq!(level("avc1.42F00B"), Some(AvcLevel::L1b));
+        assert_eq!(level("avc1.64100B"), Some(AvcLevel::L1_1));
+        assert_eq!(level("avc1.640009"), Some(AvcLevel::L1b));
+        assert_eq!(level("avc1.64001E"), Some(AvcLevel::L3_0));
+        assert_eq!(level("avc1.640034"), Some(AvcLevel::L5_2));
+        assert_eq!(level("avc1.640017"), None);
+    }
+
+    #[test]
+    fn new() {
+        for profile in &[
+            AvcProfile::Main,
+            AvcProfile::High,
+            AvcProfile::ConstrainedBaseline,
+        ] {
+            for l in AvcLevel::ALL.iter() {
+                let avc1 = Avc1::new(*profile, 0, *l);
+                assert_eq!(avc1.level_enum(), Some(*l));
+                assert_eq!(avc1.profile_enum(), *profile);
+            }
+        }
+        assert_eq!(
+            Codec::Avc1(Avc1::new(AvcProfile::Main, 0x40, AvcLevel::L1b)).to_string(),
+            "avc1.4D500B"
+        );
+        assert_eq!(
+            Codec::Avc1(Avc1::new(AvcProfile::High, 0, AvcLevel::L1b)).to_string(),
+            "avc1.640009"
+        );
+    }
+
+    #[test]
+    fn level_order() {
+        let mut sorted = AvcLevel::ALL;
+        sorted.sort();
+        assert_eq!(sorted, AvcLevel::ALL);
+        assert_eq!(AvcLevel::L3_0.to_string(), "3.0");
+        assert_eq!(AvcLevel::L1.to_string(), "1");
+    }
+
+    #[test]
+    fn constraint_flags() {
+        let avc1 = Avc1 {
+            profile: 66,
+            constraints: 0b1010_1001,
+            level: 30,
+        };
+        assert!(avc1.constraint_set0_flag());
+        assert!(!avc1.constraint_set1_flag());
+        assert!(avc1.constraint_set2_flag());
+        assert!(!avc1.constraint_set3_flag());
+        assert!(avc1.constraint_set4_flag());
+        assert!(!avc1.constraint_set5_flag());
+        assert_eq!(avc1.reserved_zero_2bits(), 1);
+        assert!(!avc1.reserved_zero_2bits_valid());
+        assert!(Avc1::new(AvcProfile::Main, 0x40, AvcLevel::L3_0).reserved_zero_2bits_valid());
+    }
+
+    #[test]
+    fn limits() {
+        let l4_1 = AvcLevel::L4_1.limits();
+        assert!(l4_1.supports_frame(1920, 1080, 30.0));
+        assert!(!l4_1.supports_frame(1920, 1080, 60.0));
+        assert!(AvcLevel::L4_2.limits().supports_frame(1920, 1080, 60.0));
+        assert_eq!(l4_1.max_frame_size(), 8192);
+        assert_eq!(l4_1.max_bitrate(AvcProfile::Main), 50_000_000);
+        assert_eq!(l4_1.max_bitrate(AvcProfile::High), 62_500_000);
+        assert_eq!(l4_1.max_cpb_size(AvcProfile::High10), 187_500_000);
+        // within MaxFS, but too wide
+        assert!(!AvcLevel::L3_0.limits().supports_frame(1920, 128, 1.0));
+        for pair in AvcLevel::ALL.windows(2) {
+            let (a, b) = (pair[0].limits(), pair[1].limits());
+            assert!(a.max_macroblocks_per_second() <= b.max_macroblocks_per_second());
+            assert!(a.max_dpb_macroblocks() <= b.max_dpb_macroblocks());
+        }
+    }
+
+    #[test]
+    fn non_ascii() {
+        // 6 bytes long, but with a multibyte character in place of the constraints and level
+        assert_matches!(
+            Codec::from_str("avc1.4D👍"),
+            Err(CodecError::InvalidComponent(_))
+        );
+    }
+}
@@ -0,0 +1,87 @@
+//! Support for AVS3 video and audio codec strings.
+
+use crate::{parse_hex_element, CodecError};
+use four_cc::FourCC;
+use mp4ra_rust::SampleEntryCode;
+use std::fmt;
+use std::str::FromStr;
+
+pub(crate) const AV3A: SampleEntryCode = SampleEntryCode(FourCC(*b"av3a"));
+
+/// The `avs3` video codec parameter, giving the `profile_id` and `level_id` from the AVS3
+/// sequence header as two-digit hexadecimal elements (for example `avs3.22.20`).
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Avs3 {
+    pub(crate) profile_id: u8,
+    pub(crate) level_id: u8,
+}
+impl Avs3 {
+    pub const fn new(profile_id: u8, level_id: u8) -> Avs3 {
+        Avs3 {
+            profile_id,
+            level_id,
+        }
+    }
+    pub fn profile_id(&self) -> u8 {
+        self.profile_id
+    }
+    pub fn level_id(&self) -> u8 {
+        self.level_id
+    }
+}
+impl FromStr for Avs3 {
+    type Err = CodecError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut i = value.splitn(2, '.');
+        let profile_id = parse_hex_element(i.next().unwrap())?;
+        let level_id = i
+            .next()
+            .ok_or_else(|| CodecError::ExpectedHierarchySeparator(value.into()))?;
+        let level_id = parse_hex_element(level_id)?;
+        Ok(Avs3 {
+            profile_id,
+            level_id,
+        })
+    }
+}
+impl fmt::Display for Avs3 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02X}.{:02X}", self.profile_id, self.level_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Codec;
+    use assert_matches::*;
+
+    #[test]
+    fn avs3() {
+        assert_matches!(
+            Codec::from_str("avs3.22.20"),
+            Ok(Codec::Avs3(Avs3 {
+                profile_id: 0x22,
+                level_id: 0x20
+            }))
+        );
+        assert_eq!(
+            "avs3.32.6A",
+            Codec::from_str("avs3.32.6A").unwrap().to_string()
+        );
+        assert_matches!(Codec::from_str("avs3"), Err(_));
+        assert_matches!(Codec::from_str("avs3.22"), Err(_));
+        assert_matches!(
+            Codec::from_str("avs3.2.20"),
+            Err(CodecError::UnexpectedLength { expected: 2, .. })
+        );
+    }
+
+    #[test]
+    fn av3a() {
+        assert_matches!(Codec::from_str("av3a"), Ok(Codec::Av3a));
+        assert_eq!("av3a", Codec::from_str("av3a").unwrap().to_string());
+        assert_matches!(Codec::from_str("av3a.1"), Err(_));
+    }
+}
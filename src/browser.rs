@@ -0,0 +1,153 @@
+//! A static table of Media Source Extensions support in the major browser engines.
+
+use crate::{Codec, Mp4a};
+use mpeg4_audio_const::AudioObjectType;
+
+/// A browser, identified by its engine's best-known desktop release.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Browser {
+    /// Chrome, and other Chromium-based browsers using the same version numbers
+    Chrome,
+    /// Firefox, using the Gecko engine
+    Firefox,
+    /// Safari, using the WebKit engine
+    Safari,
+}
+
+/// How a particular browser supports a codec through Media Source Extensions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EngineSupport {
+    /// Supported from the given major version onwards.  Support may still depend on the
+    /// platform or on hardware decoding being available.
+    Since(u32),
+    /// Not supported in any release
+    Unsupported,
+    /// The table has no information for this codec
+    Unknown,
+}
+
+/// The per-browser support for a codec, as given by [`Codec::browser_support()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BrowserSupport {
+    chrome: EngineSupport,
+    firefox: EngineSupport,
+    safari: EngineSupport,
+}
+impl BrowserSupport {
+    /// The support in the given browser
+    pub fn get(&self, browser: Browser) -> EngineSupport {
+        match browser {
+            Browser::Chrome => self.chrome,
+            Browser::Firefox => self.firefox,
+            Browser::Safari => self.safari,
+        }
+    }
+    /// Answers whether the given version of the browser is expected to accept the codec, or
+    /// `None` if the table has no information.
+    pub fn is_supported(&self, browser: Browser, version: u32) -> Option<bool> {
+        match self.get(browser) {
+            EngineSupport::Since(since) => Some(version >= since),
+            EngineSupport::Unsupported => Some(false),
+            EngineSupport::Unknown => None,
+        }
+    }
+}
+
+const fn since(chrome: Option<u32>, firefox: Option<u32>, safari: Option<u32>) -> BrowserSupport {
+    const fn support(v: Option<u32>) -> EngineSupport {
+        match v {
+            Some(v) => EngineSupport::Since(v),
+            None => EngineSupport::Unsupported,
+        }
+    }
+    BrowserSupport {
+        chrome: support(chrome),
+        firefox: support(firefox),
+        safari: support(safari),
+    }
+}
+
+const UNKNOWN: BrowserSupport = BrowserSupport {
+    chrome: EngineSupport::Unknown,
+    firefox: EngineSupport::Unknown,
+    safari: EngineSupport::Unknown,
+};
+
+impl Codec {
+    /// Looks up whether each major browser is expected to accept this codec in
+    /// `MediaSource.isTypeSupported()`, in the container usually used with the codec.
+    ///
+    /// The table is compiled from browser release notes and gives the first desktop release
+    /// with support; it doesn't account for profiles and levels (beyond distinguishing the
+    /// AAC and MP3 flavours of `mp4a`) or for platform-specific restrictions, so treat it as a
+    /// guide rather than a substitute for feature detection.
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::{Browser, Codec, EngineSupport};
+    /// # use std::str::FromStr;
+    /// let support = Codec::from_str("avc1.4D401E").unwrap().browser_support();
+    /// assert_eq!(support.get(Browser::Chrome), EngineSupport::Since(31));
+    /// assert_eq!(support.is_supported(Browser::Firefox, 40), Some(false));
+    /// ```
+    pub fn browser_support(&self) -> BrowserSupport {
+        match self {
+            Codec::Avc1(_) => since(Some(31), Some(42), Some(8)),
+            #[cfg(feature = "hevc")]
+            // Firefox accepts HEVC only on some platforms, and not from a single release
+            Codec::Hevc(_) => BrowserSupport {
+                firefox: EngineSupport::Unknown,
+                ..since(Some(107), None, Some(11))
+            },
+            #[cfg(feature = "av1")]
+            Codec::Av01(_) => since(Some(70), Some(67), Some(17)),
+            #[cfg(feature = "vp9")]
+            Codec::Vp09(_) => since(Some(31), Some(42), Some(14)),
+            Codec::Mp4a(mp4a) if mp4a.is_mp3() => since(Some(51), Some(42), Some(8)),
+            Codec::Mp4a(Mp4a::Mpeg4Audio {
+                audio_object_type:
+                    Some(AudioObjectType::AAC_LC | AudioObjectType::SBR | AudioObjectType::PS),
+            }) => since(Some(31), Some(42), Some(8)),
+            Codec::Mp3 => since(Some(51), Some(42), Some(8)),
+            Codec::Opus => since(Some(31), Some(42), Some(15)),
+            Codec::Flac => since(Some(62), Some(51), None),
+            #[cfg(feature = "dolby")]
+            Codec::Ac3 | Codec::Eac3 => since(None, None, Some(10)),
+            _ => UNKNOWN,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn support(codec: &str) -> BrowserSupport {
+        Codec::from_str(codec).unwrap().browser_support()
+    }
+
+    #[test]
+    fn lookup() {
+        let s = support("avc1.4D401E");
+        assert_eq!(s.is_supported(Browser::Chrome, 31), Some(true));
+        assert_eq!(s.is_supported(Browser::Chrome, 30), Some(false));
+        assert_eq!(s.get(Browser::Safari), EngineSupport::Since(8));
+        #[cfg(feature = "hevc")]
+        {
+            let s = support("hvc1.1.6.L93.B0");
+            assert_eq!(s.get(Browser::Firefox), EngineSupport::Unknown);
+            assert_eq!(s.is_supported(Browser::Firefox, 200), None);
+        }
+        assert_eq!(support("mp4a.40.5"), support("mp4a.40.2"));
+        assert_eq!(support("mp4a.6B"), support("mp3"));
+        #[cfg(feature = "dolby")]
+        assert_eq!(
+            support("ec-3").get(Browser::Chrome),
+            EngineSupport::Unsupported
+        );
+        let s = support("mp4a.40.42");
+        assert_eq!(s.get(Browser::Chrome), EngineSupport::Unknown);
+        assert_eq!(s.is_supported(Browser::Safari, 17), None);
+        assert_eq!(support("vvc1.1.L51.CQA"), UNKNOWN);
+    }
+}
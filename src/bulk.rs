@@ -0,0 +1,103 @@
+//! Parsing large numbers of codec strings, such as when scanning manifests or logs.
+
+use crate::{Avc1, Codec, CodecError, Mp4a};
+
+impl Codec {
+    /// Parses each of the given codec strings, giving results in the same order.
+    ///
+    /// The results are the same as parsing each value with [`FromStr`](std::str::FromStr), but
+    /// the most common values (`avc1` and AAC-LC `mp4a.40.2`, along with the tokens like `ec-3`
+    /// and `Opus` which have no sub-elements) are recognised by a fast path before falling back
+    /// to the general parser.  No allocation is made for each item, except to hold the text of
+    /// [`Codec::Other`] values.
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::Codec;
+    /// let log_lines = ["avc1.640028", "mp4a.40.2", "avc1.4D401E", "hvc1.1.6.L93.B0"];
+    /// let parsed: Vec<_> = Codec::parse_many(log_lines.iter().copied()).collect();
+    /// assert_eq!(parsed.len(), 4);
+    /// assert!(parsed.iter().all(Result::is_ok));
+    /// ```
+    pub fn parse_many<'a, I>(values: I) -> impl Iterator<Item = Result<Codec, CodecError>> + 'a
+    where
+        I: IntoIterator<Item = &'a str>,
+        I::IntoIter: 'a,
+    {
+        values
+            .into_iter()
+            .map(|value| fast_path(value).map_or_else(|| value.parse(), Ok))
+    }
+}
+
+/// Recognises a few very common, fixed-format values without the general parser's sample entry
+/// lookups, returning `None` for anything else (including malformed input, which the general
+/// parser then reports).
+fn fast_path(value: &str) -> Option<Codec> {
+    match value.as_bytes() {
+        [b'a', b'v', b'c', b'1', b'.', hex @ ..] if hex.len() == 6 => Some(Codec::Avc1(Avc1 {
+            profile: hex_byte(hex[0], hex[1])?,
+            constraints: hex_byte(hex[2], hex[3])?,
+            level: hex_byte(hex[4], hex[5])?,
+        })),
+        b"mp4a.40.2" => Some(Codec::Mp4a(Mp4a::aac_lc())),
+        b"Opus" | b"opus" => Some(Codec::Opus),
+        b"fLaC" | b"flac" => Some(Codec::Flac),
+        #[cfg(feature = "dolby")]
+        b"ac-3" => Some(Codec::Ac3),
+        #[cfg(feature = "dolby")]
+        b"ec-3" => Some(Codec::Eac3),
+        #[cfg(feature = "text")]
+        b"wvtt" => Some(Codec::Wvtt),
+        _ => None,
+    }
+}
+
+fn hex_byte(high: u8, low: u8) -> Option<u8> {
+    Some(hex_digit(high)? << 4 | hex_digit(low)?)
+}
+
+fn hex_digit(digit: u8) -> Option<u8> {
+    match digit {
+        b'0'..=b'9' => Some(digit - b'0'),
+        b'a'..=b'f' => Some(digit - b'a' + 10),
+        b'A'..=b'F' => Some(digit - b'A' + 10),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_as_from_str() {
+        let values = [
+            "avc1.640028",
+            "avc1.4d401e",
+            "avc1.4D40ZZ",
+            "avc1.+14D1E",
+            "avc1.4D401",
+            "mp4a.40.2",
+            "mp4a.40.02",
+            "mp4a.40.5",
+            "Opus",
+            "opus",
+            "flac",
+            "ac-3",
+            "ec-3",
+            "wvtt",
+            "vorbis",
+            "hvc1.1.6.L93.B0",
+            "",
+        ];
+        let parsed: Vec<_> = Codec::parse_many(values.iter().copied()).collect();
+        for (value, result) in values.iter().zip(parsed) {
+            assert_eq!(
+                format!("{:?}", result),
+                format!("{:?}", value.parse::<Codec>()),
+                "{}",
+                value
+            );
+        }
+    }
+}
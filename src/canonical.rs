@@ -0,0 +1,112 @@
+//! Canonical string forms of codec values.
+
+#[cfg(feature = "av1")]
+use crate::Av1ColorConfig;
+use crate::Codec;
+#[cfg(feature = "vp9")]
+use crate::Vp09;
+
+impl Codec {
+    /// Produces the preferred string form of this codec value, so that values which differ only
+    /// in how they were written can be compared or deduplicated.
+    ///
+    /// The `Display` implementation already normalises most elements (for example always using
+    /// uppercase hexadecimal for `avc1`, and dropping trailing zero constraint bytes for
+    /// `hvc1`).  In addition, the canonical form,
+    ///
+    ///  - writes the `mp4a` object-type-indication in uppercase hexadecimal, as in `mp4a.6B`
+    ///  - omits the optional `av01` colour elements where they only repeat the default values
+    ///  - omits the optional `vp09` elements where they only repeat the default values
+    ///
+    /// [`Codec::Other`] values are returned unchanged, since this crate doesn't know their
+    /// syntax.
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::Codec;
+    /// # use std::str::FromStr;
+    /// let canonical = |s| Codec::from_str(s).unwrap().to_canonical_string();
+    /// assert_eq!(canonical("avc1.4d401f"), canonical("avc1.4D401F"));
+    /// assert_eq!(canonical("mp4a.6b"), "mp4a.6B");
+    /// # #[cfg(feature = "av1")]
+    /// assert_eq!(canonical("av01.0.04M.08.0.110.01.01.01.0"), "av01.0.04M.08");
+    /// ```
+    pub fn to_canonical_string(&self) -> String {
+        match self {
+            Codec::Mp4a(mp4a) => {
+                let value = mp4a.to_string();
+                let (oti, rest) = value.split_at(value.find('.').unwrap_or(value.len()));
+                format!("mp4a.{}{}", oti.to_ascii_uppercase(), rest)
+            }
+            #[cfg(feature = "av1")]
+            Codec::Av01(av01) if av01.color_config() == Some(&Av1ColorConfig::default()) => {
+                format!(
+                    "av01.{}.{:02}{}.{:02}",
+                    av01.profile(),
+                    av01.level(),
+                    av01.tier(),
+                    av01.bit_depth()
+                )
+            }
+            #[cfg(feature = "vp9")]
+            Codec::Vp09(vp09) => format!("vp09.{}", without_default_elements(vp09)),
+            _ => self.to_string(),
+        }
+    }
+}
+
+#[cfg(feature = "vp9")]
+fn without_default_elements(vp09: &Vp09) -> Vp09 {
+    let default = Vp09::new(vp09.profile(), vp09.level(), vp09.bit_depth());
+    let all_default = vp09.chroma_subsampling() == default.chroma_subsampling()
+        && vp09.color_primaries() == default.color_primaries()
+        && vp09.transfer_characteristics() == default.transfer_characteristics()
+        && vp09.matrix_coefficients() == default.matrix_coefficients()
+        && vp09.video_full_range_flag() == default.video_full_range_flag();
+    if all_default {
+        default
+    } else {
+        vp09.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Codec;
+    use std::str::FromStr;
+
+    fn canonical(codec: &str) -> String {
+        Codec::from_str(codec).unwrap().to_canonical_string()
+    }
+
+    #[test]
+    fn canonical_forms() {
+        assert_eq!(canonical("avc1.64001f"), "avc1.64001F");
+        assert_eq!(canonical("mp4a.40.2"), "mp4a.40.2");
+        assert_eq!(canonical("mp4a.40"), "mp4a.40");
+        assert_eq!(canonical("mp4a.6b"), "mp4a.6B");
+        assert_eq!(canonical("mp4a.a5.1"), "mp4a.A5.1");
+        assert_eq!(canonical("opus"), "Opus");
+        #[cfg(feature = "hevc")]
+        assert_eq!(canonical("hvc1.1.6.L93.B0.0.0"), "hvc1.1.6.L93.B0");
+        #[cfg(feature = "av1")]
+        assert_eq!(
+            canonical("av01.0.04M.10.0.110.09.16.09.0"),
+            "av01.0.04M.10.0.110.09.16.09.0"
+        );
+        #[cfg(feature = "av1")]
+        assert_eq!(canonical("av01.0.04M.10.0.110.01.01.01.0"), "av01.0.04M.10");
+        #[cfg(feature = "vp9")]
+        assert_eq!(canonical("vp09.00.10.08.01.01.01.01.00"), "vp09.00.10.08");
+        #[cfg(feature = "vp9")]
+        assert_eq!(
+            canonical("vp09.02.10.10.01.09.16.09.00"),
+            "vp09.02.10.10.01.09.16.09.00"
+        );
+        #[cfg(feature = "vp9")]
+        assert_eq!(
+            canonical("vp09.00.10.08.01.01.01.01.01"),
+            "vp09.00.10.08.01.01.01.01.01"
+        );
+        assert_eq!(canonical("badd.41"), "badd.41");
+    }
+}
@@ -0,0 +1,309 @@
+//! Colour description code points, as defined by ISO/IEC 23091-2 (also published as
+//! ITU-T H.273), and shared by several video codecs' codec strings.
+//!
+//! Each type has a `Reserved` variant for code points the specification doesn't assign.
+//! Values are compared, ordered and hashed by their code point, so that a `Reserved` value
+//! holding an assigned code point (like `ColorPrimaries::Reserved(1)`) is equal to the named
+//! variant (`ColorPrimaries::Bt709`), matching their identical codec string form.  Converting
+//! with `From<u8>` never produces such values.
+
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+
+/// Implements `PartialEq`, `Eq`, `PartialOrd`, `Ord` and `Hash` by comparing the code point
+/// values
+macro_rules! code_point_eq {
+    ($t:ident) => {
+        impl PartialEq for $t {
+            fn eq(&self, other: &Self) -> bool {
+                u8::from(*self) == u8::from(*other)
+            }
+        }
+        impl Eq for $t {}
+        impl PartialOrd for $t {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+        impl Ord for $t {
+            fn cmp(&self, other: &Self) -> Ordering {
+                u8::from(*self).cmp(&u8::from(*other))
+            }
+        }
+        impl Hash for $t {
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                u8::from(*self).hash(state)
+            }
+        }
+    };
+}
+
+/// The `ColourPrimaries` code point.
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub enum ColorPrimaries {
+    Bt709,
+    Unspecified,
+    Bt470M,
+    Bt470Bg,
+    /// SMPTE 170M, as used for BT.601 525-line video
+    Bt601,
+    Smpte240M,
+    GenericFilm,
+    Bt2020,
+    /// SMPTE ST 428-1 (CIE 1931 XYZ)
+    Xyz,
+    /// SMPTE RP 431-2 (DCI-P3)
+    Smpte431,
+    /// SMPTE EG 432-1 (Display P3)
+    Smpte432,
+    Ebu3213,
+    /// A value reserved for future use, which should not be one of the code points given a
+    /// variant of its own
+    Reserved(u8),
+}
+code_point_eq!(ColorPrimaries);
+impl From<u8> for ColorPrimaries {
+    fn from(v: u8) -> Self {
+        match v {
+            1 => ColorPrimaries::Bt709,
+            2 => ColorPrimaries::Unspecified,
+            4 => ColorPrimaries::Bt470M,
+            5 => ColorPrimaries::Bt470Bg,
+            6 => ColorPrimaries::Bt601,
+            7 => ColorPrimaries::Smpte240M,
+            8 => ColorPrimaries::GenericFilm,
+            9 => ColorPrimaries::Bt2020,
+            10 => ColorPrimaries::Xyz,
+            11 => ColorPrimaries::Smpte431,
+            12 => ColorPrimaries::Smpte432,
+            22 => ColorPrimaries::Ebu3213,
+            _ => ColorPrimaries::Reserved(v),
+        }
+    }
+}
+impl From<ColorPrimaries> for u8 {
+    fn from(v: ColorPrimaries) -> Self {
+        match v {
+            ColorPrimaries::Bt709 => 1,
+            ColorPrimaries::Unspecified => 2,
+            ColorPrimaries::Bt470M => 4,
+            ColorPrimaries::Bt470Bg => 5,
+            ColorPrimaries::Bt601 => 6,
+            ColorPrimaries::Smpte240M => 7,
+            ColorPrimaries::GenericFilm => 8,
+            ColorPrimaries::Bt2020 => 9,
+            ColorPrimaries::Xyz => 10,
+            ColorPrimaries::Smpte431 => 11,
+            ColorPrimaries::Smpte432 => 12,
+            ColorPrimaries::Ebu3213 => 22,
+            ColorPrimaries::Reserved(v) => v,
+        }
+    }
+}
+
+/// The `TransferCharacteristics` code point.
+///
+/// ```rust
+/// # use rfc6381_codec::TransferCharacteristics;
+/// assert_eq!(TransferCharacteristics::from(16), TransferCharacteristics::Pq);
+/// assert!(TransferCharacteristics::Pq.is_hdr());
+/// assert!(!TransferCharacteristics::Bt709.is_hdr());
+/// ```
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub enum TransferCharacteristics {
+    Bt709,
+    Unspecified,
+    /// Assumed display gamma 2.2, as for BT.470 System M
+    Bt470M,
+    /// Assumed display gamma 2.8, as for BT.470 System B, G
+    Bt470Bg,
+    /// SMPTE 170M, as used for BT.601 525-line video
+    Bt601,
+    Smpte240M,
+    Linear,
+    /// Logarithmic transfer characteristic with a 100:1 range
+    Log100,
+    /// Logarithmic transfer characteristic with a 100 * Sqrt(10):1 range
+    Log100Sqrt10,
+    /// IEC 61966-2-4 (xvYCC)
+    Iec61966_2_4,
+    Bt1361,
+    /// IEC 61966-2-1 (sRGB)
+    Srgb,
+    Bt2020_10Bit,
+    Bt2020_12Bit,
+    /// SMPTE ST 2084, the perceptual quantizer used by HDR10 and Dolby Vision
+    Pq,
+    /// SMPTE ST 428-1
+    Smpte428,
+    /// ARIB STD-B67 hybrid log-gamma
+    Hlg,
+    /// A value reserved for future use, which should not be one of the code points given a
+    /// variant of its own
+    Reserved(u8),
+}
+code_point_eq!(TransferCharacteristics);
+impl TransferCharacteristics {
+    /// Returns `true` for the high dynamic range transfer characteristics, [`Pq`](Self::Pq)
+    /// and [`Hlg`](Self::Hlg).
+    pub fn is_hdr(&self) -> bool {
+        matches!(
+            TransferCharacteristics::from(u8::from(*self)),
+            TransferCharacteristics::Pq | TransferCharacteristics::Hlg
+        )
+    }
+}
+impl From<u8> for TransferCharacteristics {
+    fn from(v: u8) -> Self {
+        match v {
+            1 => TransferCharacteristics::Bt709,
+            2 => TransferCharacteristics::Unspecified,
+            4 => TransferCharacteristics::Bt470M,
+            5 => TransferCharacteristics::Bt470Bg,
+            6 => TransferCharacteristics::Bt601,
+            7 => TransferCharacteristics::Smpte240M,
+            8 => TransferCharacteristics::Linear,
+            9 => TransferCharacteristics::Log100,
+            10 => TransferCharacteristics::Log100Sqrt10,
+            11 => TransferCharacteristics::Iec61966_2_4,
+            12 => TransferCharacteristics::Bt1361,
+            13 => TransferCharacteristics::Srgb,
+            14 => TransferCharacteristics::Bt2020_10Bit,
+            15 => TransferCharacteristics::Bt2020_12Bit,
+            16 => TransferCharacteristics::Pq,
+            17 => TransferCharacteristics::Smpte428,
+            18 => TransferCharacteristics::Hlg,
+            _ => TransferCharacteristics::Reserved(v),
+        }
+    }
+}
+impl From<TransferCharacteristics> for u8 {
+    fn from(v: TransferCharacteristics) -> Self {
+        match v {
+            TransferCharacteristics::Bt709 => 1,
+            TransferCharacteristics::Unspecified => 2,
+            TransferCharacteristics::Bt470M => 4,
+            TransferCharacteristics::Bt470Bg => 5,
+            TransferCharacteristics::Bt601 => 6,
+            TransferCharacteristics::Smpte240M => 7,
+            TransferCharacteristics::Linear => 8,
+            TransferCharacteristics::Log100 => 9,
+            TransferCharacteristics::Log100Sqrt10 => 10,
+            TransferCharacteristics::Iec61966_2_4 => 11,
+            TransferCharacteristics::Bt1361 => 12,
+            TransferCharacteristics::Srgb => 13,
+            TransferCharacteristics::Bt2020_10Bit => 14,
+            TransferCharacteristics::Bt2020_12Bit => 15,
+            TransferCharacteristics::Pq => 16,
+            TransferCharacteristics::Smpte428 => 17,
+            TransferCharacteristics::Hlg => 18,
+            TransferCharacteristics::Reserved(v) => v,
+        }
+    }
+}
+
+/// The `MatrixCoefficients` code point.
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub enum MatrixCoefficients {
+    /// No matrix, as used for GBR (RGB) or YZX video
+    Identity,
+    Bt709,
+    Unspecified,
+    Fcc,
+    Bt470Bg,
+    /// SMPTE 170M, as used for BT.601 525-line video
+    Bt601,
+    Smpte240M,
+    YCgCo,
+    Bt2020Ncl,
+    Bt2020Cl,
+    Smpte2085,
+    ChromaticityDerivedNcl,
+    ChromaticityDerivedCl,
+    ICtCp,
+    /// A value reserved for future use, which should not be one of the code points given a
+    /// variant of its own
+    Reserved(u8),
+}
+code_point_eq!(MatrixCoefficients);
+impl From<u8> for MatrixCoefficients {
+    fn from(v: u8) -> Self {
+        match v {
+            0 => MatrixCoefficients::Identity,
+            1 => MatrixCoefficients::Bt709,
+            2 => MatrixCoefficients::Unspecified,
+            4 => MatrixCoefficients::Fcc,
+            5 => MatrixCoefficients::Bt470Bg,
+            6 => MatrixCoefficients::Bt601,
+            7 => MatrixCoefficients::Smpte240M,
+            8 => MatrixCoefficients::YCgCo,
+            9 => MatrixCoefficients::Bt2020Ncl,
+            10 => MatrixCoefficients::Bt2020Cl,
+            11 => MatrixCoefficients::Smpte2085,
+            12 => MatrixCoefficients::ChromaticityDerivedNcl,
+            13 => MatrixCoefficients::ChromaticityDerivedCl,
+            14 => MatrixCoefficients::ICtCp,
+            _ => MatrixCoefficients::Reserved(v),
+        }
+    }
+}
+impl From<MatrixCoefficients> for u8 {
+    fn from(v: MatrixCoefficients) -> Self {
+        match v {
+            MatrixCoefficients::Identity => 0,
+            MatrixCoefficients::Bt709 => 1,
+            MatrixCoefficients::Unspecified => 2,
+            MatrixCoefficients::Fcc => 4,
+            MatrixCoefficients::Bt470Bg => 5,
+            MatrixCoefficients::Bt601 => 6,
+            MatrixCoefficients::Smpte240M => 7,
+            MatrixCoefficients::YCgCo => 8,
+            MatrixCoefficients::Bt2020Ncl => 9,
+            MatrixCoefficients::Bt2020Cl => 10,
+            MatrixCoefficients::Smpte2085 => 11,
+            MatrixCoefficients::ChromaticityDerivedNcl => 12,
+            MatrixCoefficients::ChromaticityDerivedCl => 13,
+            MatrixCoefficients::ICtCp => 14,
+            MatrixCoefficients::Reserved(v) => v,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn code_points_roundtrip() {
+        for v in 0..=255u8 {
+            assert_eq!(v, u8::from(ColorPrimaries::from(v)));
+            assert_eq!(v, u8::from(TransferCharacteristics::from(v)));
+            assert_eq!(v, u8::from(MatrixCoefficients::from(v)));
+        }
+    }
+
+    #[test]
+    fn reserved_compares_by_code_point() {
+        use std::collections::hash_map::DefaultHasher;
+        let hash = |v: &ColorPrimaries| {
+            let mut hasher = DefaultHasher::new();
+            v.hash(&mut hasher);
+            hasher.finish()
+        };
+        assert_eq!(ColorPrimaries::Reserved(1), ColorPrimaries::Bt709);
+        assert_eq!(
+            hash(&ColorPrimaries::Reserved(1)),
+            hash(&ColorPrimaries::Bt709)
+        );
+        assert!(ColorPrimaries::Reserved(3) < ColorPrimaries::Bt470M);
+        assert!(ColorPrimaries::Ebu3213 < ColorPrimaries::Reserved(23));
+        assert_eq!(
+            MatrixCoefficients::Reserved(0),
+            MatrixCoefficients::Identity
+        );
+        assert!(TransferCharacteristics::Reserved(16).is_hdr());
+    }
+}
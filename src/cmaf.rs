@@ -0,0 +1,315 @@
+//! Conformance checks against the CMAF media profiles of ISO/IEC 23000-19, and the selection
+//! of them which CTA-5001 (the WAVE content specification) permits.
+
+#[cfg(feature = "dts")]
+use crate::Dts;
+use crate::{Codec, Mp4a};
+#[cfg(feature = "mpegh")]
+use crate::{MpeghProfileLevel, MpeghSampleEntry};
+use four_cc::FourCC;
+use mpeg4_audio_const::AudioObjectType;
+use std::str::FromStr;
+
+/// The CMAF media profile brands which are checked by decoder capability, along with the codec
+/// value of a decoder for the most capable bitstream each profile allows.
+const DECODER_PROFILES: [(&[u8; 4], &str); 11] = [
+    // AVC SD, HD and HDHF: High profile at levels 3.1, 4.0 and 4.2
+    (b"cfsd", "avc1.64001F"),
+    (b"cfhd", "avc1.640028"),
+    (b"chdf", "avc1.64002A"),
+    // HEVC HHD8 and HHD10: Main and Main 10 profiles, Main tier, level 4.1
+    (b"chhd", "hvc1.1.6.L123.B0"),
+    (b"chh1", "hvc1.2.4.L123.B0"),
+    // HEVC UHD8 and UHD10, HLG10 and HDR10: Main and Main 10 profiles, Main tier, level 5.1
+    (b"cud8", "hvc1.1.6.L153.B0"),
+    (b"cud1", "hvc1.2.4.L153.B0"),
+    (b"clg1", "hvc1.2.4.L153.B0"),
+    (b"chd1", "hvc1.2.4.L153.B0"),
+    // AAC Core, and Adaptive Audio (which adds HE-AAC and HE-AAC v2)
+    (b"caac", "mp4a.40.2"),
+    (b"caaa", "mp4a.40.29"),
+];
+
+impl Codec {
+    /// Answers whether this codec value satisfies the constraints of the CMAF media profile
+    /// identified by the given brand, such as `cfhd` or `caac`.
+    ///
+    /// The check covers only what the codec value itself signals (for example the profile and
+    /// level), and not constraints like the picture size, frame rate or colour signalling of
+    /// the content.  Returns `None` for brands this crate doesn't know the constraints of, or
+    /// where the codec value doesn't give enough information to decide.
+    ///
+    /// Supported brands are `cfsd`, `cfhd` and `chdf` (AVC), `chhd`, `chh1`, `cud8`, `cud1`,
+    /// `clg1` and `chd1` (HEVC), `caac`, `caaa` and `camc` (AAC), `ceac` (E-AC-3), `cac4`
+    /// (AC-4), `cwvt` (WebVTT) and `im1t` (IMSC1 text).
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::Codec;
+    /// # use four_cc::FourCC;
+    /// # use std::str::FromStr;
+    /// let codec = Codec::from_str("avc1.4D401F").unwrap();
+    /// assert_eq!(codec.conforms_to_cmaf(FourCC(*b"cfhd")), Some(true));
+    /// assert_eq!(codec.conforms_to_cmaf(FourCC(*b"chhd")), Some(false));
+    /// let codec = Codec::from_str("avc1.640032").unwrap();
+    /// assert_eq!(codec.conforms_to_cmaf(FourCC(*b"cfhd")), Some(false));
+    /// ```
+    pub fn conforms_to_cmaf(&self, media_profile: FourCC) -> Option<bool> {
+        if let Some((_, decoder)) = DECODER_PROFILES
+            .iter()
+            .find(|(brand, _)| FourCC(**brand) == media_profile)
+        {
+            let decoder = Codec::from_str(decoder).unwrap();
+            if std::mem::discriminant(self) != std::mem::discriminant(&decoder) {
+                return Some(false);
+            }
+            return self.is_playable_by(&decoder);
+        }
+        Some(match &media_profile.0 {
+            // Multichannel AAC is AAC-LC, as for AAC Core, but without the channel count limit
+            b"camc" => matches!(
+                self,
+                Codec::Mp4a(Mp4a::Mpeg4Audio {
+                    audio_object_type: Some(AudioObjectType::AAC_LC)
+                })
+            ),
+            #[cfg(feature = "dolby")]
+            b"ceac" => matches!(self, Codec::Eac3),
+            #[cfg(feature = "dolby")]
+            b"cac4" => matches!(self, Codec::Ac4(_)),
+            #[cfg(feature = "text")]
+            b"cwvt" => matches!(self, Codec::Wvtt),
+            #[cfg(feature = "text")]
+            b"im1t" => match self {
+                Codec::Stpp(stpp) => stpp.profiles().iter().any(|p| p == "im1t"),
+                _ => false,
+            },
+            _ => return None,
+        })
+    }
+}
+
+/// The CMAF media profiles which CTA-5001 permits, and which are checked by
+/// [`Codec::conforms_to_cmaf()`]
+const WAVE_CMAF_PROFILES: [&[u8; 4]; 14] = [
+    b"cfsd", b"cfhd", b"chdf", b"chh1", b"cud1", b"clg1", b"chd1", b"caac", b"caaa", b"camc",
+    b"ceac", b"cac4", b"cwvt", b"im1t",
+];
+
+impl Codec {
+    /// Answers whether this codec value satisfies the constraints of the given CTA WAVE media
+    /// profile.
+    ///
+    /// As well as the CMAF media profiles which CTA-5001 permits (see
+    /// [`Codec::conforms_to_cmaf()`]), this covers the WAVE profiles for DTS (`dts1`, `dts2` and
+    /// `dts3`) and MPEG-H 3D Audio (`cmhm` and `cmhs`, the Low Complexity profile at up to level
+    /// 3).  Returns `None` for brands which are not WAVE media profiles, or where the codec
+    /// value doesn't give enough information to decide.
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::Codec;
+    /// # use four_cc::FourCC;
+    /// # use std::str::FromStr;
+    /// # #[cfg(feature = "mpegh")]
+    /// let codec = Codec::from_str("mhm1.0x0D").unwrap();
+    /// # #[cfg(feature = "mpegh")]
+    /// assert_eq!(codec.conforms_to_wave(FourCC(*b"cmhm")), Some(true));
+    /// // 8-bit HEVC CMAF profiles are not among the WAVE media profiles
+    /// # #[cfg(feature = "hevc")]
+    /// let codec = Codec::from_str("hvc1.1.6.L93.B0").unwrap();
+    /// # #[cfg(feature = "hevc")]
+    /// assert_eq!(codec.conforms_to_wave(FourCC(*b"chhd")), None);
+    /// ```
+    pub fn conforms_to_wave(&self, media_profile: FourCC) -> Option<bool> {
+        if WAVE_CMAF_PROFILES
+            .iter()
+            .any(|brand| FourCC(**brand) == media_profile)
+        {
+            return self.conforms_to_cmaf(media_profile);
+        }
+        #[cfg(feature = "mpegh")]
+        let mpegh = |sample_entry| match self {
+            Codec::Mpegh(mpegh) if mpegh.sample_entry() == sample_entry => {
+                match mpegh.profile_level()? {
+                    MpeghProfileLevel::LowComplexity(level) => Some(level <= 3),
+                    _ => Some(false),
+                }
+            }
+            _ => Some(false),
+        };
+        match &media_profile.0 {
+            #[cfg(feature = "dts")]
+            b"dts1" => Some(matches!(self, Codec::Dts(Dts::Dtsc))),
+            #[cfg(feature = "dts")]
+            b"dts2" => Some(matches!(
+                self,
+                Codec::Dts(Dts::Dtsh | Dts::Dtsl | Dts::Dtse)
+            )),
+            #[cfg(feature = "dts")]
+            b"dts3" => Some(matches!(self, Codec::Dts(Dts::Dtsx))),
+            #[cfg(feature = "mpegh")]
+            b"cmhm" => mpegh(MpeghSampleEntry::Mhm1),
+            #[cfg(feature = "mpegh")]
+            b"cmhs" => mpegh(MpeghSampleEntry::Mhm2),
+            _ => None,
+        }
+    }
+
+    /// Answers whether every entry of the given codecs list (such as that of a WAVE
+    /// presentation's tracks) conforms to at least one of the given WAVE media profiles.
+    ///
+    /// Returns `None` if an entry conforms to none of the profiles, but the answer could not be
+    /// determined for at least one of them.
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::Codec;
+    /// # use four_cc::FourCC;
+    /// let codecs: Vec<_> = Codec::parse_codecs("avc1.640028,mp4a.40.2")
+    ///     .collect::<Result<_, _>>()
+    ///     .unwrap();
+    /// let profiles = [FourCC(*b"cfhd"), FourCC(*b"caac")];
+    /// assert_eq!(Codec::all_conform_to_wave(&codecs, &profiles), Some(true));
+    /// assert_eq!(Codec::all_conform_to_wave(&codecs, &profiles[..1]), Some(false));
+    /// ```
+    pub fn all_conform_to_wave(codecs: &[Codec], media_profiles: &[FourCC]) -> Option<bool> {
+        let mut result = Some(true);
+        for codec in codecs {
+            let mut conforms = Some(false);
+            for profile in media_profiles {
+                match codec.conforms_to_wave(*profile) {
+                    Some(true) => {
+                        conforms = Some(true);
+                        break;
+                    }
+                    Some(false) => {}
+                    None => conforms = None,
+                }
+            }
+            match conforms {
+                Some(true) => {}
+                Some(false) => return Some(false),
+                None => result = None,
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Codec;
+    use four_cc::FourCC;
+    use std::str::FromStr;
+
+    fn conforms(codec: &str, brand: &[u8; 4]) -> Option<bool> {
+        Codec::from_str(codec)
+            .unwrap()
+            .conforms_to_cmaf(FourCC(*brand))
+    }
+
+    #[test]
+    fn video() {
+        assert_eq!(conforms("avc1.64001F", b"cfsd"), Some(true));
+        assert_eq!(conforms("avc1.640028", b"cfsd"), Some(false));
+        assert_eq!(conforms("avc1.42E01E", b"cfhd"), Some(true));
+        assert_eq!(conforms("avc1.64002A", b"chdf"), Some(true));
+        assert_eq!(conforms("avc1.F40028", b"cfhd"), Some(false));
+        #[cfg(feature = "hevc")]
+        assert_eq!(conforms("hvc1.1.6.L120.90", b"chhd"), Some(true));
+        #[cfg(feature = "hevc")]
+        assert_eq!(conforms("hev1.2.4.L120.90", b"chhd"), Some(false));
+        #[cfg(feature = "hevc")]
+        assert_eq!(conforms("hev1.2.4.L120.90", b"chh1"), Some(true));
+        #[cfg(feature = "hevc")]
+        assert_eq!(conforms("hvc1.2.4.L153.B0", b"chh1"), Some(false));
+        #[cfg(feature = "hevc")]
+        assert_eq!(conforms("hvc1.2.4.H153.B0", b"chd1"), Some(false));
+        #[cfg(feature = "hevc")]
+        assert_eq!(conforms("hvc1.2.4.L150.B0", b"clg1"), Some(true));
+        #[cfg(feature = "av1")]
+        assert_eq!(conforms("av01.0.08M.10", b"cud1"), Some(false));
+    }
+
+    #[test]
+    fn audio_and_text() {
+        assert_eq!(conforms("mp4a.40.2", b"caac"), Some(true));
+        assert_eq!(conforms("mp4a.40.5", b"caac"), Some(false));
+        assert_eq!(conforms("mp4a.40.29", b"caaa"), Some(true));
+        assert_eq!(conforms("mp4a.40.2", b"camc"), Some(true));
+        assert_eq!(conforms("mp4a.40.5", b"camc"), Some(false));
+        #[cfg(feature = "dolby")]
+        assert_eq!(conforms("ec-3", b"ceac"), Some(true));
+        #[cfg(feature = "dolby")]
+        assert_eq!(conforms("ac-3", b"ceac"), Some(false));
+        #[cfg(feature = "text")]
+        assert_eq!(conforms("wvtt", b"cwvt"), Some(true));
+        #[cfg(feature = "text")]
+        assert_eq!(conforms("stpp.ttml.im1t", b"im1t"), Some(true));
+        #[cfg(feature = "text")]
+        assert_eq!(conforms("stpp.ttml.im1i", b"im1t"), Some(false));
+        assert_eq!(conforms("Opus", b"cfhd"), Some(false));
+        assert_eq!(conforms("mp4a.40.2", b"zzzz"), None);
+    }
+
+    fn wave(codec: &str, brand: &[u8; 4]) -> Option<bool> {
+        Codec::from_str(codec)
+            .unwrap()
+            .conforms_to_wave(FourCC(*brand))
+    }
+
+    #[test]
+    fn wave_profiles() {
+        assert_eq!(wave("avc1.640028", b"cfhd"), Some(true));
+        #[cfg(feature = "hevc")]
+        assert_eq!(wave("hvc1.2.4.L153.B0", b"cud1"), Some(true));
+        #[cfg(feature = "hevc")]
+        assert_eq!(wave("hvc1.1.6.L153.B0", b"cud8"), None);
+        #[cfg(feature = "dts")]
+        assert_eq!(wave("dtsc", b"dts1"), Some(true));
+        #[cfg(feature = "dts")]
+        assert_eq!(wave("dtsh", b"dts1"), Some(false));
+        #[cfg(feature = "dts")]
+        assert_eq!(wave("dtsl", b"dts2"), Some(true));
+        #[cfg(feature = "dts")]
+        assert_eq!(wave("dtsx", b"dts3"), Some(true));
+        #[cfg(feature = "mpegh")]
+        assert_eq!(wave("mhm1.0x0D", b"cmhm"), Some(true));
+        #[cfg(feature = "mpegh")]
+        assert_eq!(wave("mhm1.0x0E", b"cmhm"), Some(false));
+        #[cfg(feature = "mpegh")]
+        assert_eq!(wave("mhm2.0x0B", b"cmhm"), Some(false));
+        #[cfg(feature = "mpegh")]
+        assert_eq!(wave("mhm2.0x0B", b"cmhs"), Some(true));
+        #[cfg(feature = "mpegh")]
+        assert_eq!(wave("mhm1.0x03", b"cmhm"), Some(false));
+        #[cfg(feature = "mpegh")]
+        assert_eq!(wave("mhm1", b"cmhm"), None);
+    }
+
+    #[cfg(all(
+        feature = "hevc",
+        feature = "dolby",
+        feature = "mpegh",
+        feature = "text"
+    ))]
+    #[test]
+    fn wave_presentation() {
+        let codecs = |s| {
+            Codec::parse_codecs(s)
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap()
+        };
+        let profiles = [FourCC(*b"chd1"), FourCC(*b"ceac"), FourCC(*b"im1t")];
+        assert_eq!(
+            Codec::all_conform_to_wave(&codecs("hvc1.2.4.L150.B0,ec-3,stpp.ttml.im1t"), &profiles),
+            Some(true)
+        );
+        assert_eq!(
+            Codec::all_conform_to_wave(&codecs("hvc1.2.4.L150.B0,mp4a.40.2"), &profiles),
+            Some(false)
+        );
+        assert_eq!(
+            Codec::all_conform_to_wave(&codecs("mhm1,ec-3"), &[FourCC(*b"cmhm"), FourCC(*b"ceac")]),
+            None
+        );
+    }
+}
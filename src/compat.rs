@@ -0,0 +1,274 @@
+//! Decoder compatibility checks between codec values.
+
+#[cfg(feature = "av1")]
+use crate::Av01;
+#[cfg(feature = "hevc")]
+use crate::Hevc;
+use crate::{Avc1, AvcProfile, Codec, Mp4a, Mpeg2AacProfile};
+use mpeg4_audio_const::AudioObjectType;
+use std::mem;
+
+impl Codec {
+    /// Answers whether a decoder capable of decoding `decoder` would also be able to decode
+    /// `self`.
+    ///
+    /// For the codecs where this crate understands profiles and levels (`avc1`, `hvc1` / `hev1`,
+    /// `av01` and the AAC flavours of `mp4a`), this checks that the profile of `self` is one the
+    /// decoder's profile supports, and that the level of `self` does not exceed the decoder's
+    /// level.
+    ///
+    /// Returns `None` when the answer can't be determined from the codec values alone, for
+    /// example where either value is [`Codec::Other`], or uses a profile this crate does not
+    /// know the relationships of.
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::Codec;
+    /// # use std::str::FromStr;
+    /// let decoder = Codec::from_str("avc1.640028").unwrap(); // High profile, level 4.0
+    /// let main_l31 = Codec::from_str("avc1.4D401F").unwrap();
+    /// let high_l51 = Codec::from_str("avc1.640033").unwrap();
+    /// assert_eq!(main_l31.is_playable_by(&decoder), Some(true));
+    /// assert_eq!(high_l51.is_playable_by(&decoder), Some(false));
+    /// assert_eq!(Codec::aac_lc().is_playable_by(&decoder), Some(false));
+    /// ```
+    pub fn is_playable_by(&self, decoder: &Codec) -> Option<bool> {
+        match (self, decoder) {
+            (Codec::Avc1(stream), Codec::Avc1(decoder)) => avc1_playable(stream, decoder),
+            #[cfg(feature = "hevc")]
+            (Codec::Hevc(stream), Codec::Hevc(decoder)) => hevc_playable(stream, decoder),
+            #[cfg(feature = "av1")]
+            (Codec::Av01(stream), Codec::Av01(decoder)) => av01_playable(stream, decoder),
+            (Codec::Mp4a(stream), Codec::Mp4a(decoder)) => mp4a_playable(stream, decoder),
+            (Codec::Mp3, Codec::Mp4a(decoder)) => Some(decoder.is_mp3()),
+            (Codec::Mp4a(stream), Codec::Mp3) => Some(stream.is_mp3()),
+            // E-AC-3 decoders are required to support AC-3 too
+            #[cfg(feature = "dolby")]
+            (Codec::Ac3, Codec::Eac3) => Some(true),
+            (Codec::Other(stream), Codec::Other(decoder)) if stream == decoder => Some(true),
+            (Codec::Other(_), _) | (_, Codec::Other(_)) => None,
+            _ if mem::discriminant(self) != mem::discriminant(decoder) => Some(false),
+            _ if self.to_string() == decoder.to_string() => Some(true),
+            _ => None,
+        }
+    }
+}
+
+fn avc1_playable(stream: &Avc1, decoder: &Avc1) -> Option<bool> {
+    if !avc1_profile_supported(stream, decoder.profile_enum())? {
+        return Some(false);
+    }
+    Some(stream.level_enum()? <= decoder.level_enum()?)
+}
+
+/// Implements the profile relationships given in H.264 Annex A, where decoders for some
+/// profiles are required to also decode bitstreams conforming to 'lesser' profiles (as
+/// signalled by `profile_idc`, or by the `constraint_set*_flag` values).
+fn avc1_profile_supported(stream: &Avc1, decoder: AvcProfile) -> Option<bool> {
+    use AvcProfile::*;
+
+    let profile = stream.profile_enum();
+    if let Unknown(profile_idc) = profile {
+        return if u8::from(decoder) == profile_idc {
+            Some(true)
+        } else {
+            None
+        };
+    }
+    let baseline = stream.profile() == 66 || stream.constraint_set0_flag();
+    let main = stream.profile() == 77 || stream.constraint_set1_flag();
+    let extended = stream.profile() == 88 || stream.constraint_set2_flag();
+    let constrained_baseline = baseline && main;
+    let high = main || matches!(profile, High | ProgressiveHigh | ConstrainedHigh);
+    let high10 = high || matches!(profile, High10 | High10Intra);
+    let high422 = high10 || matches!(profile, High422 | High422Intra);
+    Some(match decoder {
+        Baseline => baseline,
+        ConstrainedBaseline => constrained_baseline,
+        Main => main,
+        Extended => extended || baseline,
+        High => high,
+        ProgressiveHigh => {
+            constrained_baseline || matches!(profile, ProgressiveHigh | ConstrainedHigh)
+        }
+        ConstrainedHigh => constrained_baseline || profile == ConstrainedHigh,
+        High10 => high10,
+        High422 => high422,
+        High444Predictive => {
+            high422 || matches!(profile, High444Predictive | High444Intra | Cavlc444Intra)
+        }
+        High10Intra => profile == High10Intra,
+        High422Intra => matches!(profile, High10Intra | High422Intra),
+        High444Intra => matches!(
+            profile,
+            High10Intra | High422Intra | High444Intra | Cavlc444Intra
+        ),
+        Cavlc444Intra => profile == Cavlc444Intra,
+        _ if u8::from(decoder) == stream.profile() => true,
+        _ => return None,
+    })
+}
+
+/// The `general_max_12bit_constraint_flag` to `general_lower_bit_rate_constraint_flag` bits, for
+/// which a decoder setting the flag only supports bitstreams that set the flag too.
+#[cfg(feature = "hevc")]
+const HEVC_DECODER_CONSTRAINTS: u64 = 0x1ff << (47 - 12);
+
+#[cfg(feature = "hevc")]
+fn hevc_playable(stream: &Hevc, decoder: &Hevc) -> Option<bool> {
+    if stream.profile_space() != 0 || decoder.profile_space() != 0 {
+        return None;
+    }
+    let profile = decoder.profile_idc();
+    if profile == 0 {
+        return None;
+    }
+    let compatible = stream.profile_idc() == profile
+        || (profile < 32 && stream.profile_compatibility_flag(profile as usize));
+    if !compatible {
+        return Some(false);
+    }
+    let constraints = |hevc: &Hevc| {
+        hevc.constraint_indicator_flags()
+            .iter()
+            .fold(0u64, |acc, b| acc << 8 | u64::from(*b))
+    };
+    let missing = constraints(decoder) & !constraints(stream) & HEVC_DECODER_CONSTRAINTS;
+    Some(
+        missing == 0
+            && stream.tier() <= decoder.tier()
+            && stream.level_idc() <= decoder.level_idc(),
+    )
+}
+
+#[cfg(feature = "av1")]
+fn av01_playable(stream: &Av01, decoder: &Av01) -> Option<bool> {
+    // decoders for each AV1 profile also support the lower-numbered profiles
+    if stream.profile() > 2 || decoder.profile() > 2 {
+        return None;
+    }
+    Some(
+        stream.profile() <= decoder.profile()
+            && stream.bit_depth() <= decoder.bit_depth()
+            && stream.tier() <= decoder.tier()
+            && stream.level() <= decoder.level(),
+    )
+}
+
+fn mp4a_playable(stream: &Mp4a, decoder: &Mp4a) -> Option<bool> {
+    if stream.is_mp3() || decoder.is_mp3() {
+        return Some(stream.is_mp3() && decoder.is_mp3());
+    }
+    match (aac_rank(stream), aac_rank(decoder)) {
+        (Some(stream), Some(decoder)) => Some(stream <= decoder),
+        _ if stream.to_string() == decoder.to_string() => Some(true),
+        _ => None,
+    }
+}
+
+/// Orders the AAC flavours so that each decoder is able to handle the flavours ranked below it;
+/// HE-AAC decoders handle AAC-LC, HE-AAC v2 decoders handle HE-AAC, and xHE-AAC decoders (the
+/// _Extended HE AAC_ profile) handle HE-AAC v2.
+fn aac_rank(mp4a: &Mp4a) -> Option<u8> {
+    match mp4a {
+        Mp4a::Mpeg4Audio {
+            audio_object_type: Some(aot),
+        } => match *aot {
+            AudioObjectType::AAC_LC => Some(0),
+            AudioObjectType::SBR => Some(1),
+            AudioObjectType::PS => Some(2),
+            AudioObjectType::USAC => Some(3),
+            _ => None,
+        },
+        Mp4a::Mpeg2Aac {
+            profile: Mpeg2AacProfile::LowComplexity,
+            ..
+        } => Some(0),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Codec;
+    use std::str::FromStr;
+
+    fn playable(stream: &str, decoder: &str) -> Option<bool> {
+        Codec::from_str(stream)
+            .unwrap()
+            .is_playable_by(&Codec::from_str(decoder).unwrap())
+    }
+
+    #[test]
+    fn avc1() {
+        assert_eq!(playable("avc1.42E01E", "avc1.64001F"), Some(true));
+        assert_eq!(playable("avc1.42E01E", "avc1.4D401E"), Some(true));
+        assert_eq!(playable("avc1.42001E", "avc1.4D401E"), Some(false));
+        assert_eq!(playable("avc1.42001E", "avc1.42001E"), Some(true));
+        assert_eq!(playable("avc1.64001F", "avc1.4D401F"), Some(false));
+        assert_eq!(playable("avc1.6E0028", "avc1.7A0028"), Some(true));
+        assert_eq!(playable("avc1.7A0028", "avc1.6E0028"), Some(false));
+        assert_eq!(playable("avc1.64001F", "avc1.64001E"), Some(false));
+        // level 1b is below level 1.1
+        assert_eq!(playable("avc1.42F00B", "avc1.42E00B"), Some(true));
+        assert_eq!(playable("avc1.640017", "avc1.640028"), None);
+        assert_eq!(playable("avc1.FF0028", "avc1.640028"), None);
+    }
+
+    #[cfg(feature = "hevc")]
+    #[test]
+    fn hevc() {
+        assert_eq!(playable("hvc1.1.6.L93.B0", "hev1.2.4.L120.B0"), Some(true));
+        assert_eq!(playable("hvc1.2.4.L93.B0", "hvc1.1.6.L120.B0"), Some(false));
+        assert_eq!(
+            playable("hvc1.2.4.L153.B0", "hvc1.2.4.L120.B0"),
+            Some(false)
+        );
+        assert_eq!(
+            playable("hvc1.2.4.H120.B0", "hvc1.2.4.L120.B0"),
+            Some(false)
+        );
+        // Main 4:2:2 10 decoder, and a Main 4:4:4 stream
+        assert_eq!(
+            playable("hvc1.4.10.L120.90", "hvc1.4.10.L120.9D.08"),
+            Some(false)
+        );
+        assert_eq!(
+            playable("hvc1.4.10.L120.9D.08", "hvc1.4.10.L120.90"),
+            Some(true)
+        );
+    }
+
+    #[cfg(feature = "av1")]
+    #[test]
+    fn av01() {
+        assert_eq!(playable("av01.0.04M.08", "av01.0.08M.10"), Some(true));
+        assert_eq!(playable("av01.0.08M.10", "av01.0.04M.10"), Some(false));
+        assert_eq!(playable("av01.0.04M.10", "av01.0.08M.08"), Some(false));
+        assert_eq!(playable("av01.1.04M.08", "av01.0.08M.08"), Some(false));
+        assert_eq!(playable("av01.0.04M.08", "av01.2.08M.12"), Some(true));
+    }
+
+    #[test]
+    fn audio() {
+        assert_eq!(playable("mp4a.40.2", "mp4a.40.5"), Some(true));
+        assert_eq!(playable("mp4a.40.29", "mp4a.40.5"), Some(false));
+        assert_eq!(playable("mp4a.67", "mp4a.40.2"), Some(true));
+        assert_eq!(playable("mp3", "mp4a.6B"), Some(true));
+        assert_eq!(playable("mp4a.40.34", "mp4a.40.2"), Some(false));
+        assert_eq!(playable("mp4a.40.36", "mp4a.40.36"), Some(true));
+        assert_eq!(playable("mp4a.40.36", "mp4a.40.2"), None);
+        #[cfg(feature = "dolby")]
+        assert_eq!(playable("ac-3", "ec-3"), Some(true));
+        #[cfg(feature = "dolby")]
+        assert_eq!(playable("ec-3", "ac-3"), Some(false));
+        assert_eq!(playable("Opus", "opus"), Some(true));
+        assert_eq!(playable("Opus", "fLaC"), Some(false));
+    }
+
+    #[test]
+    fn unknown() {
+        assert_eq!(playable("vvc1.1.L51.CQA", "vvc1.1.L51.CQA"), Some(true));
+        assert_eq!(playable("vvc1.1.L51.CQA", "vvc1.1.L83.CQA"), None);
+        assert_eq!(playable("avc3.64001F", "avc1.64001F"), None);
+    }
+}
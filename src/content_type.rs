@@ -0,0 +1,356 @@
+//! Composing and parsing whole media type strings that carry a codecs parameter.
+
+use crate::param::decode_extended_value;
+use crate::{Codec, CodecError, Profiles};
+use std::fmt;
+use std::str::FromStr;
+
+/// A media type such as `video/mp4; codecs="avc1.4D401E, mp4a.40.2"`, together with any other
+/// parameters it has.
+///
+/// The `codecs` parameter value is parsed using [`Codec::parse_for_mime()`], so that entries
+/// are checked against the media type where this crate knows the rules.  The `cod-fancy` form,
+/// `codecs*=...`, is accepted when parsing too, though is always written in the simple form.
+/// Parameter names are matched case-insensitively.
+///
+/// ```rust
+/// # use rfc6381_codec::{Codec, ContentType};
+/// # use std::str::FromStr;
+/// let content_type = ContentType::new("video/mp4")
+///     .with_codecs(vec![Codec::avc1(0x4d, 0x40, 0x1e), Codec::aac_lc()]);
+/// assert_eq!(content_type.to_string(), r#"video/mp4; codecs="avc1.4D401E, mp4a.40.2""#);
+///
+/// let parsed = ContentType::from_str(r#"audio/mp4;Codecs="mp4a.40.5";profiles=cmfc"#).unwrap();
+/// assert_eq!(parsed.media_type(), "audio/mp4");
+/// assert_eq!(parsed.codecs()[0].to_string(), "mp4a.40.5");
+/// assert_eq!(parsed.parameter("profiles"), Some("cmfc"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ContentType {
+    media_type: String,
+    parameters: Vec<(String, String)>,
+    codecs: Vec<Codec>,
+}
+impl ContentType {
+    /// Creates a value for the given `type/subtype`, without any parameters.
+    pub fn new(media_type: &str) -> ContentType {
+        ContentType {
+            media_type: media_type.to_string(),
+            parameters: vec![],
+            codecs: vec![],
+        }
+    }
+    /// Sets the entries of the `codecs` parameter.  The parameter is omitted when the list is
+    /// empty.
+    pub fn with_codecs(mut self, codecs: Vec<Codec>) -> ContentType {
+        self.codecs = codecs;
+        self
+    }
+    /// Adds a parameter, replacing any existing value for that parameter.
+    ///
+    /// A `codecs` (or `codecs*`) parameter replaces the entries set by
+    /// [`with_codecs()`](Self::with_codecs), and is parsed as when parsing a whole media type
+    /// string, failing if the `codecs*` value can't be decoded or any entry can't be parsed.
+    pub fn with_parameter(
+        mut self,
+        name: &str,
+        value: &str,
+    ) -> Result<ContentType, ContentTypeError> {
+        if name.eq_ignore_ascii_case("codecs") {
+            self.codecs =
+                Codec::parse_for_mime(&self.media_type, value).collect::<Result<_, _>>()?;
+            return Ok(self);
+        }
+        if name.eq_ignore_ascii_case("codecs*") {
+            let (_, _, decoded) = decode_extended_value(value)
+                .ok_or_else(|| ContentTypeError::InvalidParameter(value.to_string()))?;
+            self.codecs =
+                Codec::parse_for_mime(&self.media_type, &decoded).collect::<Result<_, _>>()?;
+            return Ok(self);
+        }
+        match self
+            .parameters
+            .iter_mut()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+        {
+            Some(param) => param.1 = value.to_string(),
+            None => self.parameters.push((name.to_string(), value.to_string())),
+        }
+        Ok(self)
+    }
+    /// The `type/subtype` part
+    pub fn media_type(&self) -> &str {
+        &self.media_type
+    }
+    /// The entries of the `codecs` parameter, empty if there was no such parameter
+    pub fn codecs(&self) -> &[Codec] {
+        &self.codecs
+    }
+    /// Consumes the value, giving the entries of the `codecs` parameter
+    pub fn into_codecs(self) -> Vec<Codec> {
+        self.codecs
+    }
+    /// The value of the named parameter (other than `codecs`), with any quoting removed
+    pub fn parameter(&self, name: &str) -> Option<&str> {
+        self.parameters
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+    /// Parses the `profiles` parameter, giving an empty list if it is absent
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::ContentType;
+    /// # use std::str::FromStr;
+    /// let content_type = ContentType::from_str(r#"video/mp4; codecs="avc1.4D401E"; profiles="isom,cmfc""#)
+    ///     .unwrap();
+    /// assert_eq!(content_type.profiles().unwrap().brands().len(), 2);
+    /// ```
+    pub fn profiles(&self) -> Result<Profiles, CodecError> {
+        match self.parameter("profiles") {
+            Some(value) => Profiles::from_value(value),
+            None => Ok(Profiles::new(vec![])),
+        }
+    }
+    /// The parameters other than `codecs`, in the order they were given
+    pub fn parameters(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.parameters
+            .iter()
+            .map(|(n, v)| (n.as_str(), v.as_str()))
+    }
+}
+
+/// Problems found when parsing a `ContentType`, or when adding a `codecs` parameter to one.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ContentTypeError {
+    /// The text before any parameters was not of the form `type/subtype`
+    InvalidMediaType(String),
+    /// A parameter was not of the form `name=value`, or the value had unbalanced quotes
+    InvalidParameter(String),
+    /// The `codecs` parameter value included an entry that could not be parsed
+    Codec(CodecError),
+}
+impl From<CodecError> for ContentTypeError {
+    fn from(e: CodecError) -> Self {
+        ContentTypeError::Codec(e)
+    }
+}
+
+impl FromStr for ContentType {
+    type Err = ContentTypeError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (media_type, mut rest) = value.split_at(value.find(';').unwrap_or(value.len()));
+        let media_type = media_type.trim();
+        let valid = match media_type.split_once('/') {
+            Some((t, s)) => is_token(t) && is_token(s),
+            None => false,
+        };
+        if !valid {
+            return Err(ContentTypeError::InvalidMediaType(media_type.to_string()));
+        }
+        let mut result = ContentType::new(media_type);
+        let mut codecs = None;
+        while let Some(r) = rest.trim_start().strip_prefix(';') {
+            let r = r.trim_start();
+            if r.is_empty() {
+                rest = r;
+                break;
+            }
+            let (name, value, remaining) =
+                parameter(r).ok_or_else(|| ContentTypeError::InvalidParameter(r.to_string()))?;
+            if name.eq_ignore_ascii_case("codecs") {
+                codecs = Some(value);
+            } else if name.eq_ignore_ascii_case("codecs*") {
+                let (_, _, decoded) = decode_extended_value(&value)
+                    .ok_or_else(|| ContentTypeError::InvalidParameter(r.to_string()))?;
+                codecs = Some(decoded);
+            } else {
+                result.parameters.push((name.to_string(), value));
+            }
+            rest = remaining;
+        }
+        if !rest.trim().is_empty() {
+            return Err(ContentTypeError::InvalidParameter(rest.to_string()));
+        }
+        if let Some(codecs) = codecs {
+            result.codecs = Codec::parse_for_mime(media_type, &codecs).collect::<Result<_, _>>()?;
+        }
+        Ok(result)
+    }
+}
+
+impl Codec {
+    /// Parses the codecs list from the value of a `Content-Type` header, like
+    /// `video/mp4; codecs="avc1.4D401E, mp4a.40.2"`, accepting either form of the `codecs`
+    /// parameter.  If the header has no `codecs` parameter, the list is empty.
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::Codec;
+    /// let codecs = Codec::from_content_type("audio/mp4; codecs*=''mp4a.40.2").unwrap();
+    /// assert_eq!(codecs[0].to_string(), "mp4a.40.2");
+    /// ```
+    pub fn from_content_type(header: &str) -> Result<Vec<Codec>, ContentTypeError> {
+        ContentType::from_str(header).map(ContentType::into_codecs)
+    }
+}
+
+/// Parses a `name=value` parameter from the start of the given text, returning the name, the
+/// unquoted value, and the remaining text.
+fn parameter(text: &str) -> Option<(&str, String, &str)> {
+    let (name, rest) = text.split_once('=')?;
+    if !is_token(name) {
+        return None;
+    }
+    let (value, rest) = parameter_value(rest)?;
+    Some((name, value, rest))
+}
+
+/// Parses either a token, or a quoted-string (removing any backslash escapes), from the start
+/// of the given text, returning the value and the remaining text.
+pub(crate) fn parameter_value(text: &str) -> Option<(String, &str)> {
+    if let Some(quoted) = text.strip_prefix('"') {
+        let mut value = String::new();
+        let mut chars = quoted.char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => return Some((value, &quoted[i + 1..])),
+                '\\' => value.push(chars.next()?.1),
+                _ => value.push(c),
+            }
+        }
+        None
+    } else {
+        let end = text.find(|c: char| !is_token_char(c)).unwrap_or(text.len());
+        if end == 0 {
+            return None;
+        }
+        Some((text[..end].to_string(), &text[end..]))
+    }
+}
+
+fn is_token_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c)
+}
+
+fn is_token(value: &str) -> bool {
+    !value.is_empty() && value.chars().all(is_token_char)
+}
+
+impl fmt::Display for ContentType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.media_type)?;
+        for (name, value) in &self.parameters {
+            if is_token(value) {
+                write!(f, "; {}={}", name, value)?;
+            } else {
+                write!(f, "; {}=\"", name)?;
+                for c in value.chars() {
+                    if c == '"' || c == '\\' {
+                        f.write_str("\\")?;
+                    }
+                    write!(f, "{}", c)?;
+                }
+                f.write_str("\"")?;
+            }
+        }
+        if !self.codecs.is_empty() {
+            f.write_str("; codecs=\"")?;
+            for (i, codec) in self.codecs.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(", ")?;
+                }
+                write!(f, "{}", codec)?;
+            }
+            f.write_str("\"")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::*;
+
+    #[test]
+    fn parse() {
+        let c = ContentType::from_str(r#"video/mp4; codecs="avc1.4D401E, mp4a.40.2""#).unwrap();
+        assert_eq!(c.media_type(), "video/mp4");
+        assert_eq!(c.codecs().len(), 2);
+        let c = ContentType::from_str("audio/webm;codecs=opus").unwrap();
+        assert_matches!(c.codecs(), [Codec::Opus]);
+        let c = ContentType::from_str(r#"text/plain; x="a \"b\"; c"; charset=utf-8;"#).unwrap();
+        assert_eq!(c.parameter("x"), Some(r#"a "b"; c"#));
+        assert_eq!(c.parameter("Charset"), Some("utf-8"));
+        assert!(c.codecs().is_empty());
+        let c =
+            ContentType::from_str(r#"video/mp4;CODECS*="UTF-8'en'avc1.4D401E,%4Fpus""#).unwrap();
+        assert_matches!(c.codecs(), [Codec::Avc1(_), Codec::Opus]);
+    }
+
+    #[test]
+    fn bad_content_types() {
+        assert_matches!(
+            ContentType::from_str("video"),
+            Err(ContentTypeError::InvalidMediaType(_))
+        );
+        assert_matches!(
+            ContentType::from_str(r#"video/mp4; codecs="avc1.4D401E"#),
+            Err(ContentTypeError::InvalidParameter(_))
+        );
+        assert_matches!(
+            ContentType::from_str("video/mp4; codecs"),
+            Err(ContentTypeError::InvalidParameter(_))
+        );
+        assert_matches!(
+            ContentType::from_str("video/mp4; codecs=avc1 x"),
+            Err(ContentTypeError::InvalidParameter(_))
+        );
+        assert_matches!(
+            ContentType::from_str("video/mp4; codecs*=avc1.4D401E"),
+            Err(ContentTypeError::InvalidParameter(_))
+        );
+        assert_matches!(
+            ContentType::from_str(r#"image/avif; codecs="hvc1.1.6.L93.B0""#),
+            Err(ContentTypeError::Codec(
+                CodecError::UnexpectedForMediaType { .. }
+            ))
+        );
+    }
+
+    #[test]
+    fn generate() {
+        let c = ContentType::new("audio/mp4")
+            .with_parameter("profiles", "cmfc")
+            .and_then(|c| c.with_parameter("title", "a \"b\""))
+            .unwrap()
+            .with_codecs(vec![Codec::aac_lc()]);
+        assert_eq!(
+            c.to_string(),
+            r#"audio/mp4; profiles=cmfc; title="a \"b\""; codecs="mp4a.40.2""#
+        );
+        let roundtrip = ContentType::from_str(&c.to_string()).unwrap();
+        assert_eq!(roundtrip.to_string(), c.to_string());
+        assert_eq!(ContentType::new("video/mp4").to_string(), "video/mp4");
+        let c = ContentType::new("audio/mp4")
+            .with_parameter("Codecs", "mp4a.40.2, Opus")
+            .unwrap();
+        assert_matches!(c.codecs(), [Codec::Mp4a(_), Codec::Opus]);
+        assert_eq!(c.parameters().count(), 0);
+        assert_matches!(
+            ContentType::new("audio/mp4").with_parameter("codecs", "mp4a.40.2, mp4a.4g"),
+            Err(ContentTypeError::Codec(_))
+        );
+        let c = ContentType::new("audio/mp4")
+            .with_parameter("CODECS*", "''mp4a.40.2,%4Fpus")
+            .unwrap();
+        assert_matches!(c.codecs(), [Codec::Mp4a(_), Codec::Opus]);
+        assert_eq!(c.parameters().count(), 0);
+        assert_eq!(c.to_string(), r#"audio/mp4; codecs="mp4a.40.2, Opus""#);
+        assert_matches!(
+            ContentType::new("audio/mp4").with_parameter("codecs*", "mp4a.40.2"),
+            Err(ContentTypeError::InvalidParameter(_))
+        );
+    }
+}
@@ -0,0 +1,145 @@
+//! `TryFrom` conversions from strings, for each type which can be parsed with `FromStr`.
+//!
+//! Most sub-types of [`Codec`] parse the elements following the sample entry code, as in
+//! `Avc1::from_str("4D401E")`.  The sub-types covering several sample entry codes, [`Hevc`],
+//! [`Mpegh`], [`Dts`] and [`Pcm`], instead parse the full codec string, as in
+//! `Hevc::from_str("hvc1.1.6.L93.B0")`, since the sample entry code forms part of their value.
+
+#[cfg(feature = "dolby")]
+use crate::Ac4;
+#[cfg(feature = "av1")]
+use crate::Av01;
+#[cfg(feature = "dts")]
+use crate::Dts;
+#[cfg(feature = "hevc")]
+use crate::Hevc;
+#[cfg(feature = "mpegh")]
+use crate::Mpegh;
+#[cfg(feature = "text")]
+use crate::Stpp;
+#[cfg(feature = "vp9")]
+use crate::Vp09;
+use crate::{Apv1, Avc1, Avs3, Codec, CodecError, CodecList, CodecsParameter, ContentType};
+use crate::{
+    HlsCodecs, Lvc1, Mp4a, Pcm, Profiles, SourcedCodec, SupplementalCodec, SupplementalCodecs, Uncv,
+};
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+/// Implements `TryFrom<&str>` and `TryFrom<String>` by delegating to the `FromStr`
+/// implementation of each given type
+macro_rules! try_from_str {
+    ($($t:ty),* $(,)?) => {
+        $(
+            impl TryFrom<&str> for $t {
+                type Error = <$t as FromStr>::Err;
+
+                fn try_from(value: &str) -> Result<Self, Self::Error> {
+                    value.parse()
+                }
+            }
+            impl TryFrom<String> for $t {
+                type Error = <$t as FromStr>::Err;
+
+                fn try_from(value: String) -> Result<Self, Self::Error> {
+                    value.parse()
+                }
+            }
+        )*
+    };
+}
+
+/// Implements `FromStr` by parsing a full codec string and requiring the given `Codec` variant
+macro_rules! codec_variant_from_str {
+    ($t:ident) => {
+        impl FromStr for $t {
+            type Err = CodecError;
+
+            fn from_str(value: &str) -> Result<Self, Self::Err> {
+                match Codec::from_str(value)? {
+                    Codec::$t(v) => Ok(v),
+                    _ => Err(CodecError::InvalidComponent(value.into())),
+                }
+            }
+        }
+    };
+}
+
+#[cfg(feature = "hevc")]
+codec_variant_from_str!(Hevc);
+#[cfg(feature = "mpegh")]
+codec_variant_from_str!(Mpegh);
+#[cfg(feature = "dts")]
+codec_variant_from_str!(Dts);
+codec_variant_from_str!(Pcm);
+
+try_from_str!(
+    Codec,
+    Apv1,
+    Avc1,
+    Avs3,
+    Lvc1,
+    Mp4a,
+    Pcm,
+    Uncv,
+    CodecList,
+    CodecsParameter,
+    ContentType,
+    HlsCodecs,
+    Profiles,
+    SourcedCodec,
+    SupplementalCodec,
+    SupplementalCodecs,
+);
+#[cfg(feature = "av1")]
+try_from_str!(Av01);
+#[cfg(feature = "dolby")]
+try_from_str!(Ac4);
+#[cfg(feature = "dts")]
+try_from_str!(Dts);
+#[cfg(feature = "hevc")]
+try_from_str!(Hevc);
+#[cfg(feature = "mpegh")]
+try_from_str!(Mpegh);
+#[cfg(feature = "text")]
+try_from_str!(Stpp);
+#[cfg(feature = "vp9")]
+try_from_str!(Vp09);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CodecError;
+    use assert_matches::*;
+    use std::convert::TryInto;
+
+    fn codec<C: TryInto<Codec, Error = CodecError>>(value: C) -> Result<Codec, CodecError> {
+        value.try_into()
+    }
+
+    #[test]
+    fn try_from() {
+        assert_matches!(codec("avc1.4D401E"), Ok(Codec::Avc1(_)));
+        assert_matches!(codec("mp4a.40.2".to_owned()), Ok(Codec::Mp4a(_)));
+        assert_matches!(codec("avc1.4D40"), Err(CodecError::UnexpectedLength { .. }));
+        assert_matches!(Avc1::try_from("640028"), Ok(a) if a.profile() == 0x64);
+        assert_matches!(CodecList::try_from("opus,flac"), Ok(l) if l.len() == 2);
+        assert_matches!(Pcm::try_from("ipcm"), Ok(_));
+        assert_matches!(Pcm::try_from("opus"), Err(CodecError::InvalidComponent(_)));
+        #[cfg(feature = "hevc")]
+        assert_matches!(
+            Hevc::try_from("hev1.1.6.L93.B0".to_owned()),
+            Ok(h) if h.to_string() == "hev1.1.6.L93.B0"
+        );
+        #[cfg(feature = "hevc")]
+        assert_matches!(Hevc::try_from("avc1.4D401E"), Err(_));
+        #[cfg(feature = "mpegh")]
+        assert_matches!(Mpegh::try_from("mhm1.0x0D"), Ok(_));
+        #[cfg(feature = "dts")]
+        assert_matches!(Dts::try_from("dtsx"), Ok(_));
+        assert_matches!(
+            ContentType::try_from("audio/mp4; codecs=\"mp4a.40.2\"".to_owned()),
+            Ok(_)
+        );
+    }
+}
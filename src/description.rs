@@ -0,0 +1,296 @@
+//! Human-readable descriptions of codec values.
+
+#[cfg(feature = "av1")]
+use crate::Av1Tier;
+#[cfg(feature = "dts")]
+use crate::Dts;
+#[cfg(feature = "hevc")]
+use crate::HevcTier;
+#[cfg(feature = "mpegh")]
+use crate::MpeghProfileLevel;
+use crate::{AvcProfile, Codec, Mp4a, Mpeg2AacProfile, Pcm};
+use mpeg4_audio_const::AudioObjectType;
+
+impl Codec {
+    /// Describes this codec in terms suitable for display in a user interface or in logging.
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::Codec;
+    /// # use std::str::FromStr;
+    /// assert_eq!(
+    ///     Codec::from_str("avc1.4D401E").unwrap().description(),
+    ///     "H.264/AVC Main Profile, Level 3.0"
+    /// );
+    /// assert_eq!(
+    ///     Codec::from_str("mp4a.40.2").unwrap().description(),
+    ///     "AAC-LC (MPEG-4 Audio)"
+    /// );
+    /// ```
+    ///
+    /// The exact wording is not guaranteed to remain the same between releases of this crate, so
+    /// don't depend on being able to parse the result.
+    pub fn description(&self) -> String {
+        match self {
+            Codec::Avc1(avc1) => {
+                let level = match avc1.level_enum() {
+                    Some(level) => format!("Level {}", level),
+                    None => format!("level_idc {}", avc1.level()),
+                };
+                match avc1.profile_enum() {
+                    AvcProfile::Unknown(idc) => {
+                        format!("H.264/AVC, profile_idc {}, {}", idc, level)
+                    }
+                    profile => format!("H.264/AVC {} Profile, {}", profile, level),
+                }
+            }
+            #[cfg(feature = "hevc")]
+            Codec::Hevc(hevc) => {
+                let tier = match hevc.tier() {
+                    HevcTier::Main => "Main Tier",
+                    HevcTier::High => "High Tier",
+                };
+                let level = match hevc.level_enum() {
+                    Some(level) => format!("Level {}", level),
+                    None => format!("level_idc {}", hevc.level_idc()),
+                };
+                let still = if hevc.one_picture_only_constraint_flag() {
+                    " (still picture)"
+                } else {
+                    ""
+                };
+                format!(
+                    "H.265/HEVC {} Profile{}, {}, {}",
+                    hevc.profile_enum(),
+                    still,
+                    tier,
+                    level
+                )
+            }
+            #[cfg(feature = "av1")]
+            Codec::Av01(av01) => {
+                let level = match av01.level_enum() {
+                    Some(level) => format!("Level {}", level),
+                    None => format!("seq_level_idx {}", av01.level()),
+                };
+                let tier = match av01.tier() {
+                    Av1Tier::Main => "Main Tier",
+                    Av1Tier::High => "High Tier",
+                };
+                let hdr = if av01.transfer_characteristics().is_hdr() {
+                    ", HDR"
+                } else {
+                    ""
+                };
+                format!(
+                    "AV1 {} Profile, {}, {}, {}-bit{}",
+                    av01.profile_enum(),
+                    level,
+                    tier,
+                    av01.bit_depth(),
+                    hdr
+                )
+            }
+            #[cfg(feature = "vp9")]
+            Codec::Vp09(vp09) => {
+                let hdr = if vp09.transfer_characteristics().is_hdr() {
+                    ", HDR"
+                } else {
+                    ""
+                };
+                format!(
+                    "VP9 Profile {}, Level {}.{}, {}-bit{}",
+                    vp09.profile(),
+                    vp09.level() / 10,
+                    vp09.level() % 10,
+                    vp09.bit_depth(),
+                    hdr
+                )
+            }
+            Codec::Mp4a(mp4a) => describe_mp4a(mp4a),
+            Codec::Lvc1(lvc1) => format!(
+                "LC-EVC enhancement, profile {}, level {}",
+                lvc1.profile(),
+                lvc1.level()
+            ),
+            Codec::Uncv(uncv) => match uncv.profile() {
+                Some(profile) => format!("Uncompressed video, profile {}", profile),
+                None => "Uncompressed video".to_string(),
+            },
+            Codec::Avs3(avs3) => format!(
+                "AVS3 video, profile_id 0x{:02X}, level_id 0x{:02X}",
+                avs3.profile_id(),
+                avs3.level_id()
+            ),
+            Codec::Av3a => "AVS3 audio".to_string(),
+            Codec::Apv1(apv1) => {
+                let mut desc = "APV (Advanced Professional Video)".to_string();
+                let fields = [
+                    ("profile", apv1.profile()),
+                    ("level_idc", apv1.level()),
+                    ("band", apv1.band()),
+                ];
+                for (name, value) in fields.iter() {
+                    if let Some(value) = value {
+                        desc.push_str(&format!(", {} {}", name, value));
+                    }
+                }
+                desc
+            }
+            Codec::Opus => "Opus".to_string(),
+            Codec::Flac => "FLAC".to_string(),
+            Codec::Alac => "Apple Lossless (ALAC)".to_string(),
+            #[cfg(feature = "dolby")]
+            Codec::Ac3 => "Dolby Digital (AC-3)".to_string(),
+            #[cfg(feature = "dolby")]
+            Codec::Eac3 => "Dolby Digital Plus (E-AC-3)".to_string(),
+            #[cfg(feature = "dolby")]
+            Codec::Ac4(ac4) => format!(
+                "Dolby AC-4, bitstream version {}, presentation version {}",
+                ac4.bitstream_version(),
+                ac4.presentation_version()
+            ),
+            #[cfg(feature = "dts")]
+            Codec::Dts(dts) => match dts {
+                Dts::Dtsc => "DTS Digital Surround",
+                Dts::Dtsh => "DTS-HD",
+                Dts::Dtsl => "DTS-HD Master Audio (lossless)",
+                Dts::Dtse => "DTS Express",
+                Dts::Dtsx => "DTS:X",
+                Dts::Dtsy => "DTS-UHD Profile 3",
+            }
+            .to_string(),
+            #[cfg(feature = "mpegh")]
+            Codec::Mpegh(mpegh) => match mpegh.profile_level() {
+                Some(pl) => format!("MPEG-H 3D Audio, {}", describe_mpegh_profile_level(pl)),
+                None => "MPEG-H 3D Audio".to_string(),
+            },
+            Codec::Mp3 => "MP3".to_string(),
+            Codec::Dra1 => "DRA audio".to_string(),
+            Codec::Pcm(Pcm::Integer) => "Integer PCM".to_string(),
+            Codec::Pcm(Pcm::FloatingPoint) => "Floating-point PCM".to_string(),
+            #[cfg(feature = "text")]
+            Codec::Wvtt => "WebVTT".to_string(),
+            #[cfg(feature = "text")]
+            Codec::Stpp(stpp) => {
+                if stpp.profiles().is_empty() {
+                    "TTML".to_string()
+                } else {
+                    format!("TTML, profiles {}", stpp.profiles().join(", "))
+                }
+            }
+            #[cfg(feature = "text")]
+            Codec::Tx3g => "3GPP Timed Text".to_string(),
+            Codec::Extension(ext) => ext.get().description(),
+            Codec::Other(val) => format!("Unrecognised codec \"{}\"", val),
+        }
+    }
+}
+
+fn describe_mp4a(mp4a: &Mp4a) -> String {
+    match mp4a {
+        Mp4a::Mpeg4Audio {
+            audio_object_type: Some(aot),
+        } => format!("{} (MPEG-4 Audio)", describe_aot(*aot)),
+        Mp4a::Mpeg4Audio {
+            audio_object_type: None,
+        } => "MPEG-4 Audio".to_string(),
+        Mp4a::Mpeg2Aac { profile, .. } => match profile {
+            Mpeg2AacProfile::Main => "MPEG-2 AAC Main",
+            Mpeg2AacProfile::LowComplexity => "MPEG-2 AAC-LC",
+            Mpeg2AacProfile::ScalableSamplingRate => "MPEG-2 AAC SSR",
+        }
+        .to_string(),
+        Mp4a::Mpeg1Audio => "MP3 (MPEG-1 Audio)".to_string(),
+        Mp4a::Mpeg2Audio => "MP3 (MPEG-2 Audio)".to_string(),
+        Mp4a::Unknown {
+            object_type_indication,
+            ..
+        } => format!(
+            "MP4 audio, object type 0x{:02X}",
+            u8::from(*object_type_indication)
+        ),
+    }
+}
+
+fn describe_aot(aot: AudioObjectType) -> String {
+    match aot {
+        AudioObjectType::AAC_MAIN => "AAC Main",
+        AudioObjectType::AAC_LC => "AAC-LC",
+        AudioObjectType::AAC_SSR => "AAC SSR",
+        AudioObjectType::AAC_LTP => "AAC LTP",
+        AudioObjectType::SBR => "HE-AAC",
+        AudioObjectType::PS => "HE-AAC v2",
+        AudioObjectType::ER_AAC_LD => "AAC-LD",
+        AudioObjectType::ER_AAC_ELD => "AAC-ELD",
+        AudioObjectType::LAYER3 => "MP3",
+        AudioObjectType::ALS => "ALS",
+        AudioObjectType::USAC => "xHE-AAC",
+        _ => return format!("Audio Object Type {}", u8::from(aot)),
+    }
+    .to_string()
+}
+
+#[cfg(feature = "mpegh")]
+fn describe_mpegh_profile_level(pl: MpeghProfileLevel) -> String {
+    match pl {
+        MpeghProfileLevel::Main(l) => format!("Main Profile Level {}", l),
+        MpeghProfileLevel::High(l) => format!("High Profile Level {}", l),
+        MpeghProfileLevel::LowComplexity(l) => format!("Low Complexity Profile Level {}", l),
+        MpeghProfileLevel::Baseline(l) => format!("Baseline Profile Level {}", l),
+        MpeghProfileLevel::Reserved(v) => format!("profile-level indication 0x{:02X}", v),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Codec;
+    use std::str::FromStr;
+
+    fn desc(codec: &str) -> String {
+        Codec::from_str(codec).unwrap().description()
+    }
+
+    #[test]
+    fn descriptions() {
+        assert_eq!(
+            desc("avc1.42E01E"),
+            "H.264/AVC Constrained Baseline Profile, Level 3.0"
+        );
+        assert_eq!(desc("avc1.640017"), "H.264/AVC High Profile, level_idc 23");
+        assert_eq!(desc("avc1.FF0028"), "H.264/AVC, profile_idc 255, Level 4.0");
+        #[cfg(feature = "hevc")]
+        assert_eq!(
+            desc("hvc1.2.4.H153.B0"),
+            "H.265/HEVC Main 10 Profile, High Tier, Level 5.1"
+        );
+        #[cfg(feature = "hevc")]
+        assert_eq!(
+            desc("hvc1.2.4.L93.B0.10"),
+            "H.265/HEVC Main 10 Profile (still picture), Main Tier, Level 3.1"
+        );
+        #[cfg(feature = "av1")]
+        assert_eq!(
+            desc("av01.0.08M.10.0.110.09.16.09.0"),
+            "AV1 Main Profile, Level 4.0, Main Tier, 10-bit, HDR"
+        );
+        #[cfg(feature = "vp9")]
+        assert_eq!(
+            desc("vp09.02.10.10.01.09.16.09.01"),
+            "VP9 Profile 2, Level 1.0, 10-bit, HDR"
+        );
+        assert_eq!(desc("mp4a.40.29"), "HE-AAC v2 (MPEG-4 Audio)");
+        assert_eq!(desc("mp4a.40.46"), "Audio Object Type 46 (MPEG-4 Audio)");
+        assert_eq!(desc("mp4a.6B"), "MP3 (MPEG-1 Audio)");
+        assert_eq!(desc("mp4a.a5"), "MP4 audio, object type 0xA5");
+        #[cfg(feature = "mpegh")]
+        assert_eq!(
+            desc("mhm1.0x0D"),
+            "MPEG-H 3D Audio, Low Complexity Profile Level 3"
+        );
+        #[cfg(feature = "dolby")]
+        assert_eq!(desc("ec-3"), "Dolby Digital Plus (E-AC-3)");
+        #[cfg(feature = "text")]
+        assert_eq!(desc("stpp.ttml.im1t"), "TTML, profiles im1t");
+        assert_eq!(desc("badd.41"), "Unrecognised codec \"badd.41\"");
+    }
+}
@@ -0,0 +1,114 @@
+//! Support for Dolby codec strings which carry sub-parameters.
+
+use crate::{parse_hex_element, CodecError};
+use std::fmt;
+use std::str::FromStr;
+
+/// The `ac-4` codec parameter, as specified in ETSI TS 103 190-2.
+///
+/// Takes the form `ac-4.BB.PP.LL`, where each field is a two-digit hexadecimal value,
+///
+/// ```rust
+/// # use rfc6381_codec::Codec;
+/// # use std::str::FromStr;
+/// if let Ok(Codec::Ac4(ac4)) = Codec::from_str("ac-4.02.01.03") {
+///     assert_eq!(ac4.bitstream_version(), 2);
+///     assert_eq!(ac4.presentation_version(), 1);
+///     assert_eq!(ac4.mdcompat(), 3);
+/// } else {
+///     panic!("unexpected codec type");
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Ac4 {
+    pub(crate) bitstream_version: u8,
+    pub(crate) presentation_version: u8,
+    pub(crate) mdcompat: u8,
+}
+impl Ac4 {
+    pub const fn new(bitstream_version: u8, presentation_version: u8, mdcompat: u8) -> Ac4 {
+        Ac4 {
+            bitstream_version,
+            presentation_version,
+            mdcompat,
+        }
+    }
+    pub fn bitstream_version(&self) -> u8 {
+        self.bitstream_version
+    }
+    pub fn presentation_version(&self) -> u8 {
+        self.presentation_version
+    }
+    /// The `mdcompat` value, indicating the presentation level
+    pub fn mdcompat(&self) -> u8 {
+        self.mdcompat
+    }
+}
+impl FromStr for Ac4 {
+    type Err = CodecError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut i = value.split('.');
+        let mut next = || {
+            i.next()
+                .ok_or_else(|| CodecError::ExpectedHierarchySeparator(value.into()))
+                .and_then(parse_hex_element)
+        };
+        let result = Ac4 {
+            bitstream_version: next()?,
+            presentation_version: next()?,
+            mdcompat: next()?,
+        };
+        if i.next().is_some() {
+            return Err(CodecError::InvalidComponent(value.into()));
+        }
+        Ok(result)
+    }
+}
+impl fmt::Display for Ac4 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:02x}.{:02x}.{:02x}",
+            self.bitstream_version, self.presentation_version, self.mdcompat
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Codec;
+    use assert_matches::*;
+
+    #[test]
+    fn ac4() {
+        assert_matches!(
+            Codec::from_str("ac-4.02.01.03"),
+            Ok(Codec::Ac4(Ac4 {
+                bitstream_version: 2,
+                presentation_version: 1,
+                mdcompat: 3
+            }))
+        );
+        assert_eq!(
+            "ac-4.02.01.00",
+            Codec::from_str("ac-4.02.01.00").unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn bad_ac4() {
+        assert_matches!(Codec::from_str("ac-4"), Err(_));
+        assert_matches!(Codec::from_str("ac-4.02.01"), Err(_));
+        assert_matches!(Codec::from_str("ac-4.02.01.03.04"), Err(_));
+        assert_matches!(
+            Codec::from_str("ac-4.2.01.03"),
+            Err(CodecError::UnexpectedLength { .. })
+        );
+        assert_matches!(
+            Codec::from_str("ac-4.0g.01.03"),
+            Err(CodecError::InvalidComponent(_))
+        );
+    }
+}
@@ -0,0 +1,71 @@
+//! Support for the DTS family of codec strings.
+
+use mp4ra_rust::SampleEntryCode;
+use std::fmt;
+
+/// The DTS sample entry codes, as specified in ETSI TS 102 114 and ETSI TS 103 491.
+///
+/// None of these take any sub-parameters, so the codec string is just the sample entry code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum Dts {
+    /// DTS Digital Surround (core substream only)
+    Dtsc,
+    /// DTS-HD audio, with a core substream
+    Dtsh,
+    /// DTS-HD Master Audio lossless, without a core substream
+    Dtsl,
+    /// DTS Express (low bitrate)
+    Dtse,
+    /// DTS:X (DTS-UHD profile 2)
+    Dtsx,
+    /// DTS-UHD profile 3
+    Dtsy,
+}
+impl Dts {
+    pub(crate) fn from_sample_entry(sample_entry: SampleEntryCode) -> Option<Dts> {
+        match sample_entry {
+            SampleEntryCode::DTSC => Some(Dts::Dtsc),
+            SampleEntryCode::DTSH => Some(Dts::Dtsh),
+            SampleEntryCode::DTSL => Some(Dts::Dtsl),
+            SampleEntryCode::DTSE => Some(Dts::Dtse),
+            SampleEntryCode::DTSX => Some(Dts::Dtsx),
+            SampleEntryCode::DTSY => Some(Dts::Dtsy),
+            _ => None,
+        }
+    }
+
+    pub fn sample_entry_code(&self) -> SampleEntryCode {
+        match self {
+            Dts::Dtsc => SampleEntryCode::DTSC,
+            Dts::Dtsh => SampleEntryCode::DTSH,
+            Dts::Dtsl => SampleEntryCode::DTSL,
+            Dts::Dtse => SampleEntryCode::DTSE,
+            Dts::Dtsx => SampleEntryCode::DTSX,
+            Dts::Dtsy => SampleEntryCode::DTSY,
+        }
+    }
+}
+impl fmt::Display for Dts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.sample_entry_code().0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Codec;
+    use assert_matches::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn dts() {
+        assert_matches!(Codec::from_str("dtsc"), Ok(Codec::Dts(Dts::Dtsc)));
+        assert_matches!(Codec::from_str("dtsx"), Ok(Codec::Dts(Dts::Dtsx)));
+        for s in &["dtsc", "dtsh", "dtsl", "dtse", "dtsx", "dtsy"] {
+            assert_eq!(*s, Codec::from_str(s).unwrap().to_string());
+        }
+        assert_matches!(Codec::from_str("dtsh.1"), Err(_));
+    }
+}
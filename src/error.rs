@@ -0,0 +1,141 @@
+//! Holding the offending input text of a `CodecError` without heap allocation.
+
+use std::fmt;
+use std::ops::Deref;
+
+/// The number of bytes of input text which an [`ErrorText`] can hold
+const CAPACITY: usize = 32;
+
+/// A copy of (the start of) the input text which caused a [`CodecError`](crate::CodecError),
+/// held inline so that creating an error never allocates.
+///
+/// Text longer than 32 bytes is truncated at a character boundary, which
+/// [`is_truncated()`](ErrorText::is_truncated) reports.  The value dereferences to `str`, and
+/// can be compared with string slices directly.
+///
+/// ```rust
+/// # use rfc6381_codec::{Codec, CodecError};
+/// # use std::str::FromStr;
+/// match Codec::from_str("avc1.4D40ZZ") {
+///     Err(CodecError::InvalidComponent(text)) => {
+///         assert_eq!(text, "4D40ZZ");
+///         assert!(!text.is_truncated());
+///     }
+///     other => panic!("unexpected {:?}", other),
+/// }
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ErrorText {
+    buf: [u8; CAPACITY],
+    len: u8,
+    truncated: bool,
+}
+impl ErrorText {
+    const EMPTY: ErrorText = ErrorText {
+        buf: [0; CAPACITY],
+        len: 0,
+        truncated: false,
+    };
+
+    pub fn as_str(&self) -> &str {
+        // only whole characters are ever copied into the buffer
+        std::str::from_utf8(&self.buf[..usize::from(self.len)]).unwrap()
+    }
+    /// Whether the input text was too long to be held in full
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    fn push_str(&mut self, s: &str) {
+        if self.truncated {
+            return;
+        }
+        let len = usize::from(self.len);
+        let mut end = s.len().min(CAPACITY - len);
+        while !s.is_char_boundary(end) {
+            end -= 1;
+        }
+        self.buf[len..len + end].copy_from_slice(&s.as_bytes()[..end]);
+        self.len += end as u8;
+        self.truncated = end < s.len();
+    }
+}
+impl From<&str> for ErrorText {
+    fn from(s: &str) -> Self {
+        let mut text = ErrorText::EMPTY;
+        text.push_str(s);
+        text
+    }
+}
+impl From<fmt::Arguments<'_>> for ErrorText {
+    fn from(args: fmt::Arguments<'_>) -> Self {
+        let mut text = ErrorText::EMPTY;
+        let _ = fmt::write(&mut text, args);
+        text
+    }
+}
+impl fmt::Write for ErrorText {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.push_str(s);
+        Ok(())
+    }
+}
+impl Deref for ErrorText {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+impl PartialEq<str> for ErrorText {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+impl PartialEq<&str> for ErrorText {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+/// Displays the text, followed by `...` where it was truncated
+impl fmt::Display for ErrorText {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())?;
+        if self.truncated {
+            f.write_str("...")?;
+        }
+        Ok(())
+    }
+}
+impl fmt::Debug for ErrorText {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self.as_str())?;
+        if self.truncated {
+            f.write_str("...")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncation() {
+        let text = ErrorText::from("avc1.4D401E");
+        assert_eq!(text, "avc1.4D401E");
+        assert!(!text.is_truncated());
+        assert_eq!(format!("{:?}", text), r#""avc1.4D401E""#);
+
+        let long = "x".repeat(31) + "\u{e9}";
+        let text = ErrorText::from(long.as_str());
+        assert_eq!(text.len(), 31);
+        assert!(text.is_truncated());
+        assert_eq!(text.to_string(), "x".repeat(31) + "...");
+
+        let text = ErrorText::from(format_args!("{:02}", 7));
+        assert_eq!(text, "07");
+        assert_eq!(ErrorText::from("x".repeat(32).as_str()).len(), 32);
+    }
+}
@@ -0,0 +1,304 @@
+//! Creating `mp4a` codec values from MPEG-4 Audio decoder configuration data and ADTS headers.
+
+use crate::{CodecError, Mp4a, Mpeg2AacProfile};
+use mp4ra_rust::ObjectTypeIdentifier;
+use mpeg4_audio_const::AudioObjectType;
+use std::convert::TryFrom;
+
+const ES_DESCRIPTOR_TAG: u8 = 0x03;
+const DECODER_CONFIG_DESCRIPTOR_TAG: u8 = 0x04;
+const DECODER_SPECIFIC_INFO_TAG: u8 = 0x05;
+/// The length of an ADTS header without the optional CRC
+const ADTS_HEADER_LEN: usize = 7;
+
+impl Mp4a {
+    /// Creates a value from the given MPEG-4 Audio `AudioSpecificConfig` (the decoder specific
+    /// info for object-type-indication `0x40`), taking the audio-object-type-indication from
+    /// its `audioObjectType` field.
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::{Codec, Mp4a};
+    /// // AAC-LC, 48kHz, stereo
+    /// let mp4a = Mp4a::from_audio_specific_config(&[0x11, 0x90]).unwrap();
+    /// assert_eq!(Codec::Mp4a(mp4a).to_string(), "mp4a.40.2");
+    /// ```
+    pub fn from_audio_specific_config(asc: &[u8]) -> Result<Mp4a, CodecError> {
+        let first = *asc.first().ok_or(CodecError::ConfigurationRecordTooShort {
+            expected: 1,
+            got: 0,
+        })?;
+        let mut aot = first >> 3;
+        if aot == 31 {
+            // escape value; the actual type is 32 plus the following 6 bits
+            let second = *asc.get(1).ok_or(CodecError::ConfigurationRecordTooShort {
+                expected: 2,
+                got: asc.len(),
+            })?;
+            aot = 32 + ((first & 0b111) << 3 | second >> 5);
+        }
+        let audio_object_type = AudioObjectType::try_from(aot)
+            .map_err(|_| CodecError::InvalidComponent(format_args!("{}", aot).into()))?;
+        Ok(Mp4a::Mpeg4Audio {
+            audio_object_type: Some(audio_object_type),
+        })
+    }
+
+    /// Creates a value from the payload of an `esds` box (i.e. the full box version and flags,
+    /// followed by the `ES_Descriptor`).
+    ///
+    /// The object-type-indication is taken from the `DecoderConfigDescriptor`, and for MPEG-4
+    /// Audio, the audio-object-type-indication from the `AudioSpecificConfig` where present.
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::{Codec, Mp4a};
+    /// let esds = [
+    ///     0x00, 0x00, 0x00, 0x00, 0x03, 0x19, 0x00, 0x01, 0x00, 0x04, 0x11, 0x40, 0x15, 0x00,
+    ///     0x00, 0x00, 0x00, 0x01, 0xf4, 0x00, 0x00, 0x01, 0xf4, 0x00, 0x05, 0x02, 0x2b, 0x10,
+    ///     0x06, 0x01, 0x02,
+    /// ];
+    /// let mp4a = Mp4a::from_esds(&esds).unwrap();
+    /// assert_eq!(Codec::Mp4a(mp4a).to_string(), "mp4a.40.5");
+    /// ```
+    pub fn from_esds(esds: &[u8]) -> Result<Mp4a, CodecError> {
+        if esds.len() < 4 {
+            return Err(CodecError::ConfigurationRecordTooShort {
+                expected: 4,
+                got: esds.len(),
+            });
+        }
+        if esds[0] != 0 {
+            return Err(CodecError::UnsupportedConfigurationVersion(esds[0]));
+        }
+        let es = descriptor(&esds[4..], ES_DESCRIPTOR_TAG)?;
+        let flags = *es.get(2).ok_or(CodecError::ConfigurationRecordTooShort {
+            expected: 3,
+            got: es.len(),
+        })?;
+        let mut pos = 3;
+        if flags & 0b1000_0000 != 0 {
+            // streamDependenceFlag, so dependsOn_ES_ID is present
+            pos += 2;
+        }
+        if flags & 0b0100_0000 != 0 {
+            // URL_Flag, so a length-prefixed URLstring is present
+            let len = *es.get(pos).ok_or(CodecError::ConfigurationRecordTooShort {
+                expected: pos + 1,
+                got: es.len(),
+            })?;
+            pos += 1 + usize::from(len);
+        }
+        if flags & 0b0010_0000 != 0 {
+            // OCRstreamFlag, so OCR_ES_Id is present
+            pos += 2;
+        }
+        let rest = es
+            .get(pos..)
+            .ok_or(CodecError::ConfigurationRecordTooShort {
+                expected: pos,
+                got: es.len(),
+            })?;
+        let config = descriptor(rest, DECODER_CONFIG_DESCRIPTOR_TAG)?;
+        if config.len() < 13 {
+            return Err(CodecError::ConfigurationRecordTooShort {
+                expected: 13,
+                got: config.len(),
+            });
+        }
+        let oti = ObjectTypeIdentifier::from(config[0]);
+        if oti == ObjectTypeIdentifier::AUDIO_ISO_IEC_14496_3 && config.len() > 13 {
+            let asc = descriptor(&config[13..], DECODER_SPECIFIC_INFO_TAG)?;
+            Mp4a::from_audio_specific_config(asc)
+        } else {
+            Mp4a::from_indications(oti, None)
+        }
+    }
+
+    /// Creates a value from the `profile` field of the given ADTS frame header, for live
+    /// ingest of AAC audio where no `esds` box is available.
+    ///
+    /// Headers with the `ID` bit set signal MPEG-2 AAC, and give one of the [`Mp4a::Mpeg2Aac`]
+    /// values; otherwise the profile gives the MPEG-4 audio-object-type.
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::{Codec, Mp4a};
+    /// let adts = [0xff, 0xf1, 0x50, 0x80, 0x02, 0x1f, 0xfc];
+    /// let mp4a = Mp4a::from_adts_header(&adts).unwrap();
+    /// assert_eq!(Codec::Mp4a(mp4a).to_string(), "mp4a.40.2");
+    /// ```
+    pub fn from_adts_header(header: &[u8]) -> Result<Mp4a, CodecError> {
+        if header.len() < ADTS_HEADER_LEN {
+            return Err(CodecError::ConfigurationRecordTooShort {
+                expected: ADTS_HEADER_LEN,
+                got: header.len(),
+            });
+        }
+        if header[0] != 0xff || header[1] & 0xf0 != 0xf0 {
+            return Err(CodecError::InvalidSyncWord);
+        }
+        let profile = header[2] >> 6;
+        if header[1] & 0b1000 == 0 {
+            // MPEG-4, where the profile is one less than the audio object type
+            return Mp4a::from_indications(
+                ObjectTypeIdentifier::AUDIO_ISO_IEC_14496_3,
+                Some(profile + 1),
+            );
+        }
+        let profile = match profile {
+            0 => Mpeg2AacProfile::Main,
+            1 => Mpeg2AacProfile::LowComplexity,
+            2 => Mpeg2AacProfile::ScalableSamplingRate,
+            _ => {
+                return Err(CodecError::InvalidComponent(
+                    format_args!("{}", profile).into(),
+                ))
+            }
+        };
+        Ok(Mp4a::Mpeg2Aac {
+            profile,
+            audio_object_type_indication: None,
+        })
+    }
+}
+
+/// Returns the body of the descriptor at the start of `data`, checking that it has the given
+/// tag value.
+fn descriptor(data: &[u8], tag: u8) -> Result<&[u8], CodecError> {
+    let too_short = |expected| CodecError::ConfigurationRecordTooShort {
+        expected,
+        got: data.len(),
+    };
+    let got = *data.first().ok_or_else(|| too_short(1))?;
+    if got != tag {
+        return Err(CodecError::UnexpectedDescriptor { expected: tag, got });
+    }
+    // the size is encoded in up to four bytes, seven bits at a time
+    let mut size = 0usize;
+    let mut pos = 1;
+    loop {
+        let b = *data.get(pos).ok_or_else(|| too_short(pos + 1))?;
+        size = size << 7 | usize::from(b & 0x7f);
+        pos += 1;
+        if b & 0x80 == 0 || pos == 5 {
+            break;
+        }
+    }
+    data.get(pos..pos + size)
+        .ok_or_else(|| too_short(pos + size))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Codec;
+    use assert_matches::*;
+
+    fn asc(asc: &[u8]) -> String {
+        Codec::Mp4a(Mp4a::from_audio_specific_config(asc).unwrap()).to_string()
+    }
+
+    #[test]
+    fn audio_specific_config() {
+        assert_eq!(asc(&[0x12, 0x10]), "mp4a.40.2");
+        assert_eq!(asc(&[0x2b, 0x92, 0x08, 0x00]), "mp4a.40.5");
+        assert_eq!(asc(&[0xeb, 0x09, 0x88, 0x00]), "mp4a.40.29");
+        // escaped values, for USAC (42) and Layer-3 (34)
+        assert_eq!(asc(&[0xf9, 0x46, 0x40]), "mp4a.40.42");
+        assert_eq!(asc(&[0xf8, 0x46, 0x40]), "mp4a.40.34");
+        assert_matches!(
+            Mp4a::from_audio_specific_config(&[]),
+            Err(CodecError::ConfigurationRecordTooShort {
+                expected: 1,
+                got: 0
+            })
+        );
+        assert_matches!(
+            Mp4a::from_audio_specific_config(&[0xf9]),
+            Err(CodecError::ConfigurationRecordTooShort {
+                expected: 2,
+                got: 1
+            })
+        );
+    }
+
+    #[test]
+    fn adts() {
+        let adts = |id: u8, profile: u8| {
+            let header = [
+                0xff,
+                0xf1 | id << 3,
+                profile << 6 | 0x10,
+                0x80,
+                0x02,
+                0x1f,
+                0xfc,
+            ];
+            Codec::Mp4a(Mp4a::from_adts_header(&header).unwrap()).to_string()
+        };
+        assert_eq!(adts(0, 0), "mp4a.40.1");
+        assert_eq!(adts(0, 3), "mp4a.40.4");
+        assert_eq!(adts(1, 1), "mp4a.67");
+        assert_eq!(adts(1, 2), "mp4a.68");
+        assert_matches!(
+            Mp4a::from_adts_header(&[0xff, 0xf9, 0xd0, 0x80, 0x02, 0x1f, 0xfc]),
+            Err(CodecError::InvalidComponent(_))
+        );
+        assert_matches!(
+            Mp4a::from_adts_header(&[0xff, 0x01, 0x50, 0x80, 0x02, 0x1f, 0xfc]),
+            Err(CodecError::InvalidSyncWord)
+        );
+        assert_matches!(
+            Mp4a::from_adts_header(&[0xff, 0xf1, 0x50]),
+            Err(CodecError::ConfigurationRecordTooShort {
+                expected: 7,
+                got: 3
+            })
+        );
+    }
+
+    #[test]
+    fn esds() {
+        let esds = |oti: u8, dsi: &[u8]| {
+            let mut config = vec![oti, 0x15, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+            if !dsi.is_empty() {
+                config.extend_from_slice(&[DECODER_SPECIFIC_INFO_TAG, dsi.len() as u8]);
+                config.extend_from_slice(dsi);
+            }
+            // ES_ID, and flags indicating a dependsOn_ES_ID and OCR_ES_Id are present
+            let mut es = vec![0x00, 0x01, 0xa0, 0x00, 0x02, 0x00, 0x03];
+            es.extend_from_slice(&[
+                DECODER_CONFIG_DESCRIPTOR_TAG,
+                0x80,
+                0x80,
+                config.len() as u8,
+            ]);
+            es.extend_from_slice(&config);
+            let mut esds = vec![0, 0, 0, 0, ES_DESCRIPTOR_TAG, es.len() as u8];
+            esds.extend_from_slice(&es);
+            esds
+        };
+        let to_string = |esds: &[u8]| Codec::Mp4a(Mp4a::from_esds(esds).unwrap()).to_string();
+        assert_eq!(to_string(&esds(0x40, &[0x12, 0x10])), "mp4a.40.2");
+        assert_eq!(to_string(&esds(0x40, &[])), "mp4a.40");
+        assert_eq!(to_string(&esds(0x6b, &[])), "mp4a.6b");
+        assert_eq!(to_string(&esds(0x67, &[])), "mp4a.67");
+
+        let mut bad = esds(0x40, &[0x12, 0x10]);
+        bad[4] = DECODER_CONFIG_DESCRIPTOR_TAG;
+        assert_matches!(
+            Mp4a::from_esds(&bad),
+            Err(CodecError::UnexpectedDescriptor {
+                expected: ES_DESCRIPTOR_TAG,
+                got: DECODER_CONFIG_DESCRIPTOR_TAG
+            })
+        );
+        let truncated = esds(0x40, &[0x12, 0x10]);
+        assert_matches!(
+            Mp4a::from_esds(&truncated[..truncated.len() - 1]),
+            Err(CodecError::ConfigurationRecordTooShort { .. })
+        );
+        assert_matches!(
+            Mp4a::from_esds(&[1, 0, 0, 0]),
+            Err(CodecError::UnsupportedConfigurationVersion(1))
+        );
+    }
+}
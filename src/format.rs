@@ -0,0 +1,144 @@
+//! Formatting codec values with a consistent letter case and padding for hexadecimal elements.
+
+use crate::Codec;
+
+/// The letter case to use for hexadecimal digits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HexCase {
+    Upper,
+    Lower,
+}
+
+/// Options controlling [`Codec::format_with()`].
+///
+/// By default the output is the same as the `Display` implementation, which follows the
+/// conventions of each codec's specification; for example `avc1.4D401E` uses uppercase
+/// hexadecimal, but `mp4a.6b` uses lowercase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct FormatOptions {
+    hex_case: Option<HexCase>,
+    zero_padding: bool,
+}
+impl FormatOptions {
+    pub fn new() -> FormatOptions {
+        FormatOptions::default()
+    }
+    /// Write all hexadecimal elements using the given letter case
+    pub fn with_hex_case(mut self, hex_case: HexCase) -> FormatOptions {
+        self.hex_case = Some(hex_case);
+        self
+    }
+    /// Pad single-byte elements which are otherwise written with as few digits as possible to
+    /// two digits; these are the constraint indicator bytes of `hvc1` / `hev1`, and the audio
+    /// object type of `mp4a`
+    pub fn with_zero_padding(mut self, zero_padding: bool) -> FormatOptions {
+        self.zero_padding = zero_padding;
+        self
+    }
+    pub fn hex_case(&self) -> Option<HexCase> {
+        self.hex_case
+    }
+    pub fn zero_padding(&self) -> bool {
+        self.zero_padding
+    }
+}
+
+impl Codec {
+    /// Formats this codec value using the given options.
+    ///
+    /// The letter case only applies to elements which the codec's specification defines as
+    /// hexadecimal, so for example the tier letter of `hvc1.1.6.L93.B0` is unaffected.
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::{Codec, FormatOptions, HexCase};
+    /// # use std::str::FromStr;
+    /// let lower = FormatOptions::new().with_hex_case(HexCase::Lower);
+    /// let codec = Codec::from_str("avc1.4D401E").unwrap();
+    /// assert_eq!(codec.format_with(&lower), "avc1.4d401e");
+    ///
+    /// let upper = FormatOptions::new()
+    ///     .with_hex_case(HexCase::Upper)
+    ///     .with_zero_padding(true);
+    /// let codec = Codec::from_str("mp4a.6b").unwrap();
+    /// assert_eq!(codec.format_with(&upper), "mp4a.6B");
+    /// # #[cfg(feature = "hevc")]
+    /// let codec = Codec::from_str("hvc1.1.6.L93.b0").unwrap();
+    /// # #[cfg(feature = "hevc")]
+    /// assert_eq!(codec.format_with(&upper), "hvc1.1.6.L93.B0");
+    /// # #[cfg(feature = "hevc")]
+    /// let codec = Codec::from_str("hvc1.1.6.L93.90.8").unwrap();
+    /// # #[cfg(feature = "hevc")]
+    /// assert_eq!(codec.format_with(&upper), "hvc1.1.6.L93.90.08");
+    /// ```
+    pub fn format_with(&self, options: &FormatOptions) -> String {
+        let value = self.to_string();
+        let mut elements: Vec<String> = value.split('.').map(str::to_owned).collect();
+        // the indexes of the hexadecimal elements, and of the elements to zero-pad
+        let (hex, padded): (&[usize], &[usize]) = match self {
+            Codec::Avc1(_) => (&[1], &[]),
+            #[cfg(feature = "mpegh")]
+            Codec::Mpegh(_) => (&[1], &[]),
+            Codec::Avs3(_) => (&[1, 2], &[]),
+            #[cfg(feature = "dolby")]
+            Codec::Ac4(_) => (&[1, 2, 3], &[]),
+            Codec::Mp4a(_) => (&[1], &[2]),
+            #[cfg(feature = "hevc")]
+            Codec::Hevc(_) => (&[2, 4, 5, 6, 7, 8, 9], &[4, 5, 6, 7, 8, 9]),
+            _ => return value,
+        };
+        for (index, element) in elements.iter_mut().enumerate() {
+            if options.zero_padding && padded.contains(&index) && element.len() == 1 {
+                element.insert(0, '0');
+            }
+            if hex.contains(&index) {
+                // the `mha1.0x0D` form has a prefix which is not itself part of the value
+                let start = if element.starts_with("0x") { 2 } else { 0 };
+                match options.hex_case {
+                    Some(HexCase::Upper) => element[start..].make_ascii_uppercase(),
+                    Some(HexCase::Lower) => element[start..].make_ascii_lowercase(),
+                    None => {}
+                }
+            }
+        }
+        elements.join(".")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn format(codec: &str, options: FormatOptions) -> String {
+        Codec::from_str(codec).unwrap().format_with(&options)
+    }
+
+    #[test]
+    fn hex_case() {
+        let lower = FormatOptions::new().with_hex_case(HexCase::Lower);
+        let upper = FormatOptions::new().with_hex_case(HexCase::Upper);
+        assert_eq!(format("avc1.4d401e", FormatOptions::new()), "avc1.4D401E");
+        assert_eq!(format("mp4a.6B", FormatOptions::new()), "mp4a.6b");
+        assert_eq!(format("mp4a.A5.1", upper), "mp4a.A5.1");
+        #[cfg(feature = "hevc")]
+        assert_eq!(format("hvc1.2.4.H120.B0", lower), "hvc1.2.4.H120.b0");
+        #[cfg(feature = "hevc")]
+        assert_eq!(format("hev1.A4.1F.L120.B0", lower), "hev1.A4.1f.L120.b0");
+        #[cfg(feature = "mpegh")]
+        assert_eq!(format("mhm1.0x0D", lower), "mhm1.0x0d");
+        assert_eq!(format("avs3.20.0A", lower), "avs3.20.0a");
+        #[cfg(feature = "vp9")]
+        assert_eq!(format("vp09.00.10.08", upper), "vp09.00.10.08");
+    }
+
+    #[test]
+    fn zero_padding() {
+        let padded = FormatOptions::new().with_zero_padding(true);
+        assert_eq!(format("mp4a.40.2", padded), "mp4a.40.02");
+        assert_eq!(format("mp4a.40.42", padded), "mp4a.40.42");
+        assert_eq!(format("mp4a.40", padded), "mp4a.40");
+        #[cfg(feature = "hevc")]
+        assert_eq!(format("hvc1.1.6.L93.B0.1", padded), "hvc1.1.6.L93.B0.01");
+        assert_eq!(format("avc1.4d001e", padded), "avc1.4D001E");
+    }
+}
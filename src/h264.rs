@@ -0,0 +1,29 @@
+//! Integration with the `h264-reader` crate, enabled by the `h264-reader` cargo feature.
+
+use crate::Avc1;
+use h264_reader::nal::sps::SeqParameterSet;
+
+impl Avc1 {
+    /// Creates a value from a sequence parameter set that has been parsed by the `h264-reader`
+    /// crate.
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::{Avc1, Codec};
+    /// use h264_reader::nal::sps::SeqParameterSet;
+    /// use h264_reader::rbsp::BitReader;
+    ///
+    /// let rbsp = [
+    ///     0x64, 0x00, 0x0a, 0xac, 0x72, 0x84, 0x44, 0x26, 0x84, 0x00, 0x00, 0x00, 0x04, 0x00,
+    ///     0x00, 0x00, 0xca, 0x3c, 0x48, 0x96, 0x11, 0x80,
+    /// ];
+    /// let sps = SeqParameterSet::from_bits(BitReader::new(&rbsp[..])).unwrap();
+    /// assert_eq!(Codec::Avc1(Avc1::from_sps(&sps)).to_string(), "avc1.64000A");
+    /// ```
+    pub fn from_sps(sps: &SeqParameterSet) -> Avc1 {
+        Avc1 {
+            profile: sps.profile_idc.into(),
+            constraints: sps.constraint_flags.into(),
+            level: sps.level_idc,
+        }
+    }
+}
@@ -0,0 +1,147 @@
+//! Creating `hvc1` / `hev1` codec values from H.265 parameter set NAL units, enabled by the
+//! `h265` cargo feature.
+
+use crate::{CodecError, Hevc, HevcSampleEntry};
+
+const VPS_NUT: u8 = 32;
+const SPS_NUT: u8 = 33;
+
+/// The length of the general part of the `profile_tier_level()` syntax structure
+const GENERAL_PROFILE_TIER_LEVEL_LEN: usize = 12;
+
+impl Hevc {
+    /// Creates a value from the `profile_tier_level()` of the given H.265 sequence parameter
+    /// set NAL unit, for sources (such as Annex B byte streams or MPEG-TS) that have no `hvcC`
+    /// box.
+    ///
+    /// The `nal` data must start with the two-byte NAL unit header, without any Annex B start
+    /// code, but may still contain emulation prevention bytes.
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::{Codec, Hevc, HevcSampleEntry};
+    /// let sps = [
+    ///     0x42, 0x01, 0x01, 0x01, 0x60, 0x00, 0x00, 0x03, 0x00, 0x90, 0x00, 0x00, 0x03, 0x00,
+    ///     0x00, 0x03, 0x00, 0x5d, 0xa0, 0x02, 0x80, 0x80, 0x2d, 0x16, 0x59,
+    /// ];
+    /// let hevc = Hevc::from_sps_nal(HevcSampleEntry::Hev1, &sps).unwrap();
+    /// assert_eq!(Codec::Hevc(hevc).to_string(), "hev1.1.6.L93.90");
+    /// ```
+    pub fn from_sps_nal(sample_entry: HevcSampleEntry, nal: &[u8]) -> Result<Hevc, CodecError> {
+        // sps_video_parameter_set_id, sps_max_sub_layers_minus1 and
+        // sps_temporal_id_nesting_flag occupy the byte before profile_tier_level()
+        from_parameter_set(sample_entry, nal, SPS_NUT, 1)
+    }
+
+    /// Creates a value from the `profile_tier_level()` of the given H.265 video parameter set
+    /// NAL unit.  The `nal` data is given in the same way as for [`Hevc::from_sps_nal()`].
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::{Codec, Hevc, HevcSampleEntry};
+    /// let vps = [
+    ///     0x40, 0x01, 0x0c, 0x01, 0xff, 0xff, 0x01, 0x60, 0x00, 0x00, 0x03, 0x00, 0x90, 0x00,
+    ///     0x00, 0x03, 0x00, 0x00, 0x03, 0x00, 0x5d, 0x95, 0x98, 0x09,
+    /// ];
+    /// let hevc = Hevc::from_vps_nal(HevcSampleEntry::Hvc1, &vps).unwrap();
+    /// assert_eq!(Codec::Hevc(hevc).to_string(), "hvc1.1.6.L93.90");
+    /// ```
+    pub fn from_vps_nal(sample_entry: HevcSampleEntry, nal: &[u8]) -> Result<Hevc, CodecError> {
+        // vps_video_parameter_set_id to vps_reserved_0xffff_16bits occupy the four bytes before
+        // profile_tier_level()
+        from_parameter_set(sample_entry, nal, VPS_NUT, 4)
+    }
+}
+
+fn from_parameter_set(
+    sample_entry: HevcSampleEntry,
+    nal: &[u8],
+    expected_nal_unit_type: u8,
+    ptl_offset: usize,
+) -> Result<Hevc, CodecError> {
+    let rbsp_len = ptl_offset + GENERAL_PROFILE_TIER_LEVEL_LEN;
+    if nal.len() < 2 {
+        return Err(CodecError::ConfigurationRecordTooShort {
+            expected: 2 + rbsp_len,
+            got: nal.len(),
+        });
+    }
+    let nal_unit_type = (nal[0] >> 1) & 0b11_1111;
+    if nal_unit_type != expected_nal_unit_type {
+        return Err(CodecError::UnexpectedNalUnitType(nal_unit_type));
+    }
+    let rbsp = rbsp_prefix(&nal[2..], rbsp_len);
+    if rbsp.len() < rbsp_len {
+        // lengths are reported without counting any emulation prevention bytes
+        return Err(CodecError::ConfigurationRecordTooShort {
+            expected: 2 + rbsp_len,
+            got: 2 + rbsp.len(),
+        });
+    }
+    Ok(Hevc::from_profile_tier_level(
+        sample_entry,
+        &rbsp[ptl_offset..],
+    ))
+}
+
+/// Returns up to `len` bytes from the start of the given NAL unit payload, with any emulation
+/// prevention bytes removed.
+fn rbsp_prefix(payload: &[u8], len: usize) -> Vec<u8> {
+    let mut rbsp = Vec::with_capacity(len);
+    let mut zeros = 0;
+    for &b in payload {
+        if rbsp.len() == len {
+            break;
+        }
+        if zeros >= 2 && b == 0x03 {
+            zeros = 0;
+            continue;
+        }
+        zeros = if b == 0 { zeros + 1 } else { 0 };
+        rbsp.push(b);
+    }
+    rbsp
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Codec, HevcLevel, HevcProfile, HevcTier};
+    use assert_matches::*;
+
+    const SPS: [u8; 18] = [
+        0x42, 0x01, 0x01, 0x22, 0x20, 0x00, 0x00, 0x03, 0x00, 0xb0, 0x00, 0x00, 0x03, 0x00, 0x00,
+        0x03, 0x00, 0x99,
+    ];
+
+    #[test]
+    fn from_sps_nal() {
+        let hevc = Hevc::from_sps_nal(HevcSampleEntry::Hvc1, &SPS).unwrap();
+        assert_eq!(hevc.profile_enum(), HevcProfile::Main10);
+        assert_eq!(hevc.tier(), HevcTier::High);
+        assert_eq!(hevc.level_enum(), Some(HevcLevel::L5_1));
+        assert_eq!(Codec::Hevc(hevc).to_string(), "hvc1.2.4.H153.B0");
+        assert_matches!(
+            Hevc::from_sps_nal(HevcSampleEntry::Hvc1, &SPS[..17]),
+            Err(CodecError::ConfigurationRecordTooShort {
+                expected: 15,
+                got: 14
+            })
+        );
+        assert_matches!(
+            Hevc::from_sps_nal(HevcSampleEntry::Hvc1, &SPS[..1]),
+            Err(CodecError::ConfigurationRecordTooShort { .. })
+        );
+        assert_matches!(
+            Hevc::from_vps_nal(HevcSampleEntry::Hvc1, &SPS),
+            Err(CodecError::UnexpectedNalUnitType(SPS_NUT))
+        );
+    }
+
+    #[test]
+    fn emulation_prevention() {
+        assert_eq!(
+            rbsp_prefix(&[0x00, 0x00, 0x03, 0x00, 0x00, 0x03, 0x03, 0x01], 8),
+            [0x00, 0x00, 0x00, 0x00, 0x03, 0x01]
+        );
+        assert_eq!(rbsp_prefix(&[0x00, 0x00, 0x03, 0x01], 2), [0x00, 0x00]);
+    }
+}
@@ -0,0 +1,126 @@
+//! Validation of codecs parameters for HEIF and AVIF image media types.
+//!
+//! The `image/heif`, `image/heic` and `image/avif` media types (and their `-sequence`
+//! equivalents) use the same codecs parameter syntax as video, but only some codec families may
+//! be used to code the image items.
+
+use crate::{Codec, CodecError};
+
+struct ImageType {
+    media_types: &'static [&'static str],
+    /// sample entry / item type codes which may code the images
+    codecs: &'static [&'static str],
+    /// brands which some producers list alongside the image codecs
+    brands: &'static [&'static str],
+}
+
+const IMAGE_TYPES: [ImageType; 3] = [
+    ImageType {
+        media_types: &["image/avif", "image/avif-sequence"],
+        codecs: &["av01"],
+        brands: &["avif", "avis", "mif1", "msf1", "miaf"],
+    },
+    ImageType {
+        media_types: &["image/heic", "image/heic-sequence"],
+        codecs: &["hvc1", "hev1"],
+        brands: &["heic", "heix", "hevc", "hevx", "mif1", "msf1", "miaf"],
+    },
+    ImageType {
+        media_types: &["image/heif", "image/heif-sequence"],
+        codecs: &[
+            "hvc1", "hev1", "av01", "avc1", "avc3", "vvc1", "vvi1", "j2ki", "mjpg",
+        ],
+        brands: &["mif1", "msf1", "miaf", "heic", "heix", "avif", "avis"],
+    },
+];
+
+impl Codec {
+    /// Parses a comma-separated codecs list given for the specified media type.
+    ///
+    /// For the HEIF family of image media types (`image/heif`, `image/heic`, `image/avif` and
+    /// their `-sequence` variants) each entry is checked to be a codec that may be used with that
+    /// type, and an entry that is not permitted produces
+    /// [`CodecError::UnexpectedForMediaType`].  Image brands such as `mif1` or `avif` are
+    /// accepted as entries and produce `Codec::Other`.
+    ///
+    /// Entries for any other media type are parsed as for [`Codec::parse_codecs()`].
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::Codec;
+    /// let mut i = Codec::parse_for_mime("image/avif", "av01.0.04M.08,hvc1.1.6.L93.B0");
+    /// assert!(i.next().unwrap().is_ok());
+    /// assert!(i.next().unwrap().is_err());
+    /// ```
+    pub fn parse_for_mime<'a>(
+        mime: &str,
+        codecs: &'a str,
+    ) -> impl Iterator<Item = Result<Codec, CodecError>> + 'a {
+        let media_type = mime.split(';').next().unwrap().trim().to_ascii_lowercase();
+        let image_type = IMAGE_TYPES
+            .iter()
+            .find(|t| t.media_types.contains(&&media_type[..]));
+        codecs.split(',').map(move |s| {
+            let s = s.trim();
+            match image_type {
+                Some(image_type) => parse_image_codec(image_type, &media_type, s),
+                None => s.parse(),
+            }
+        })
+    }
+}
+
+fn parse_image_codec(
+    image_type: &ImageType,
+    media_type: &str,
+    codec: &str,
+) -> Result<Codec, CodecError> {
+    if image_type.brands.contains(&codec) {
+        return Ok(Codec::Other(codec.into()));
+    }
+    let fourcc = codec.split('.').next().unwrap();
+    if image_type.codecs.contains(&fourcc) {
+        codec.parse()
+    } else {
+        Err(CodecError::UnexpectedForMediaType {
+            media_type: media_type.into(),
+            codec: codec.into(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::*;
+
+    #[test]
+    fn avif() {
+        let mut i = Codec::parse_for_mime("image/avif", "av01.0.04M.08, avif, mp4a.40.2");
+        assert_matches!(i.next(), Some(Ok(_)));
+        assert_matches!(i.next(), Some(Ok(Codec::Other(v))) if v.as_str() == "avif");
+        assert_matches!(
+            i.next(),
+            Some(Err(CodecError::UnexpectedForMediaType { media_type, codec }))
+                if media_type == "image/avif" && codec == "mp4a.40.2"
+        );
+        assert_matches!(i.next(), None);
+    }
+
+    #[test]
+    fn heic() {
+        let mut i = Codec::parse_for_mime("Image/HEIC", "hvc1.1.6.L93.B0,av01.0.04M.08");
+        assert_matches!(i.next(), Some(Ok(_)));
+        assert_matches!(
+            i.next(),
+            Some(Err(CodecError::UnexpectedForMediaType { .. }))
+        );
+        let mut i = Codec::parse_for_mime("image/heif", "hvc1.1.6.L93.B0,av01.0.04M.08,mif1");
+        assert!(i.all(|c| c.is_ok()));
+    }
+
+    #[test]
+    fn other_media_type() {
+        let mut i = Codec::parse_for_mime("video/mp4; profiles=isom", "mp4a.40.2");
+        assert_matches!(i.next(), Some(Ok(Codec::Mp4a(_))));
+    }
+}
@@ -0,0 +1,680 @@
+//! Support for H.265 / HEVC codec strings.
+
+use crate::{parse_decimal, CodecError};
+use mp4ra_rust::SampleEntryCode;
+use std::fmt;
+
+/// The sample entry codes used for HEVC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HevcSampleEntry {
+    /// Parameter sets are only carried in the sample entry
+    Hvc1,
+    /// Parameter sets may also be carried in-band
+    Hev1,
+}
+impl HevcSampleEntry {
+    pub(crate) fn from_sample_entry(sample_entry: SampleEntryCode) -> Option<HevcSampleEntry> {
+        match sample_entry {
+            SampleEntryCode::HVC1 => Some(HevcSampleEntry::Hvc1),
+            SampleEntryCode::HEV1 => Some(HevcSampleEntry::Hev1),
+            _ => None,
+        }
+    }
+
+    pub fn sample_entry_code(&self) -> SampleEntryCode {
+        match self {
+            HevcSampleEntry::Hvc1 => SampleEntryCode::HVC1,
+            HevcSampleEntry::Hev1 => SampleEntryCode::HEV1,
+        }
+    }
+}
+
+/// An HEVC codec parameter, like `hvc1.2.4.L153.B0`, as specified in ISO/IEC 14496-15 Annex E.
+///
+/// The elements following the sample entry code are,
+///
+///  1. the `general_profile_space` (as a letter `A`, `B` or `C`, or omitted for the value zero)
+///     followed by the decimal `general_profile_idc`
+///  2. the 32 `general_profile_compatibility_flags` in hexadecimal, in _reverse_ bit order
+///  3. the `general_tier_flag` (`L` for Main tier or `H` for High tier) followed by the decimal
+///     `general_level_idc`
+///  4. up to six bytes of constraint flags in hexadecimal, each as a separate element, with any
+///     trailing zero bytes omitted
+///
+/// ```rust
+/// # use rfc6381_codec::{Codec, HevcProfile, HevcTier, HevcLevel};
+/// # use std::str::FromStr;
+/// if let Ok(Codec::Hevc(hevc)) = Codec::from_str("hvc1.2.4.L153.B0") {
+///     assert_eq!(hevc.profile_enum(), HevcProfile::Main10);
+///     assert_eq!(hevc.tier(), HevcTier::Main);
+///     assert_eq!(hevc.level_enum(), Some(HevcLevel::L5_1));
+///     assert!(hevc.progressive_source_flag());
+///     assert!(hevc.frame_only_constraint_flag());
+/// } else {
+///     panic!("unexpected codec type");
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Hevc {
+    pub(crate) sample_entry: HevcSampleEntry,
+    pub(crate) profile_space: u8,
+    pub(crate) profile_idc: u8,
+    pub(crate) profile_compatibility_flags: u32,
+    pub(crate) tier: HevcTier,
+    pub(crate) level_idc: u8,
+    pub(crate) constraint_indicator_flags: [u8; 6],
+}
+impl Hevc {
+    /// Creates a value with the given fields.
+    ///
+    /// The `profile_compatibility_flags` value must have bit _j_ set for each
+    /// `general_profile_compatibility_flag[j]` (i.e. the bit order used in the codec string,
+    /// rather than the bit order used in the bitstream).  Only the low two bits of
+    /// `profile_space` and the low five bits of `profile_idc` are kept, matching the sizes of
+    /// the bitstream fields.
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::{Codec, Hevc, HevcSampleEntry, HevcTier};
+    /// let flags = [0xb0, 0, 0, 0, 0, 0];
+    /// let hevc = Hevc::new(HevcSampleEntry::Hvc1, 0, 2, 0x4, HevcTier::Main, 120, flags);
+    /// assert_eq!(Codec::Hevc(hevc).to_string(), "hvc1.2.4.L120.B0");
+    /// ```
+    pub const fn new(
+        sample_entry: HevcSampleEntry,
+        profile_space: u8,
+        profile_idc: u8,
+        profile_compatibility_flags: u32,
+        tier: HevcTier,
+        level_idc: u8,
+        constraint_indicator_flags: [u8; 6],
+    ) -> Hevc {
+        Hevc {
+            sample_entry,
+            profile_space: profile_space & 0b11,
+            profile_idc: profile_idc & 0x1f,
+            profile_compatibility_flags,
+            tier,
+            level_idc,
+            constraint_indicator_flags,
+        }
+    }
+    /// Creates a value from the payload of an `hvcC` box (i.e. the
+    /// `HEVCDecoderConfigurationRecord` defined in ISO/IEC 14496-15, without the box header).
+    ///
+    /// The record does not indicate which sample entry it belongs to, so this must be given
+    /// too.
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::{Codec, Hevc, HevcSampleEntry};
+    /// let hvcc = [
+    ///     0x01, 0x02, 0x20, 0x00, 0x00, 0x00, 0xb0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x99, 0xf0,
+    /// ];
+    /// let hevc = Hevc::from_hvcc(HevcSampleEntry::Hvc1, &hvcc).unwrap();
+    /// assert_eq!(Codec::Hevc(hevc).to_string(), "hvc1.2.4.L153.B0");
+    /// ```
+    pub fn from_hvcc(sample_entry: HevcSampleEntry, hvcc: &[u8]) -> Result<Hevc, CodecError> {
+        if hvcc.len() < 13 {
+            return Err(CodecError::ConfigurationRecordTooShort {
+                expected: 13,
+                got: hvcc.len(),
+            });
+        }
+        if hvcc[0] != 1 {
+            return Err(CodecError::UnsupportedConfigurationVersion(hvcc[0]));
+        }
+        Ok(Hevc::from_profile_tier_level(sample_entry, &hvcc[1..13]))
+    }
+
+    /// Creates a value from the twelve bytes holding the `general_profile_space` to
+    /// `general_level_idc` fields, which are laid out the same way in both the
+    /// `profile_tier_level()` syntax structure and the `HEVCDecoderConfigurationRecord`.
+    pub(crate) fn from_profile_tier_level(sample_entry: HevcSampleEntry, ptl: &[u8]) -> Hevc {
+        let mut compat = [0; 4];
+        compat.copy_from_slice(&ptl[1..5]);
+        let mut constraint_indicator_flags = [0; 6];
+        constraint_indicator_flags.copy_from_slice(&ptl[5..11]);
+        Hevc {
+            sample_entry,
+            profile_space: ptl[0] >> 6,
+            profile_idc: ptl[0] & 0b1_1111,
+            // the general_profile_compatibility_flag[0] value is in the most significant bit
+            profile_compatibility_flags: u32::from_be_bytes(compat).reverse_bits(),
+            tier: if ptl[0] & 0b10_0000 != 0 {
+                HevcTier::High
+            } else {
+                HevcTier::Main
+            },
+            level_idc: ptl[11],
+            constraint_indicator_flags,
+        }
+    }
+    pub fn sample_entry(&self) -> HevcSampleEntry {
+        self.sample_entry
+    }
+    pub fn profile_space(&self) -> u8 {
+        self.profile_space
+    }
+    pub fn profile_idc(&self) -> u8 {
+        self.profile_idc
+    }
+    /// The `general_profile_compatibility_flags`, with bit _j_ of the result holding
+    /// `general_profile_compatibility_flag[j]`
+    pub fn profile_compatibility_flags(&self) -> u32 {
+        self.profile_compatibility_flags
+    }
+    /// Returns the value of `general_profile_compatibility_flag[j]`.
+    ///
+    /// Panics if `j` is greater than 31.
+    pub fn profile_compatibility_flag(&self, j: usize) -> bool {
+        assert!(j < 32);
+        self.profile_compatibility_flags & (1 << j) != 0
+    }
+    pub fn tier(&self) -> HevcTier {
+        self.tier
+    }
+    pub fn level_idc(&self) -> u8 {
+        self.level_idc
+    }
+    /// The 48 bits of general constraint indicator flags, with the first flag in the most
+    /// significant bit of the first byte
+    pub fn constraint_indicator_flags(&self) -> [u8; 6] {
+        self.constraint_indicator_flags
+    }
+
+    /// Interprets the `general_profile_idc` value.
+    ///
+    /// Where `general_profile_idc` is zero, the profile is taken from the lowest-numbered
+    /// `general_profile_compatibility_flag` which is set, if any.
+    pub fn profile_enum(&self) -> HevcProfile {
+        if self.profile_idc == 0 && self.profile_compatibility_flags != 0 {
+            HevcProfile::from(self.profile_compatibility_flags.trailing_zeros() as u8)
+        } else {
+            HevcProfile::from(self.profile_idc)
+        }
+    }
+    /// Interprets the `general_level_idc` value, returning `None` if the value is not one defined
+    /// by the H.265 spec.
+    pub fn level_enum(&self) -> Option<HevcLevel> {
+        HevcLevel::ALL
+            .iter()
+            .find(|l| l.level_idc() == self.level_idc)
+            .copied()
+    }
+
+    fn constraint_flag(&self, bit: usize) -> bool {
+        self.constraint_indicator_flags[bit / 8] & (0x80 >> (bit % 8)) != 0
+    }
+    pub fn progressive_source_flag(&self) -> bool {
+        self.constraint_flag(0)
+    }
+    pub fn interlaced_source_flag(&self) -> bool {
+        self.constraint_flag(1)
+    }
+    pub fn non_packed_constraint_flag(&self) -> bool {
+        self.constraint_flag(2)
+    }
+    pub fn frame_only_constraint_flag(&self) -> bool {
+        self.constraint_flag(3)
+    }
+    /// The `general_one_picture_only_constraint_flag`, signalling a still-picture stream.  This
+    /// flag is defined for the Main 10 and format range extensions profiles, and in the same
+    /// constraint-bit position in both cases.
+    pub fn one_picture_only_constraint_flag(&self) -> bool {
+        self.constraint_flag(11)
+    }
+    /// `general_max_12bit_constraint_flag`, defined for the format range extensions profiles
+    /// and those derived from them
+    pub fn max_12bit_constraint_flag(&self) -> bool {
+        self.constraint_flag(4)
+    }
+    /// `general_max_10bit_constraint_flag`, defined for the format range extensions profiles
+    /// and those derived from them
+    pub fn max_10bit_constraint_flag(&self) -> bool {
+        self.constraint_flag(5)
+    }
+    /// `general_max_8bit_constraint_flag`, defined for the format range extensions profiles
+    /// and those derived from them
+    pub fn max_8bit_constraint_flag(&self) -> bool {
+        self.constraint_flag(6)
+    }
+    /// `general_max_422chroma_constraint_flag`, defined for the format range extensions
+    /// profiles and those derived from them
+    pub fn max_422chroma_constraint_flag(&self) -> bool {
+        self.constraint_flag(7)
+    }
+    /// `general_max_420chroma_constraint_flag`, defined for the format range extensions
+    /// profiles and those derived from them
+    pub fn max_420chroma_constraint_flag(&self) -> bool {
+        self.constraint_flag(8)
+    }
+    /// `general_max_monochrome_constraint_flag`, defined for the format range extensions
+    /// profiles and those derived from them
+    pub fn max_monochrome_constraint_flag(&self) -> bool {
+        self.constraint_flag(9)
+    }
+    /// `general_intra_constraint_flag`, defined for the format range extensions profiles and
+    /// those derived from them
+    pub fn intra_constraint_flag(&self) -> bool {
+        self.constraint_flag(10)
+    }
+    /// `general_lower_bit_rate_constraint_flag`, defined for the format range extensions
+    /// profiles and those derived from them
+    pub fn lower_bit_rate_constraint_flag(&self) -> bool {
+        self.constraint_flag(12)
+    }
+
+    /// Parses the sub-elements following the sample entry code.
+    pub(crate) fn parse(sample_entry: HevcSampleEntry, value: &str) -> Result<Hevc, CodecError> {
+        let mut i = value.split('.');
+        let profile = i.next().unwrap();
+        let (profile_space, profile_idc) = match profile.as_bytes().first() {
+            Some(b'A') => (1, &profile[1..]),
+            Some(b'B') => (2, &profile[1..]),
+            Some(b'C') => (3, &profile[1..]),
+            _ => (0, profile),
+        };
+        let profile_idc = parse_decimal(profile_idc, 31)?;
+
+        let compat = i
+            .next()
+            .ok_or_else(|| CodecError::ExpectedHierarchySeparator(value.into()))?;
+        if compat.is_empty() || compat.len() > 8 || !compat.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(CodecError::InvalidComponent(compat.into()));
+        }
+        let profile_compatibility_flags = u32::from_str_radix(compat, 16)
+            .map_err(|_| CodecError::InvalidComponent(compat.into()))?;
+
+        let tier_level = i
+            .next()
+            .ok_or_else(|| CodecError::ExpectedHierarchySeparator(value.into()))?;
+        let (tier, level_idc) = match tier_level.as_bytes().first() {
+            Some(b'L') => (HevcTier::Main, &tier_level[1..]),
+            Some(b'H') => (HevcTier::High, &tier_level[1..]),
+            _ => return Err(CodecError::InvalidComponent(tier_level.into())),
+        };
+        let level_idc = parse_decimal(level_idc, u8::MAX)?;
+
+        let mut constraint_indicator_flags = [0; 6];
+        for (index, byte) in i.enumerate() {
+            if index >= constraint_indicator_flags.len() {
+                return Err(CodecError::InvalidComponent(value.into()));
+            }
+            if byte.is_empty() || byte.len() > 2 || !byte.bytes().all(|b| b.is_ascii_hexdigit()) {
+                return Err(CodecError::InvalidComponent(byte.into()));
+            }
+            constraint_indicator_flags[index] = u8::from_str_radix(byte, 16)
+                .map_err(|_| CodecError::InvalidComponent(byte.into()))?;
+        }
+
+        Ok(Hevc {
+            sample_entry,
+            profile_space,
+            profile_idc,
+            profile_compatibility_flags,
+            tier,
+            level_idc,
+            constraint_indicator_flags,
+        })
+    }
+}
+impl fmt::Display for Hevc {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.", self.sample_entry.sample_entry_code().0)?;
+        match self.profile_space {
+            0 => {}
+            1 => f.write_str("A")?,
+            2 => f.write_str("B")?,
+            _ => f.write_str("C")?,
+        }
+        write!(
+            f,
+            "{}.{:X}.{}{}",
+            self.profile_idc, self.profile_compatibility_flags, self.tier, self.level_idc
+        )?;
+        let len = self
+            .constraint_indicator_flags
+            .iter()
+            .rposition(|b| *b != 0)
+            .map(|p| p + 1)
+            .unwrap_or(0);
+        for byte in &self.constraint_indicator_flags[..len] {
+            write!(f, ".{:X}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+/// H.265 profiles, as identified by `general_profile_idc`.
+///
+/// ```rust
+/// # use rfc6381_codec::HevcProfile;
+/// assert_eq!(HevcProfile::from(2), HevcProfile::Main10);
+/// assert_eq!(u8::from(HevcProfile::FormatRangeExtensions), 4);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum HevcProfile {
+    Main,
+    Main10,
+    MainStillPicture,
+    /// The format range extensions (RExt) profiles, such as Main 4:2:2 10 or Main 4:4:4
+    FormatRangeExtensions,
+    HighThroughput,
+    MultiviewMain,
+    ScalableMain,
+    ThreeDimensionalMain,
+    ScreenContentCoding,
+    ScalableFormatRangeExtensions,
+    HighThroughputScreenContentCoding,
+    /// A `general_profile_idc` value not covered by the other variants
+    Unknown(u8),
+}
+impl From<u8> for HevcProfile {
+    fn from(profile_idc: u8) -> Self {
+        match profile_idc {
+            1 => HevcProfile::Main,
+            2 => HevcProfile::Main10,
+            3 => HevcProfile::MainStillPicture,
+            4 => HevcProfile::FormatRangeExtensions,
+            5 => HevcProfile::HighThroughput,
+            6 => HevcProfile::MultiviewMain,
+            7 => HevcProfile::ScalableMain,
+            8 => HevcProfile::ThreeDimensionalMain,
+            9 => HevcProfile::ScreenContentCoding,
+            10 => HevcProfile::ScalableFormatRangeExtensions,
+            11 => HevcProfile::HighThroughputScreenContentCoding,
+            _ => HevcProfile::Unknown(profile_idc),
+        }
+    }
+}
+impl From<HevcProfile> for u8 {
+    fn from(profile: HevcProfile) -> Self {
+        match profile {
+            HevcProfile::Main => 1,
+            HevcProfile::Main10 => 2,
+            HevcProfile::MainStillPicture => 3,
+            HevcProfile::FormatRangeExtensions => 4,
+            HevcProfile::HighThroughput => 5,
+            HevcProfile::MultiviewMain => 6,
+            HevcProfile::ScalableMain => 7,
+            HevcProfile::ThreeDimensionalMain => 8,
+            HevcProfile::ScreenContentCoding => 9,
+            HevcProfile::ScalableFormatRangeExtensions => 10,
+            HevcProfile::HighThroughputScreenContentCoding => 11,
+            HevcProfile::Unknown(profile_idc) => profile_idc,
+        }
+    }
+}
+
+/// Displays the profile name as used in the H.265 spec, like `"Main 10"`
+impl fmt::Display for HevcProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            HevcProfile::Main => "Main",
+            HevcProfile::Main10 => "Main 10",
+            HevcProfile::MainStillPicture => "Main Still Picture",
+            HevcProfile::FormatRangeExtensions => "Format Range Extensions",
+            HevcProfile::HighThroughput => "High Throughput",
+            HevcProfile::MultiviewMain => "Multiview Main",
+            HevcProfile::ScalableMain => "Scalable Main",
+            HevcProfile::ThreeDimensionalMain => "3D Main",
+            HevcProfile::ScreenContentCoding => "Screen Content Coding Extensions",
+            HevcProfile::ScalableFormatRangeExtensions => "Scalable Format Range Extensions",
+            HevcProfile::HighThroughputScreenContentCoding => {
+                "High Throughput Screen Content Coding Extensions"
+            }
+            HevcProfile::Unknown(profile_idc) => return write!(f, "profile_idc {}", profile_idc),
+        };
+        f.write_str(name)
+    }
+}
+
+/// The H.265 tier, signalled by `general_tier_flag`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HevcTier {
+    Main,
+    High,
+}
+/// Displays the tier as in the codec string, `"L"` for the Main tier and `"H"` for High tier
+impl fmt::Display for HevcTier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HevcTier::Main => f.write_str("L"),
+            HevcTier::High => f.write_str("H"),
+        }
+    }
+}
+
+/// H.265 levels.
+///
+/// Levels are ordered by `general_level_idc`.  The tier is signalled separately (see
+/// [`HevcTier`]), so a level compares the same whether it is used with the Main or the High
+/// tier, even though the High tier raises the bitrate limits of the levels from 4 upwards.
+///
+/// ```rust
+/// # use rfc6381_codec::HevcLevel;
+/// assert!(HevcLevel::L5_1 > HevcLevel::L4_1);
+/// assert_eq!(HevcLevel::L5_1.to_string(), "5.1");
+/// assert_eq!(HevcLevel::L5_1.level_idc(), 153);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HevcLevel {
+    L1,
+    L2_0,
+    L2_1,
+    L3_0,
+    L3_1,
+    L4_0,
+    L4_1,
+    L5_0,
+    L5_1,
+    L5_2,
+    L6_0,
+    L6_1,
+    L6_2,
+}
+impl HevcLevel {
+    /// All H.265 levels, from level 1 to level 6.2
+    pub const ALL: [HevcLevel; 13] = [
+        HevcLevel::L1,
+        HevcLevel::L2_0,
+        HevcLevel::L2_1,
+        HevcLevel::L3_0,
+        HevcLevel::L3_1,
+        HevcLevel::L4_0,
+        HevcLevel::L4_1,
+        HevcLevel::L5_0,
+        HevcLevel::L5_1,
+        HevcLevel::L5_2,
+        HevcLevel::L6_0,
+        HevcLevel::L6_1,
+        HevcLevel::L6_2,
+    ];
+
+    /// The `general_level_idc` value for this level, which is thirty times the level number
+    pub fn level_idc(&self) -> u8 {
+        match self {
+            HevcLevel::L1 => 30,
+            HevcLevel::L2_0 => 60,
+            HevcLevel::L2_1 => 63,
+            HevcLevel::L3_0 => 90,
+            HevcLevel::L3_1 => 93,
+            HevcLevel::L4_0 => 120,
+            HevcLevel::L4_1 => 123,
+            HevcLevel::L5_0 => 150,
+            HevcLevel::L5_1 => 153,
+            HevcLevel::L5_2 => 156,
+            HevcLevel::L6_0 => 180,
+            HevcLevel::L6_1 => 183,
+            HevcLevel::L6_2 => 186,
+        }
+    }
+}
+/// Displays the level number as used in the H.265 spec, like `"1"`, `"3.0"` or `"5.1"`
+impl fmt::Display for HevcLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HevcLevel::L1 => f.write_str("1"),
+            _ => {
+                let idc = self.level_idc();
+                write!(f, "{}.{}", idc / 30, idc % 30 / 3)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Codec;
+    use assert_matches::*;
+    use std::str::FromStr;
+
+    fn hevc(codec: &str) -> Hevc {
+        match Codec::from_str(codec) {
+            Ok(Codec::Hevc(hevc)) => hevc,
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parse() {
+        let h = hevc("hev1.1.6.L93.B0");
+        assert_eq!(h.sample_entry(), HevcSampleEntry::Hev1);
+        assert_eq!(h.profile_space(), 0);
+        assert_eq!(h.profile_idc(), 1);
+        assert!(h.profile_compatibility_flag(1));
+        assert!(h.profile_compatibility_flag(2));
+        assert!(!h.profile_compatibility_flag(0));
+        assert_eq!(h.tier(), HevcTier::Main);
+        assert_eq!(h.level_idc(), 93);
+        assert_eq!(h.constraint_indicator_flags(), [0xb0, 0, 0, 0, 0, 0]);
+
+        let h = hevc("hvc1.A4.10.H186.9D.20.0.0.0.1");
+        assert_eq!(h.profile_space(), 1);
+        assert_eq!(h.profile_enum(), HevcProfile::FormatRangeExtensions);
+        assert_eq!(h.tier(), HevcTier::High);
+        assert_eq!(h.level_enum(), Some(HevcLevel::L6_2));
+        assert_eq!(h.constraint_indicator_flags(), [0x9d, 0x20, 0, 0, 0, 1]);
+
+        // constraint bytes may be omitted entirely
+        assert_eq!(hevc("hvc1.1.6.L93").constraint_indicator_flags(), [0; 6]);
+    }
+
+    #[test]
+    fn roundtrip() {
+        for s in &[
+            "hvc1.1.6.L93.B0",
+            "hev1.2.4.L153.B0",
+            "hvc1.A4.10.H186.9D.20.0.0.0.1",
+            "hvc1.C1.6.L93",
+            "hvc1.3.E.L120.90",
+        ] {
+            assert_eq!(*s, hevc(s).to_string());
+        }
+        // trailing zero constraint bytes are omitted
+        assert_eq!("hvc1.1.6.L93.B0", hevc("hvc1.1.6.L93.B0.0.00").to_string());
+    }
+
+    #[test]
+    fn bad_hevc() {
+        for s in &[
+            "hvc1",
+            "hvc1.1",
+            "hvc1.1.6",
+            "hvc1.D1.6.L93",
+            "hvc1.1.6.X93",
+            "hvc1.1.6.L",
+            "hvc1.1.123456789.L93",
+            "hvc1.1.G.L93",
+            "hvc1.1.6.L93.B00",
+            "hvc1.1.6.L93.B0.0.0.0.0.0.0",
+            "hvc1.1.6.L93.",
+            "hvc1.1.6.L+93",
+            "hvc1.32.6.L93",
+            "hvc1.C40.6.L93",
+        ] {
+            assert_matches!(Codec::from_str(s), Err(_), "{}", s);
+        }
+    }
+
+    #[test]
+    fn new_masks_fields() {
+        let hevc = Hevc::new(
+            HevcSampleEntry::Hev1,
+            7,
+            40,
+            0x4,
+            HevcTier::High,
+            93,
+            [0; 6],
+        );
+        assert_eq!(hevc.profile_space(), 3);
+        assert_eq!(hevc.profile_idc(), 8);
+        let s = Codec::Hevc(hevc.clone()).to_string();
+        assert_eq!(s, "hev1.C8.4.H93");
+        assert_eq!(Codec::from_str(&s).unwrap(), Codec::Hevc(hevc));
+    }
+
+    #[test]
+    fn from_hvcc() {
+        let hvcc = [
+            0x01, 0x61, 0x60, 0x00, 0x00, 0x00, 0x90, 0x00, 0x00, 0x00, 0x00, 0x00, 0x5d,
+        ];
+        let hevc = Hevc::from_hvcc(HevcSampleEntry::Hev1, &hvcc).unwrap();
+        assert_eq!(Codec::Hevc(hevc).to_string(), "hev1.A1.6.H93.90");
+        assert_matches!(
+            Hevc::from_hvcc(HevcSampleEntry::Hvc1, &hvcc[..12]),
+            Err(CodecError::ConfigurationRecordTooShort {
+                expected: 13,
+                got: 12
+            })
+        );
+        let mut hvcc = hvcc;
+        hvcc[0] = 0;
+        assert_matches!(
+            Hevc::from_hvcc(HevcSampleEntry::Hvc1, &hvcc),
+            Err(CodecError::UnsupportedConfigurationVersion(0))
+        );
+    }
+
+    #[test]
+    fn semantics() {
+        let h = hevc("hvc1.2.4.L153.B0");
+        assert!(h.progressive_source_flag());
+        assert!(!h.interlaced_source_flag());
+        assert!(h.non_packed_constraint_flag());
+        assert!(h.frame_only_constraint_flag());
+        assert!(!h.one_picture_only_constraint_flag());
+        // Main 10 still picture
+        assert!(hevc("hvc1.2.4.L153.B0.10").one_picture_only_constraint_flag());
+        // Main 4:2:2 10
+        let h = hevc("hvc1.4.10.L120.9D.08");
+        assert_eq!(h.profile_enum(), HevcProfile::FormatRangeExtensions);
+        assert!(h.max_12bit_constraint_flag());
+        assert!(h.max_10bit_constraint_flag());
+        assert!(!h.max_8bit_constraint_flag());
+        assert!(h.max_422chroma_constraint_flag());
+        assert!(!h.max_420chroma_constraint_flag());
+        assert!(h.lower_bit_rate_constraint_flag());
+        // profile_idc of zero falls back to the compatibility flags
+        assert_eq!(hevc("hvc1.0.4.L93").profile_enum(), HevcProfile::Main10);
+        assert_eq!(hevc("hvc1.0.0.L93").profile_enum(), HevcProfile::Unknown(0));
+    }
+
+    #[test]
+    fn levels() {
+        let mut sorted = HevcLevel::ALL;
+        sorted.sort();
+        assert_eq!(sorted, HevcLevel::ALL);
+        assert_eq!(HevcLevel::L1.to_string(), "1");
+        assert_eq!(HevcLevel::L3_0.to_string(), "3.0");
+        assert_eq!(HevcLevel::L6_2.to_string(), "6.2");
+        assert_eq!(hevc("hvc1.1.6.L91").level_enum(), None);
+        assert_eq!(HevcProfile::Main10.to_string(), "Main 10");
+        assert_eq!(HevcProfile::Unknown(99).to_string(), "profile_idc 99");
+        for v in 0..=255u8 {
+            assert_eq!(v, u8::from(HevcProfile::from(v)));
+        }
+    }
+}
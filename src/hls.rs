@@ -0,0 +1,356 @@
+//! Support for the `CODECS` and `SUPPLEMENTAL-CODECS` attributes of HLS `EXT-X-STREAM-INF`
+//! tags, as specified by RFC 8216 and its successor drafts.
+
+use crate::{parse_decimal, Avc1, Codec, CodecError, MediaKind};
+use four_cc::FourCC;
+use std::fmt;
+use std::str::FromStr;
+
+/// The value of an HLS `CODECS` attribute, like `"avc1.4D401E,mp4a.40.2"`.
+///
+/// The attribute lists every format found in any rendition of the variant stream, so a list may
+/// have several entries for the same [`MediaKind`] (for example both AAC and E-AC-3 where the
+/// variant's audio group carries both).  Where an application needs just one entry for each
+/// media type, [`check_one_per_media_type()`](Self::check_one_per_media_type) tests for that.
+///
+/// When parsing, the legacy decimal `avc1` form which older Apple tooling writes (for example
+/// `avc1.66.30` in place of `avc1.42001E`) is accepted.  The `Display` implementation always
+/// writes the hexadecimal form, quoted and without spaces after the commas.
+///
+/// ```rust
+/// # use rfc6381_codec::{Codec, HlsCodecs};
+/// # use std::str::FromStr;
+/// let codecs = HlsCodecs::from_str(r#"CODECS="avc1.77.30,mp4a.40.2""#).unwrap();
+/// assert_eq!(codecs.to_string(), r#""avc1.4D001E,mp4a.40.2""#);
+///
+/// let codecs = HlsCodecs::new(vec![Codec::avc1(0x64, 0, 0x28), Codec::aac_lc()]).unwrap();
+/// assert_eq!(codecs.to_attribute(), r#"CODECS="avc1.640028,mp4a.40.2""#);
+/// assert!(codecs.check_one_per_media_type().is_ok());
+///
+/// let codecs = HlsCodecs::from_str(r#""avc1.640028,mp4a.40.2,mp4a.40.5""#).unwrap();
+/// assert!(codecs.check_one_per_media_type().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct HlsCodecs {
+    codecs: Vec<Codec>,
+}
+impl HlsCodecs {
+    /// Creates a value from the given codecs, of which there must be at least one.
+    pub fn new(codecs: Vec<Codec>) -> Result<HlsCodecs, HlsError> {
+        if codecs.is_empty() {
+            return Err(HlsError::Empty);
+        }
+        Ok(HlsCodecs { codecs })
+    }
+    pub fn codecs(&self) -> &[Codec] {
+        &self.codecs
+    }
+    pub fn into_codecs(self) -> Vec<Codec> {
+        self.codecs
+    }
+    /// Checks that no two entries are for the same [`MediaKind`], ignoring entries of unknown
+    /// kind and `lvc1` enhancement entries (which accompany their base video codec).
+    pub fn check_one_per_media_type(&self) -> Result<(), HlsError> {
+        let mut seen = vec![];
+        for (index, codec) in self.codecs.iter().enumerate() {
+            if let Codec::Lvc1(_) = codec {
+                continue;
+            }
+            let kind = codec.media_kind();
+            if kind == MediaKind::Unknown {
+                continue;
+            }
+            if seen.contains(&kind) {
+                return Err(HlsError::DuplicateMediaKind { index, kind });
+            }
+            seen.push(kind);
+        }
+        Ok(())
+    }
+    /// Produces the whole attribute, like `CODECS="avc1.4D401E,mp4a.40.2"`, suitable for
+    /// inclusion in an `EXT-X-STREAM-INF` attribute list.
+    pub fn to_attribute(&self) -> String {
+        format!("CODECS={}", self)
+    }
+}
+
+/// Problems found when creating or parsing an [`HlsCodecs`] value.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum HlsError {
+    /// The attribute value was not a quoted string, or the attribute was not named `CODECS`
+    InvalidAttribute(String),
+    /// There were no codecs given
+    Empty,
+    /// The entry at position `index` is for the same kind of media as an earlier entry, as found
+    /// by [`HlsCodecs::check_one_per_media_type()`]
+    DuplicateMediaKind { index: usize, kind: MediaKind },
+    /// An entry in the list could not be parsed
+    Codec(CodecError),
+}
+impl From<CodecError> for HlsError {
+    fn from(e: CodecError) -> Self {
+        HlsError::Codec(e)
+    }
+}
+
+impl FromStr for HlsCodecs {
+    type Err = HlsError;
+
+    /// Parses either the quoted attribute value, or the whole `CODECS="..."` attribute.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let codecs = attribute_entries("CODECS", value)?
+            .map(parse_entry)
+            .collect::<Result<_, _>>()?;
+        HlsCodecs::new(codecs)
+    }
+}
+
+/// Parses the quoted value of an attribute, or the whole `name="..."` attribute, giving the
+/// comma-separated entries
+fn attribute_entries<'a>(
+    name: &str,
+    value: &'a str,
+) -> Result<impl Iterator<Item = &'a str>, HlsError> {
+    let invalid = || HlsError::InvalidAttribute(value.to_string());
+    let quoted = match value.split_once('=') {
+        Some((n, quoted)) if n == name => quoted,
+        Some(_) => return Err(invalid()),
+        None => value,
+    };
+    let list = quoted
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .ok_or_else(invalid)?;
+    Ok(list.split(',').map(str::trim))
+}
+
+/// Parses a single entry, accepting the legacy `avc1.<profile>.<level>` form with decimal values
+fn parse_entry(codec: &str) -> Result<Codec, CodecError> {
+    if let Some(rest) = codec.strip_prefix("avc1.") {
+        if let Some((profile, level)) = rest.split_once('.') {
+            return Ok(Codec::Avc1(Avc1 {
+                profile: parse_decimal(profile, u8::MAX)?,
+                constraints: 0,
+                level: parse_decimal(level, u8::MAX)?,
+            }));
+        }
+    }
+    Codec::from_str(codec)
+}
+
+impl fmt::Display for HlsCodecs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("\"")?;
+        for (i, codec) in self.codecs.iter().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            write!(f, "{}", codec)?;
+        }
+        f.write_str("\"")
+    }
+}
+
+/// An entry of an HLS `SUPPLEMENTAL-CODECS` attribute, like `dvh1.08.07/db4h`.
+///
+/// Each entry describes a format which a client may use in place of the `CODECS` entry for the
+/// same kind of media, such as the Dolby Vision enhancement of an HEVC base layer.  The entry
+/// may be followed by one or more compatibility brands, separated by `/`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SupplementalCodec {
+    codec: Codec,
+    brands: Vec<FourCC>,
+}
+impl SupplementalCodec {
+    pub fn new(codec: Codec, brands: Vec<FourCC>) -> SupplementalCodec {
+        SupplementalCodec { codec, brands }
+    }
+    pub fn codec(&self) -> &Codec {
+        &self.codec
+    }
+    /// The compatibility brands following the codec value
+    pub fn brands(&self) -> &[FourCC] {
+        &self.brands
+    }
+    /// Finds the entry of the given `CODECS` attribute which this entry supplements, being the
+    /// entry for the same kind of media.
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::{HlsCodecs, SupplementalCodecs};
+    /// # use std::str::FromStr;
+    /// let codecs = HlsCodecs::from_str(r#""hvc1.2.4.L153.B0,ec-3""#).unwrap();
+    /// let supplemental = SupplementalCodecs::from_str(r#""dvh1.08.07/db4h""#).unwrap();
+    /// let entry = &supplemental.entries()[0];
+    /// assert_eq!(entry.brands()[0].to_string(), "db4h");
+    /// assert_eq!(entry.base(&codecs).unwrap().to_string(), "hvc1.2.4.L153.B0");
+    /// ```
+    pub fn base<'a>(&self, codecs: &'a HlsCodecs) -> Option<&'a Codec> {
+        let kind = self.codec.media_kind();
+        if kind == MediaKind::Unknown {
+            return None;
+        }
+        codecs
+            .codecs()
+            .iter()
+            .find(|c| c.media_kind() == kind && !matches!(c, Codec::Lvc1(_)))
+    }
+}
+impl FromStr for SupplementalCodec {
+    type Err = CodecError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut parts = value.split('/');
+        let codec = Codec::from_str(parts.next().unwrap())?;
+        let brands = parts
+            .map(|brand| {
+                if brand.len() == 4 {
+                    Ok(FourCC::from(brand.as_bytes()))
+                } else {
+                    Err(CodecError::UnexpectedLength {
+                        expected: 4,
+                        got: brand.into(),
+                    })
+                }
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(SupplementalCodec { codec, brands })
+    }
+}
+impl fmt::Display for SupplementalCodec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.codec)?;
+        for brand in &self.brands {
+            write!(f, "/{}", brand)?;
+        }
+        Ok(())
+    }
+}
+
+/// The value of an HLS `SUPPLEMENTAL-CODECS` attribute, like `"dvh1.08.07/db4h"`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SupplementalCodecs {
+    entries: Vec<SupplementalCodec>,
+}
+impl SupplementalCodecs {
+    pub fn new(entries: Vec<SupplementalCodec>) -> SupplementalCodecs {
+        SupplementalCodecs { entries }
+    }
+    pub fn entries(&self) -> &[SupplementalCodec] {
+        &self.entries
+    }
+    /// Produces the whole attribute, like `SUPPLEMENTAL-CODECS="dvh1.08.07/db4h"`.
+    pub fn to_attribute(&self) -> String {
+        format!("SUPPLEMENTAL-CODECS={}", self)
+    }
+}
+impl FromStr for SupplementalCodecs {
+    type Err = HlsError;
+
+    /// Parses either the quoted attribute value, or the whole `SUPPLEMENTAL-CODECS="..."`
+    /// attribute.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let entries = attribute_entries("SUPPLEMENTAL-CODECS", value)?
+            .map(SupplementalCodec::from_str)
+            .collect::<Result<_, _>>()?;
+        Ok(SupplementalCodecs { entries })
+    }
+}
+impl fmt::Display for SupplementalCodecs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("\"")?;
+        for (i, entry) in self.entries.iter().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            write!(f, "{}", entry)?;
+        }
+        f.write_str("\"")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::*;
+
+    #[test]
+    fn parse() {
+        #[cfg(feature = "text")]
+        let codecs = HlsCodecs::from_str(r#""avc1.640028, mp4a.40.2, wvtt""#).unwrap();
+        #[cfg(feature = "text")]
+        assert_matches!(
+            codecs.codecs(),
+            [Codec::Avc1(_), Codec::Mp4a(_), Codec::Wvtt]
+        );
+        let codecs = HlsCodecs::from_str(r#"CODECS="avc1.66.30""#).unwrap();
+        assert_eq!(codecs.to_string(), r#""avc1.42001E""#);
+        let codecs = HlsCodecs::from_str(r#""hvc1.2.4.L153.B0,lvc1.1.6,ec-3""#).unwrap();
+        assert_eq!(codecs.codecs().len(), 3);
+        assert_eq!(codecs.check_one_per_media_type(), Ok(()));
+        for s in &["avc1.640028", r#"codecs="avc1.640028""#, r#""avc1.640028"#] {
+            assert_matches!(
+                HlsCodecs::from_str(s),
+                Err(HlsError::InvalidAttribute(_)),
+                "{}",
+                s
+            );
+        }
+        let codecs = HlsCodecs::from_str(r#""avc1.640028,mp4a.40.2,mp4a.40.5""#).unwrap();
+        assert_eq!(codecs.codecs().len(), 3);
+        assert_matches!(
+            codecs.check_one_per_media_type(),
+            Err(HlsError::DuplicateMediaKind {
+                index: 2,
+                kind: MediaKind::Audio
+            })
+        );
+        assert_matches!(
+            HlsCodecs::from_str(r#""avc1.66.300""#),
+            Err(HlsError::Codec(CodecError::InvalidComponent(_)))
+        );
+        assert_matches!(
+            HlsCodecs::from_str(r#""avc1.+66.30""#),
+            Err(HlsError::Codec(CodecError::InvalidComponent(_)))
+        );
+        assert_matches!(HlsCodecs::from_str(r#""""#), Err(HlsError::Codec(_)));
+    }
+
+    #[test]
+    fn build() {
+        assert_matches!(HlsCodecs::new(vec![]), Err(HlsError::Empty));
+        let codecs = HlsCodecs::new(vec![Codec::aac_lc(), Codec::Other("xyz1".into())]);
+        assert_eq!(codecs.unwrap().to_attribute(), r#"CODECS="mp4a.40.2,xyz1""#);
+    }
+
+    #[test]
+    fn supplemental() {
+        let codecs = HlsCodecs::from_str(r#""hvc1.2.4.L150.B0,mp4a.40.2""#).unwrap();
+        let supplemental =
+            SupplementalCodecs::from_str(r#"SUPPLEMENTAL-CODECS="dvh1.08.07/db4h/db1p""#).unwrap();
+        let entry = &supplemental.entries()[0];
+        assert_eq!(
+            entry.brands(),
+            [FourCC(*b"db4h"), FourCC(*b"db1p")].as_slice()
+        );
+        #[cfg(feature = "hevc")]
+        assert_matches!(entry.base(&codecs), Some(Codec::Hevc(_)));
+        assert_eq!(
+            supplemental.to_attribute(),
+            r#"SUPPLEMENTAL-CODECS="dvh1.08.07/db4h/db1p""#
+        );
+        let supplemental = SupplementalCodecs::from_str(r#""dvh1.08.07,xyz1.1""#).unwrap();
+        assert_eq!(supplemental.entries()[0].brands().len(), 0);
+        assert!(supplemental.entries()[1].base(&codecs).is_none());
+        assert_matches!(
+            SupplementalCodecs::from_str(r#""dvh1.08.07/db4""#),
+            Err(HlsError::Codec(CodecError::UnexpectedLength {
+                expected: 4,
+                ..
+            }))
+        );
+        assert_matches!(
+            SupplementalCodecs::from_str(r#"CODECS="dvh1.08.07""#),
+            Err(HlsError::InvalidAttribute(_))
+        );
+    }
+}
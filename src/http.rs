@@ -0,0 +1,60 @@
+//! Integration with the `http` crate, enabled by the `http` cargo feature.
+
+use crate::{Codec, ContentType, ContentTypeError};
+use http::HeaderValue;
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+impl TryFrom<&HeaderValue> for ContentType {
+    type Error = ContentTypeError;
+
+    /// Parses the value of a `Content-Type` header.
+    ///
+    /// Header values which are not visible ASCII text give
+    /// [`ContentTypeError::InvalidMediaType`].
+    fn try_from(header: &HeaderValue) -> Result<Self, Self::Error> {
+        let value = header.to_str().map_err(|_| {
+            ContentTypeError::InvalidMediaType(String::from_utf8_lossy(header.as_bytes()).into())
+        })?;
+        ContentType::from_str(value)
+    }
+}
+
+impl Codec {
+    /// Parses the codecs list from a `Content-Type` header value, as for
+    /// [`Codec::from_content_type()`].
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::Codec;
+    /// use http::HeaderValue;
+    ///
+    /// let header = HeaderValue::from_static(r#"video/webm; codecs="vp09.00.10.08, opus""#);
+    /// let codecs = Codec::from_content_type_header(&header).unwrap();
+    /// assert_eq!(codecs.len(), 2);
+    /// ```
+    pub fn from_content_type_header(header: &HeaderValue) -> Result<Vec<Codec>, ContentTypeError> {
+        ContentType::try_from(header).map(ContentType::into_codecs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::*;
+
+    #[test]
+    fn header() {
+        let header = HeaderValue::from_static("audio/mp4; codecs*=us-ascii''mp4a.40.2");
+        assert_matches!(
+            Codec::from_content_type_header(&header).unwrap()[..],
+            [Codec::Mp4a(_)]
+        );
+        let header = HeaderValue::from_bytes(b"video/mp4; codecs=\"avc1.4D401E\xc3\xa9\"").unwrap();
+        assert_matches!(
+            ContentType::try_from(&header),
+            Err(ContentTypeError::InvalidMediaType(_))
+        );
+        let header = HeaderValue::from_static("video/mp4");
+        assert!(Codec::from_content_type_header(&header).unwrap().is_empty());
+    }
+}
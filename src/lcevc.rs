@@ -0,0 +1,205 @@
+//! Support for LC-EVC (MPEG-5 Part 2) enhancement codec strings.
+//!
+//! LC-EVC is not a standalone codec; an `lvc1` entry in a codecs list enhances the base video
+//! codec that precedes it, for example `avc1.640028,lvc1.1.6`.  Use
+//! [`Codec::pair_enhancements()`] to group each enhancement with its base codec.
+
+use crate::{parse_decimal, Codec, CodecError};
+use four_cc::FourCC;
+use mp4ra_rust::SampleEntryCode;
+use std::fmt;
+use std::str::FromStr;
+
+pub(crate) const LVC1: SampleEntryCode = SampleEntryCode(FourCC(*b"lvc1"));
+
+/// Base-layer sample entry codes that LC-EVC may be used to enhance.
+const BASE_CODECS: [&str; 8] = [
+    "avc1", "avc3", "hvc1", "hev1", "vvc1", "vvi1", "av01", "vp09",
+];
+
+/// The `lvc1` codec parameter, giving the LC-EVC profile and level.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Lvc1 {
+    pub(crate) profile: u8,
+    pub(crate) level: u8,
+}
+impl Lvc1 {
+    pub const fn new(profile: u8, level: u8) -> Lvc1 {
+        Lvc1 { profile, level }
+    }
+    pub fn profile(&self) -> u8 {
+        self.profile
+    }
+    pub fn level(&self) -> u8 {
+        self.level
+    }
+}
+impl FromStr for Lvc1 {
+    type Err = CodecError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut i = value.splitn(2, '.');
+        let profile = i.next().unwrap();
+        let level = i
+            .next()
+            .ok_or_else(|| CodecError::ExpectedHierarchySeparator(value.into()))?;
+        Ok(Lvc1 {
+            profile: parse_element(profile)?,
+            level: parse_element(level)?,
+        })
+    }
+}
+/// Parses a decimal element, rejecting leading zeros so that the value displays as it was given
+fn parse_element(value: &str) -> Result<u8, CodecError> {
+    if value.len() > 1 && value.starts_with('0') {
+        return Err(CodecError::InvalidComponent(value.into()));
+    }
+    parse_decimal(value, u8::MAX)
+}
+
+impl fmt::Display for Lvc1 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.profile, self.level)
+    }
+}
+
+/// An entry from a codecs list, together with the LC-EVC enhancement applied to it (if any).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LayeredCodec<'a> {
+    base: &'a Codec,
+    enhancement: Option<&'a Lvc1>,
+}
+impl<'a> LayeredCodec<'a> {
+    pub fn base(&self) -> &'a Codec {
+        self.base
+    }
+    pub fn enhancement(&self) -> Option<&'a Lvc1> {
+        self.enhancement
+    }
+}
+
+/// Problems found when pairing enhancement codecs with their base codecs.
+///
+/// The `index` given in each variant is the position of the offending `lvc1` entry within the
+/// codecs list.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum EnhancementError {
+    /// The enhancement was the first entry in the list, or directly followed another
+    /// enhancement, so there is no base codec for it to enhance
+    MissingBase { index: usize },
+    /// The entry preceding the enhancement is not a video codec that LC-EVC can enhance
+    UnsupportedBase { index: usize },
+}
+
+impl Codec {
+    /// Groups each `lvc1` enhancement in the given codecs list with the base codec entry that
+    /// immediately precedes it.
+    ///
+    /// Entries without an enhancement are returned with `enhancement()` giving `None`, so the
+    /// result has one item per base codec, in the original order.
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::Codec;
+    /// let codecs: Vec<Codec> = Codec::parse_codecs("avc1.640028,lvc1.1.6,mp4a.40.2")
+    ///     .collect::<Result<_, _>>()
+    ///     .unwrap();
+    /// let layers = Codec::pair_enhancements(&codecs).unwrap();
+    /// assert_eq!(layers.len(), 2);
+    /// assert_eq!(layers[0].base().to_string(), "avc1.640028");
+    /// assert_eq!(layers[0].enhancement().unwrap().level(), 6);
+    /// assert!(layers[1].enhancement().is_none());
+    /// ```
+    pub fn pair_enhancements(codecs: &[Codec]) -> Result<Vec<LayeredCodec<'_>>, EnhancementError> {
+        let mut result: Vec<LayeredCodec<'_>> = Vec::with_capacity(codecs.len());
+        for (index, codec) in codecs.iter().enumerate() {
+            if let Codec::Lvc1(lvc1) = codec {
+                let last = match result.last_mut() {
+                    Some(last) if last.enhancement.is_none() => last,
+                    _ => return Err(EnhancementError::MissingBase { index }),
+                };
+                if !is_enhanceable(last.base) {
+                    return Err(EnhancementError::UnsupportedBase { index });
+                }
+                last.enhancement = Some(lvc1);
+            } else {
+                result.push(LayeredCodec {
+                    base: codec,
+                    enhancement: None,
+                });
+            }
+        }
+        Ok(result)
+    }
+}
+
+fn is_enhanceable(codec: &Codec) -> bool {
+    match codec {
+        Codec::Avc1(_) => true,
+        #[cfg(feature = "hevc")]
+        Codec::Hevc(_) => true,
+        #[cfg(feature = "av1")]
+        Codec::Av01(_) => true,
+        #[cfg(feature = "vp9")]
+        Codec::Vp09(_) => true,
+        // covers the base codecs whose support is disabled too
+        Codec::Other(other) => BASE_CODECS.contains(&other.first_element()),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::*;
+
+    fn parse(codecs: &str) -> Vec<Codec> {
+        Codec::parse_codecs(codecs)
+            .collect::<Result<_, _>>()
+            .unwrap()
+    }
+
+    #[test]
+    fn lvc1() {
+        assert_matches!(
+            Codec::from_str("lvc1.1.6"),
+            Ok(Codec::Lvc1(Lvc1 {
+                profile: 1,
+                level: 6
+            }))
+        );
+        assert_eq!("lvc1.1.6", Codec::from_str("lvc1.1.6").unwrap().to_string());
+        assert_matches!(Codec::from_str("lvc1.1"), Err(_));
+        assert_matches!(Codec::from_str("lvc1.x.6"), Err(_));
+        assert_matches!(Codec::from_str("lvc1.+1.6"), Err(_));
+        assert_matches!(Codec::from_str("lvc1.01.06"), Err(_));
+        assert_matches!(Codec::from_str("lvc1.1.06"), Err(_));
+    }
+
+    #[test]
+    fn pairing() {
+        let codecs = parse("hvc1.1.6.L93.B0,lvc1.1.6,mp4a.40.2");
+        let layers = Codec::pair_enhancements(&codecs).unwrap();
+        assert_eq!(layers.len(), 2);
+        assert!(layers[0].enhancement().is_some());
+        assert!(layers[1].enhancement().is_none());
+    }
+
+    #[test]
+    fn bad_pairing() {
+        let codecs = parse("lvc1.1.6,avc1.640028");
+        assert_eq!(
+            Codec::pair_enhancements(&codecs).unwrap_err(),
+            EnhancementError::MissingBase { index: 0 }
+        );
+        let codecs = parse("avc1.640028,lvc1.1.6,lvc1.1.6");
+        assert_eq!(
+            Codec::pair_enhancements(&codecs).unwrap_err(),
+            EnhancementError::MissingBase { index: 2 }
+        );
+        let codecs = parse("mp4a.40.2,lvc1.1.6");
+        assert_eq!(
+            Codec::pair_enhancements(&codecs).unwrap_err(),
+            EnhancementError::UnsupportedBase { index: 1 }
+        );
+    }
+}
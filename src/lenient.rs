@@ -0,0 +1,329 @@
+//! Lenient parsing of codec strings, repairing common mistakes found in real-world manifests.
+
+use crate::{Codec, CodecError, CodecListError};
+
+/// A repair made to the input by [`Codec::parse_lenient()`] or
+/// [`Codec::parse_codecs_lenient()`] in order to parse it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum LenientFix {
+    /// Whitespace surrounding a value was removed
+    TrimmedWhitespace,
+    /// Double or single quotes surrounding a value were removed
+    RemovedQuotes,
+    /// Trailing `.` or `,` separators were removed
+    RemovedTrailingSeparator,
+    /// The letter case of the sample entry code or of an element was corrected, as in `AVC1` or
+    /// the tier of `av01.0.04m.08`
+    NormalisedCase,
+    /// Leading zeros were added to a fixed width decimal element, as in `vp09.0.10.8`
+    AddedLeadingZeros,
+}
+
+/// A value produced by lenient parsing, along with the repairs which were needed to parse it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Lenient<T> {
+    value: T,
+    fixes: Vec<LenientFix>,
+}
+impl<T> Lenient<T> {
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+    pub fn into_value(self) -> T {
+        self.value
+    }
+    /// The repairs made to the input, each listed once, in the order they were first needed
+    pub fn fixes(&self) -> &[LenientFix] {
+        &self.fixes[..]
+    }
+    /// `true` if the input was parsed without needing any repair
+    pub fn is_exact(&self) -> bool {
+        self.fixes.is_empty()
+    }
+}
+
+impl Codec {
+    /// Parses a single codec string, tolerating some of the mistakes commonly seen in
+    /// real-world manifests, and reporting the repairs that were made.
+    ///
+    /// The following are accepted,
+    ///
+    ///  - whitespace or quotes surrounding the value
+    ///  - trailing `.` or `,` separators
+    ///  - the wrong letter case in the sample entry code (like `AVC1` or `OPUS`), in the `av01`
+    ///    tier or in the `hvc1` / `hev1` tier
+    ///  - missing leading zeros in the fixed width decimal elements of `av01` and `vp09` values
+    ///
+    /// Repairs are only attempted if the value does not parse as it stands, and a repaired
+    /// sample entry code is only accepted if it is one this crate understands.  The usual
+    /// [`FromStr`](std::str::FromStr) implementation remains strict.
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::{Codec, LenientFix};
+    /// let parsed = Codec::parse_lenient(" \"AVC1.4d401e\" ").unwrap();
+    /// assert_eq!(parsed.value().to_string(), "avc1.4D401E");
+    /// assert_eq!(
+    ///     parsed.fixes(),
+    ///     &[LenientFix::TrimmedWhitespace, LenientFix::RemovedQuotes, LenientFix::NormalisedCase]
+    /// );
+    ///
+    /// # #[cfg(feature = "vp9")]
+    /// let parsed = Codec::parse_lenient("vp09.0.10.8").unwrap();
+    /// # #[cfg(feature = "vp9")]
+    /// assert_eq!(parsed.value().to_string(), "vp09.00.10.08");
+    /// # #[cfg(feature = "vp9")]
+    /// assert_eq!(parsed.fixes(), &[LenientFix::AddedLeadingZeros]);
+    /// ```
+    pub fn parse_lenient(codec: &str) -> Result<Lenient<Codec>, CodecError> {
+        let mut fixes = vec![];
+        let value = parse_entry(trim(codec, &mut fixes), &mut fixes)?;
+        Ok(Lenient { value, fixes })
+    }
+
+    /// Parses a comma-separated list of codec strings in the manner of
+    /// [`parse_lenient()`](Self::parse_lenient).  Quotes surrounding the whole list, and a
+    /// trailing comma, are also accepted.
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::{Codec, LenientFix};
+    /// let parsed = Codec::parse_codecs_lenient("\"avc1.4D401E, mp4a.40.2,\"").unwrap();
+    /// assert_eq!(parsed.value().len(), 2);
+    /// assert_eq!(
+    ///     parsed.fixes(),
+    ///     &[LenientFix::RemovedQuotes, LenientFix::RemovedTrailingSeparator]
+    /// );
+    ///
+    /// let err = Codec::parse_codecs_lenient("avc1.4D401E, mp4a.4g").unwrap_err();
+    /// assert_eq!((err.index(), err.offset()), (1, 13));
+    /// ```
+    pub fn parse_codecs_lenient(codecs: &str) -> Result<Lenient<Vec<Codec>>, CodecListError> {
+        let mut fixes = vec![];
+        let mut list = trim(codecs, &mut fixes);
+        if list.matches(['"', '\'']).count() == 2 {
+            list = unquote(list, &mut fixes);
+        }
+        let trimmed = list.trim_end_matches(|c: char| c == ',' || c.is_ascii_whitespace());
+        if trimmed.len() != list.len() {
+            add(&mut fixes, LenientFix::RemovedTrailingSeparator);
+            list = trimmed;
+        }
+        let value = list
+            .split(',')
+            .enumerate()
+            .map(|(index, entry)| {
+                parse_entry(entry, &mut fixes).map_err(|error| CodecListError {
+                    index,
+                    offset: offset_within(codecs, entry.trim_start()),
+                    error,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Lenient { value, fixes })
+    }
+}
+
+fn add(fixes: &mut Vec<LenientFix>, fix: LenientFix) {
+    if !fixes.contains(&fix) {
+        fixes.push(fix);
+    }
+}
+
+fn offset_within(outer: &str, inner: &str) -> usize {
+    inner.as_ptr() as usize - outer.as_ptr() as usize
+}
+
+fn trim<'a>(value: &'a str, fixes: &mut Vec<LenientFix>) -> &'a str {
+    let trimmed = value.trim();
+    if trimmed.len() != value.len() {
+        add(fixes, LenientFix::TrimmedWhitespace);
+    }
+    trimmed
+}
+
+fn unquote<'a>(value: &'a str, fixes: &mut Vec<LenientFix>) -> &'a str {
+    for quote in &['"', '\''] {
+        if let Some(inner) = value
+            .strip_prefix(*quote)
+            .and_then(|v| v.strip_suffix(*quote))
+        {
+            add(fixes, LenientFix::RemovedQuotes);
+            return trim(inner, fixes);
+        }
+    }
+    value
+}
+
+/// Parses one entry, where whitespace surrounding the entry is not itself counted as a repair
+/// (as it is permitted after the commas of a codecs list)
+fn parse_entry(entry: &str, fixes: &mut Vec<LenientFix>) -> Result<Codec, CodecError> {
+    let entry = unquote(entry.trim(), fixes);
+    let without_separators = entry.trim_end_matches(['.', ',']);
+    let entry = if without_separators.len() != entry.len() && !without_separators.is_empty() {
+        add(fixes, LenientFix::RemovedTrailingSeparator);
+        without_separators
+    } else {
+        entry
+    };
+    let strict = entry.parse();
+    if let Ok(Codec::Other(_)) | Err(_) = strict {
+        let mut repairs = vec![];
+        if let Some(repaired) = repair(entry, &mut repairs) {
+            match repaired.parse() {
+                Ok(Codec::Other(_)) | Err(_) => {}
+                Ok(codec) => {
+                    for fix in repairs {
+                        add(fixes, fix);
+                    }
+                    return Ok(codec);
+                }
+            }
+        }
+    }
+    strict
+}
+
+/// Applies the case and zero-padding repairs, returning `None` if nothing was changed
+fn repair(entry: &str, fixes: &mut Vec<LenientFix>) -> Option<String> {
+    let mut elements: Vec<String> = entry.split('.').map(str::to_owned).collect();
+    let lead = match elements[0].to_ascii_lowercase().as_str() {
+        "opus" => "Opus".to_owned(),
+        "flac" => "fLaC".to_owned(),
+        other => other.to_owned(),
+    };
+    if lead != elements[0] {
+        fixes.push(LenientFix::NormalisedCase);
+        elements[0] = lead;
+    }
+    match elements[0].as_str() {
+        "av01" => {
+            if let Some(level_tier) = elements
+                .get_mut(2)
+                .filter(|e| e.ends_with(|c: char| c.is_ascii_alphabetic()))
+            {
+                let (level, tier) = level_tier.split_at(level_tier.len().saturating_sub(1));
+                let upper = tier.to_ascii_uppercase();
+                if upper != tier {
+                    fixes.push(LenientFix::NormalisedCase);
+                }
+                *level_tier = format!("{}{}", pad(level, 2, fixes), upper);
+            }
+            for (index, width) in &[(3, 2), (5, 3), (6, 2), (7, 2), (8, 2)] {
+                if let Some(element) = elements.get_mut(*index) {
+                    *element = pad(element, *width, fixes);
+                }
+            }
+        }
+        "vp09" => {
+            for element in &mut elements[1..] {
+                *element = pad(element, 2, fixes);
+            }
+        }
+        "hvc1" | "hev1" => {
+            if let Some(tier_level) = elements.get_mut(3) {
+                let upper = tier_level.to_ascii_uppercase();
+                if upper != *tier_level {
+                    fixes.push(LenientFix::NormalisedCase);
+                    *tier_level = upper;
+                }
+            }
+        }
+        _ => {}
+    }
+    if fixes.is_empty() {
+        None
+    } else {
+        Some(elements.join("."))
+    }
+}
+
+fn pad(element: &str, width: usize, fixes: &mut Vec<LenientFix>) -> String {
+    if !element.is_empty() && element.len() < width && element.bytes().all(|b| b.is_ascii_digit()) {
+        fixes.push(LenientFix::AddedLeadingZeros);
+        format!("{:0>width$}", element, width = width)
+    } else {
+        element.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::*;
+
+    fn lenient(codec: &str) -> (String, Vec<LenientFix>) {
+        let parsed = Codec::parse_lenient(codec).unwrap();
+        let fixes = parsed.fixes().to_vec();
+        (parsed.into_value().to_string(), fixes)
+    }
+
+    #[test]
+    fn exact() {
+        let parsed = Codec::parse_lenient("avc1.4d401e").unwrap();
+        assert!(parsed.is_exact());
+        assert_matches!(parsed.value(), Codec::Avc1(_));
+    }
+
+    #[test]
+    fn repairs() {
+        use LenientFix::*;
+        assert_eq!(lenient("OPUS"), ("Opus".to_owned(), vec![NormalisedCase]));
+        assert_eq!(lenient("FLAC"), ("fLaC".to_owned(), vec![NormalisedCase]));
+        assert_eq!(
+            lenient("'mp4a.40.2.'"),
+            (
+                "mp4a.40.2".to_owned(),
+                vec![RemovedQuotes, RemovedTrailingSeparator]
+            )
+        );
+        #[cfg(feature = "av1")]
+        assert_eq!(
+            lenient("av01.0.4m.8"),
+            (
+                "av01.0.04M.08".to_owned(),
+                vec![NormalisedCase, AddedLeadingZeros]
+            )
+        );
+        #[cfg(feature = "hevc")]
+        assert_eq!(
+            lenient("hvc1.1.6.l93.b0"),
+            ("hvc1.1.6.L93.B0".to_owned(), vec![NormalisedCase])
+        );
+        #[cfg(feature = "vp9")]
+        assert_eq!(
+            lenient("vp09.00.10.08.1.1.1.1.0"),
+            (
+                "vp09.00.10.08.01.01.01.01.00".to_owned(),
+                vec![AddedLeadingZeros]
+            )
+        );
+    }
+
+    #[test]
+    fn unrepairable() {
+        assert_matches!(Codec::parse_lenient("avc1.4d41e"), Err(_));
+        assert_matches!(Codec::parse_lenient("\"\""), Err(_));
+        // an unknown sample entry is left as given, rather than having its case changed
+        assert_matches!(
+            Codec::parse_lenient("VVC1.1.L51"),
+            Ok(l) if matches!(l.value(), Codec::Other(v) if v.as_str() == "VVC1.1.L51")
+        );
+    }
+
+    #[test]
+    fn list() {
+        let parsed = Codec::parse_codecs_lenient(" avc1.4D401E , \"OPUS\" ").unwrap();
+        assert_matches!(parsed.value()[..], [Codec::Avc1(_), Codec::Opus]);
+        assert_eq!(
+            parsed.fixes(),
+            &[
+                LenientFix::TrimmedWhitespace,
+                LenientFix::RemovedQuotes,
+                LenientFix::NormalisedCase
+            ]
+        );
+        let err = Codec::parse_codecs_lenient("\"avc1.4D401E,,mp4a.40.2\"").unwrap_err();
+        assert_eq!(err.index(), 1);
+        assert_eq!(err.offset(), 13);
+    }
+}
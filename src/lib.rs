@@ -25,10 +25,9 @@
 //! assert_eq!(codec.to_string(), "avc1.4D401E")
 //! ```
 //!
-//! ## No support for 'fancy' syntax
+//! ## Parameter syntax
 //!
-//! RFC 6381 specifies the following BNF grammar for general syntax, which this crate does not
-//! yet fully support:
+//! RFC 6381 specifies the following BNF grammar for the whole `codecs` parameter:
 //!
 //! ```text
 //!   codecs      := cod-simple / cod-fancy
@@ -59,59 +58,556 @@
 //!   DQUOTE      := %x22 ; " (double quote)
 //! ```
 //!
-//! In particular note the following productions:
+//! [`Codec::parse_codecs()`] deals with just the list of values (the part inside the quotes of
+//! a `cod-simple` parameter), while the whole parameter is handled by
+//! [`Codec::parse_codecs_parameter()`] and [`CodecsParameter`]:
 //!
-//!  - `cod-simple` - specifies the attribute name+value structure `codec=".."` — this crate only
-//!    supports dealing with the value of this attribute (the bit inside quotes).
-//!  - `cod-fancy` (and related productions `fancy-sing` / `fancy-list` etc.) — show extended
-//!    structures that can optionally specify a charset for the data like `en-gb'UTF-8'%25%20xz` or `''%25%20xz` — this crate does not support values
-//!    using these structures.
+//!  - `cod-simple` — the `codecs=".."` form, either quoted or as a single unquoted value
+//!  - `cod-fancy` (and the related productions `fancy-sing` / `fancy-list`) — the
+//!    `codecs*=..` form, with an optional charset and language tag, like `UTF-8'en-gb'avc1.4D401E`
+//!    or `''avc1.4D401E`, and percent-encoding.  Only the `US-ASCII` and `UTF-8` charsets are
+//!    supported, and [`CodecsParameter`] keeps the language tag.
+//!
+//! ```rust
+//! # use rfc6381_codec::{Codec, CodecsParameter};
+//! # use std::str::FromStr;
+//! let codecs = Codec::parse_codecs_parameter(r#"codecs="avc1.4D401E, mp4a.40.2""#).unwrap();
+//! assert_eq!(codecs.len(), 2);
+//! let param = CodecsParameter::from_str("codecs*=UTF-8'en-gb'%61vc1.4D401E").unwrap();
+//! assert_eq!(param.language(), Some("en-gb"));
+//! assert_eq!(param.codecs()[0].to_string(), "avc1.4D401E");
+//! ```
 
 use four_cc::FourCC;
-use mp4ra_rust::{ObjectTypeIdentifier, SampleEntryCode};
+use mp4ra_rust::{HandlerCode, ObjectTypeIdentifier, SampleEntryCode};
 use mpeg4_audio_const::AudioObjectType;
 use std::convert::TryFrom;
 use std::fmt;
 use std::str::FromStr;
 
-#[derive(Debug)]
+mod apv;
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+#[cfg(feature = "av1")]
+mod av1;
+mod avc;
+mod avs3;
+#[cfg(feature = "browser-support")]
+mod browser;
+mod bulk;
+mod canonical;
+mod cicp;
+mod cmaf;
+mod compat;
+mod content_type;
+mod convert;
+mod description;
+#[cfg(feature = "dolby")]
+mod dolby;
+#[cfg(feature = "dts")]
+mod dts;
+mod error;
+mod esds;
+mod format;
+#[cfg(feature = "h264-reader")]
+mod h264;
+#[cfg(feature = "h265")]
+mod h265;
+mod heif;
+#[cfg(feature = "hevc")]
+mod hevc;
+mod hls;
+#[cfg(feature = "http")]
+mod http;
+mod lcevc;
+mod lenient;
+mod list;
+#[cfg(feature = "mime")]
+mod mime;
+#[cfg(feature = "mp4")]
+mod mp4;
+#[cfg(feature = "mp4parse")]
+mod mp4parse;
+#[cfg(feature = "mpegh")]
+mod mpegh;
+#[cfg(feature = "av1")]
+mod obu;
+mod options;
+mod opus;
+mod other;
+mod param;
+mod pcm;
+mod profiles;
+mod registry;
+mod sdp;
+#[cfg(feature = "serde")]
+mod serde;
+mod source;
+mod stsd;
+#[cfg(feature = "text")]
+mod text;
+mod ts;
+mod uncv;
+#[cfg(feature = "vp9")]
+mod vp9;
+mod webcodecs;
+#[cfg(feature = "webm")]
+mod webm;
+
+pub use apv::Apv1;
+#[cfg(feature = "av1")]
+pub use av1::{Av01, Av1ChromaSamplePosition, Av1ChromaSubsampling, Av1ColorConfig, Av1Level};
+#[cfg(feature = "av1")]
+pub use av1::{Av1Profile, Av1Tier};
+pub use avc::{Avc1, AvcLevel, AvcLevelLimits, AvcProfile};
+pub use avs3::Avs3;
+#[cfg(feature = "browser-support")]
+pub use browser::{Browser, BrowserSupport, EngineSupport};
+pub use cicp::{ColorPrimaries, MatrixCoefficients, TransferCharacteristics};
+pub use content_type::{ContentType, ContentTypeError};
+#[cfg(feature = "dolby")]
+pub use dolby::Ac4;
+#[cfg(feature = "dts")]
+pub use dts::Dts;
+pub use error::ErrorText;
+pub use format::{FormatOptions, HexCase};
+#[cfg(feature = "hevc")]
+pub use hevc::{Hevc, HevcLevel, HevcProfile, HevcSampleEntry, HevcTier};
+pub use hls::{HlsCodecs, HlsError, SupplementalCodec, SupplementalCodecs};
+pub use lcevc::{EnhancementError, LayeredCodec, Lvc1};
+pub use lenient::{Lenient, LenientFix};
+pub use list::CodecList;
+#[cfg(feature = "mpegh")]
+pub use mpegh::{Mpegh, MpeghProfileLevel, MpeghSampleEntry};
+pub use options::{ParseOptions, Strictness};
+pub use other::OtherCodec;
+pub use param::CodecsParameter;
+pub use pcm::Pcm;
+pub use profiles::Profiles;
+pub use registry::{CodecExtension, CodecFamilyParser, CodecRegistry, ExtensionCodec};
+pub use source::SourcedCodec;
+#[cfg(feature = "text")]
+pub use text::Stpp;
+pub use uncv::Uncv;
+#[cfg(feature = "vp9")]
+pub use vp9::{Vp09, Vp9ChromaSubsampling};
+
+/// A codec value, such as one entry of a `codecs` parameter.
+///
+/// Values are ordered first by codec, in the order of the variants as declared here, and then
+/// element by element, giving a total order that allows codec lists to be sorted
+/// deterministically.  For `avc1` the elements compare in the order they are written, by
+/// profile, then constraint flags and then level, so `avc1.42C01F` sorts before `avc1.42E01E`
+/// despite its higher level.  The level types like [`AvcLevel`] are ordered from lowest to
+/// highest.
+///
+/// ```rust
+/// # use rfc6381_codec::Codec;
+/// let mut codecs: Vec<Codec> = Codec::parse_codecs("mp4a.40.2,avc1.640028,avc1.4D401E")
+///     .map(Result::unwrap)
+///     .collect();
+/// codecs.sort();
+/// let sorted: Vec<_> = codecs.iter().map(Codec::to_string).collect();
+/// assert_eq!(sorted, ["avc1.4D401E", "avc1.640028", "mp4a.40.2"]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[non_exhaustive]
 pub enum Codec {
     Avc1(Avc1),
+    /// H.265 / HEVC video, using either the `hvc1` or `hev1` sample entry code
+    #[cfg(feature = "hevc")]
+    Hevc(Hevc),
+    #[cfg(feature = "av1")]
+    Av01(Av01),
+    #[cfg(feature = "vp9")]
+    Vp09(Vp09),
     Mp4a(Mp4a),
-    Unknown(String),
+    Lvc1(Lvc1),
+    Uncv(Uncv),
+    Avs3(Avs3),
+    /// AVS3 audio
+    Av3a,
+    Apv1(Apv1),
+    /// Opus audio
+    ///
+    /// Displayed using the `Opus` sample entry code, but the lowercase `opus` used in WebM
+    /// codecs parameters is also accepted when parsing.
+    Opus,
+    /// FLAC audio, using the mixed-case `fLaC` sample entry code
+    Flac,
+    /// Apple Lossless Audio Codec
+    Alac,
+    /// Dolby Digital (AC-3)
+    #[cfg(feature = "dolby")]
+    Ac3,
+    /// Dolby Digital Plus (Enhanced AC-3)
+    #[cfg(feature = "dolby")]
+    Eac3,
+    #[cfg(feature = "dolby")]
+    Ac4(Ac4),
+    #[cfg(feature = "dts")]
+    Dts(Dts),
+    #[cfg(feature = "mpegh")]
+    Mpegh(Mpegh),
+    /// The bare `mp3` token which some manifests use in place of one of the `mp4a` forms
+    /// identified by [`Mp4a::is_mp3()`]
+    Mp3,
+    /// DRA (Digital Rise Audio) audio
+    Dra1,
+    Pcm(Pcm),
+    /// WebVTT text
+    #[cfg(feature = "text")]
+    Wvtt,
+    #[cfg(feature = "text")]
+    Stpp(Stpp),
+    /// 3GPP timed text
+    #[cfg(feature = "text")]
+    Tx3g,
+    /// A codec value which this crate does not interpret, including bare tokens without any
+    /// `.` separator, like the `vorbis` or `vp8` used in WebM and Ogg media types
+    Other(OtherCodec),
+    /// A codec value produced by a parser registered with a [`CodecRegistry`]
+    Extension(ExtensionCodec),
 }
 impl Codec {
     pub fn parse_codecs(codecs: &str) -> impl Iterator<Item = Result<Codec, CodecError>> + '_ {
         codecs.split(',').map(|s| s.trim().parse())
     }
 
-    pub fn avc1(profile: u8, constraints: u8, level: u8) -> Self {
+    /// Like [`parse_codecs()`](Self::parse_codecs), but each result is paired with the entry of
+    /// the list it was parsed from, exactly as it appears in the given string (including any
+    /// whitespace around it).
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::Codec;
+    /// let failed: Vec<_> = Codec::parse_codecs_with_raw("avc1.4D401E, mp4a.4g")
+    ///     .filter(|(_, result)| result.is_err())
+    ///     .map(|(raw, _)| raw)
+    ///     .collect();
+    /// assert_eq!(failed, [" mp4a.4g"]);
+    /// ```
+    pub fn parse_codecs_with_raw(
+        codecs: &str,
+    ) -> impl Iterator<Item = (&str, Result<Codec, CodecError>)> + '_ {
+        codecs.split(',').map(|s| (s, s.trim().parse()))
+    }
+
+    /// Like [`parse_codecs()`](Self::parse_codecs), but any error also identifies which entry of
+    /// the list failed to parse, and where that entry starts within the given string.
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::{Codec, CodecError};
+    /// let err = Codec::parse_codecs_with_positions("avc1.4D401E, mp4a.40.2, avc1.4D40ZZ")
+    ///     .find_map(Result::err)
+    ///     .unwrap();
+    /// assert_eq!(err.index(), 2);
+    /// assert_eq!(err.offset(), 24);
+    /// assert!(matches!(err.error(), CodecError::InvalidComponent(_)));
+    /// ```
+    pub fn parse_codecs_with_positions(
+        codecs: &str,
+    ) -> impl Iterator<Item = Result<Codec, CodecListError>> + '_ {
+        let mut start = 0;
+        codecs.split(',').enumerate().map(move |(index, s)| {
+            let offset = start + (s.len() - s.trim_start().len());
+            start += s.len() + 1;
+            s.trim().parse().map_err(|error| CodecListError {
+                index,
+                offset,
+                error,
+            })
+        })
+    }
+
+    /// The kind of media which this codec encodes.
+    ///
+    /// For `Codec::Other` values, the kind of media is derived from the handler which the MP4RA
+    /// registers for the sample entry code, if any, or for bare tokens like `vorbis` and `vp8`
+    /// from the WebM and Ogg media types which use them.
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::{Codec, MediaKind};
+    /// let (video, other): (Vec<_>, Vec<_>) = Codec::parse_codecs("avc1.640028,mp4a.40.2,hvc1.1.6.L93.B0")
+    ///     .map(Result::unwrap)
+    ///     .partition(|c| c.media_kind() == MediaKind::Video);
+    /// assert_eq!(video.len(), 2);
+    /// assert_eq!(other.len(), 1);
+    /// ```
+    pub fn media_kind(&self) -> MediaKind {
+        match self {
+            Codec::Avc1(_) | Codec::Lvc1(_) | Codec::Uncv(_) | Codec::Avs3(_) | Codec::Apv1(_) => {
+                MediaKind::Video
+            }
+            #[cfg(feature = "hevc")]
+            Codec::Hevc(_) => MediaKind::Video,
+            #[cfg(feature = "av1")]
+            Codec::Av01(_) => MediaKind::Video,
+            #[cfg(feature = "vp9")]
+            Codec::Vp09(_) => MediaKind::Video,
+            Codec::Mp4a(_)
+            | Codec::Av3a
+            | Codec::Opus
+            | Codec::Flac
+            | Codec::Alac
+            | Codec::Mp3
+            | Codec::Dra1
+            | Codec::Pcm(_) => MediaKind::Audio,
+            #[cfg(feature = "dolby")]
+            Codec::Ac3 | Codec::Eac3 | Codec::Ac4(_) => MediaKind::Audio,
+            #[cfg(feature = "dts")]
+            Codec::Dts(_) => MediaKind::Audio,
+            #[cfg(feature = "mpegh")]
+            Codec::Mpegh(_) => MediaKind::Audio,
+            #[cfg(feature = "text")]
+            Codec::Wvtt | Codec::Stpp(_) | Codec::Tx3g => MediaKind::Text,
+            Codec::Extension(ext) => ext.get().media_kind(),
+            Codec::Other(other) => {
+                let token = other.as_str();
+                if let Some((_, kind)) = BARE_TOKENS.iter().find(|(t, _)| *t == token) {
+                    return *kind;
+                }
+                let sample_entry = match other.four_cc() {
+                    Some(fourcc) => SampleEntryCode::from(fourcc),
+                    None => return MediaKind::Unknown,
+                };
+                match sample_entry.handler() {
+                    Some(HandlerCode::VIDE) | Some(HandlerCode::AUXV) | Some(HandlerCode::PICT) => {
+                        MediaKind::Video
+                    }
+                    Some(HandlerCode::SOUN) => MediaKind::Audio,
+                    Some(HandlerCode::TEXT) | Some(HandlerCode::SUBT) | Some(HandlerCode::SBTL) => {
+                        MediaKind::Text
+                    }
+                    Some(HandlerCode::META) => MediaKind::Metadata,
+                    _ => MediaKind::Unknown,
+                }
+            }
+        }
+    }
+
+    /// The four-character-code at the start of this codec value, like `avc1` in `avc1.640028`.
+    ///
+    /// Returns `None` for [`Codec::Mp3`], and for [`Codec::Other`] values where the leading
+    /// element isn't four bytes long.
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::Codec;
+    /// # use four_cc::FourCC;
+    /// # use std::str::FromStr;
+    /// let codec = Codec::from_str("hev1.1.6.L93.B0").unwrap();
+    /// assert_eq!(codec.four_cc(), Some(FourCC(*b"hev1")));
+    /// let codec = Codec::from_str("vvc1.1.L51.CQA").unwrap();
+    /// assert_eq!(codec.four_cc(), Some(FourCC(*b"vvc1")));
+    /// ```
+    pub fn four_cc(&self) -> Option<FourCC> {
+        match self {
+            Codec::Other(other) => other.four_cc(),
+            Codec::Extension(ext) => ext.four_cc(),
+            _ => self.sample_entry_code().map(|code| code.0),
+        }
+    }
+
+    /// The ISOBMFF sample entry code for this codec value.
+    ///
+    /// This differs from [`four_cc()`](Self::four_cc) in that for [`Codec::Other`] values,
+    /// `None` is returned unless the leading element is a sample entry code known to the
+    /// MP4 Registration Authority data this crate uses.
+    pub fn sample_entry_code(&self) -> Option<SampleEntryCode> {
+        let code = match self {
+            Codec::Avc1(_) => SampleEntryCode::AVC1,
+            #[cfg(feature = "hevc")]
+            Codec::Hevc(hevc) => hevc.sample_entry().sample_entry_code(),
+            #[cfg(feature = "av1")]
+            Codec::Av01(_) => SampleEntryCode::AV01,
+            #[cfg(feature = "vp9")]
+            Codec::Vp09(_) => SampleEntryCode::VP09,
+            Codec::Mp4a(_) => SampleEntryCode::MP4A,
+            Codec::Lvc1(_) => lcevc::LVC1,
+            Codec::Uncv(_) => uncv::UNCV,
+            Codec::Avs3(_) => SampleEntryCode::AVS3,
+            Codec::Av3a => avs3::AV3A,
+            Codec::Apv1(_) => apv::APV1,
+            Codec::Opus => SampleEntryCode::OPUS,
+            Codec::Flac => FLAC,
+            Codec::Alac => SampleEntryCode::ALAC,
+            #[cfg(feature = "dolby")]
+            Codec::Ac3 => SampleEntryCode::AC_3,
+            #[cfg(feature = "dolby")]
+            Codec::Eac3 => SampleEntryCode::EC_3,
+            #[cfg(feature = "dolby")]
+            Codec::Ac4(_) => SampleEntryCode::AC_4,
+            #[cfg(feature = "dts")]
+            Codec::Dts(dts) => dts.sample_entry_code(),
+            #[cfg(feature = "mpegh")]
+            Codec::Mpegh(mpegh) => mpegh.sample_entry().sample_entry_code(),
+            Codec::Mp3 => return None,
+            Codec::Dra1 => SampleEntryCode::DRA1,
+            Codec::Pcm(pcm) => pcm.sample_entry_code(),
+            #[cfg(feature = "text")]
+            Codec::Wvtt => SampleEntryCode::WVTT,
+            #[cfg(feature = "text")]
+            Codec::Stpp(_) => SampleEntryCode::STPP,
+            #[cfg(feature = "text")]
+            Codec::Tx3g => SampleEntryCode::TX3G,
+            Codec::Other(other) => {
+                return other
+                    .four_cc()
+                    .map(SampleEntryCode::from)
+                    .filter(|code| code.handler().is_some())
+            }
+            Codec::Extension(ext) => return ext.four_cc().map(SampleEntryCode::from),
+        };
+        Some(code)
+    }
+
+    /// Creates an `avc1` value from the raw `profile_idc`, constraint flags and `level_idc`
+    /// bytes, which unlike [`Avc1::new()`] can be used in `const` items
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::Codec;
+    /// const MAIN_31: Codec = Codec::avc1(0x4d, 0x40, 0x1f);
+    /// assert_eq!(MAIN_31.to_string(), "avc1.4D401F");
+    /// ```
+    pub const fn avc1(profile: u8, constraints: u8, level: u8) -> Self {
         Codec::Avc1(Avc1 {
             profile,
             constraints,
             level,
         })
     }
+
+    /// Shortcut for `Codec::Mp4a(Mp4a::aac_lc())`, the `mp4a.40.2` codec
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::Codec;
+    /// assert_eq!(Codec::aac_lc().to_string(), "mp4a.40.2");
+    /// ```
+    pub const fn aac_lc() -> Self {
+        Codec::Mp4a(Mp4a::aac_lc())
+    }
+
+    /// H.264 Constrained Baseline profile, level 3.0 (`avc1.42E01E`)
+    ///
+    /// These constants allow common codec values to be used in `const` and `static` items,
+    /// such as lookup tables.
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::Codec;
+    /// static PREFERRED: [Codec; 2] = [Codec::AVC1_HIGH_40, Codec::AAC_LC];
+    /// assert_eq!(PREFERRED[0].to_string(), "avc1.640028");
+    /// ```
+    pub const AVC1_CONSTRAINED_BASELINE_30: Codec = Codec::avc1(0x42, 0xe0, 0x1e);
+    /// H.264 Main profile, level 3.0 (`avc1.4D401E`)
+    pub const AVC1_MAIN_30: Codec = Codec::avc1(0x4d, 0x40, 0x1e);
+    /// H.264 Main profile, level 3.1 (`avc1.4D401F`)
+    pub const AVC1_MAIN_31: Codec = Codec::avc1(0x4d, 0x40, 0x1f);
+    /// H.264 High profile, level 4.0 (`avc1.640028`)
+    pub const AVC1_HIGH_40: Codec = Codec::avc1(0x64, 0x00, 0x28);
+    /// H.264 High profile, level 4.1 (`avc1.640029`)
+    pub const AVC1_HIGH_41: Codec = Codec::avc1(0x64, 0x00, 0x29);
+    /// AAC-LC (`mp4a.40.2`)
+    pub const AAC_LC: Codec = Codec::aac_lc();
+    /// HE-AAC, explicitly signalled (`mp4a.40.5`)
+    pub const HE_AAC: Codec = Codec::Mp4a(Mp4a::he_aac());
+    /// HE-AAC v2, explicitly signalled (`mp4a.40.29`)
+    pub const HE_AAC_V2: Codec = Codec::Mp4a(Mp4a::he_aac_v2());
+}
+/// The kind of media which a codec encodes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum MediaKind {
+    Video,
+    Audio,
+    /// Timed text, such as subtitles or captions
+    Text,
+    /// Timed metadata
+    Metadata,
+    /// The kind of media is not known for this codec
+    Unknown,
 }
+
+/// WebM uses the lowercase form of the `Opus` sample entry code in its codecs parameter
+const OPUS_WEBM: SampleEntryCode = SampleEntryCode(FourCC(*b"opus"));
+
+/// The sample entry for FLAC in ISOBMFF, which is not yet present in the MP4RA data
+const FLAC: SampleEntryCode = SampleEntryCode(FourCC(*b"fLaC"));
+
+/// Codec tokens without a hierarchy separator which are used in WebM and Ogg media types, and
+/// are not sample entry codes
+const BARE_TOKENS: &[(&str, MediaKind)] = &[
+    ("vorbis", MediaKind::Audio),
+    ("theora", MediaKind::Video),
+    ("vp8", MediaKind::Video),
+    ("vp9", MediaKind::Video),
+    ("av1", MediaKind::Video),
+];
+
+/// WebM and Ogg media types use the lowercase `flac` token
+const FLAC_WEBM: SampleEntryCode = SampleEntryCode(FourCC(*b"flac"));
+
 impl FromStr for Codec {
     type Err = CodecError;
 
     fn from_str(codec: &str) -> Result<Codec, Self::Err> {
-        if let Some(pos) = codec.find('.') {
-            let (fourcc, rest) = codec.split_at(pos);
-            if fourcc.len() != 4 {
-                return Ok(Codec::Unknown(codec.to_string()));
-            }
-            let fourcc = FourCC::from(fourcc.as_bytes());
-            let sample_entry = SampleEntryCode::from(fourcc);
-            match sample_entry {
-                SampleEntryCode::MP4A => Ok(Codec::Mp4a(get_rest(rest)?.parse()?)),
-                SampleEntryCode::AVC1 => Ok(Codec::Avc1(get_rest(rest)?.parse()?)),
-                _ => Ok(Codec::Unknown(codec.to_owned())),
-            }
-        } else {
-            Err(CodecError::ExpectedHierarchySeparator(codec.to_string()))
+        if codec == "mp3" {
+            return Ok(Codec::Mp3);
+        }
+        let (fourcc, rest) = match codec.find('.') {
+            Some(pos) => codec.split_at(pos),
+            None => (codec, ""),
+        };
+        if codec.is_empty() {
+            return Err(CodecError::ExpectedHierarchySeparator(codec.into()));
+        }
+        if fourcc.len() != 4 {
+            return Ok(Codec::Other(codec.into()));
+        }
+        let fourcc = FourCC::from(fourcc.as_bytes());
+        let sample_entry = SampleEntryCode::from(fourcc);
+        #[cfg(feature = "dts")]
+        if let Some(dts) = Dts::from_sample_entry(sample_entry) {
+            return no_rest(codec, rest, Codec::Dts(dts));
+        }
+        if let Some(pcm) = Pcm::from_sample_entry(sample_entry) {
+            return no_rest(codec, rest, Codec::Pcm(pcm));
+        }
+        #[cfg(feature = "hevc")]
+        if let Some(hevc) = HevcSampleEntry::from_sample_entry(sample_entry) {
+            return Ok(Codec::Hevc(Hevc::parse(hevc, get_rest(codec, rest)?)?));
+        }
+        #[cfg(feature = "mpegh")]
+        if let Some(mpegh) = MpeghSampleEntry::from_sample_entry(sample_entry) {
+            return Ok(Codec::Mpegh(Mpegh::parse(mpegh, rest.strip_prefix('.'))?));
+        }
+        match sample_entry {
+            SampleEntryCode::MP4A => Ok(Codec::Mp4a(get_rest(codec, rest)?.parse()?)),
+            SampleEntryCode::AVC1 => Ok(Codec::Avc1(get_rest(codec, rest)?.parse()?)),
+            #[cfg(feature = "av1")]
+            SampleEntryCode::AV01 => Ok(Codec::Av01(get_rest(codec, rest)?.parse()?)),
+            #[cfg(feature = "vp9")]
+            SampleEntryCode::VP09 => Ok(Codec::Vp09(get_rest(codec, rest)?.parse()?)),
+            lcevc::LVC1 => Ok(Codec::Lvc1(get_rest(codec, rest)?.parse()?)),
+            uncv::UNCV => Ok(Codec::Uncv(get_optional_rest(rest)?)),
+            SampleEntryCode::AVS3 => Ok(Codec::Avs3(get_rest(codec, rest)?.parse()?)),
+            avs3::AV3A => no_rest(codec, rest, Codec::Av3a),
+            apv::APV1 => Ok(Codec::Apv1(get_optional_rest(rest)?)),
+            SampleEntryCode::OPUS | OPUS_WEBM => no_rest(codec, rest, Codec::Opus),
+            FLAC | FLAC_WEBM => no_rest(codec, rest, Codec::Flac),
+            SampleEntryCode::ALAC => no_rest(codec, rest, Codec::Alac),
+            #[cfg(feature = "dolby")]
+            SampleEntryCode::AC_3 => no_rest(codec, rest, Codec::Ac3),
+            #[cfg(feature = "dolby")]
+            SampleEntryCode::EC_3 => no_rest(codec, rest, Codec::Eac3),
+            #[cfg(feature = "dolby")]
+            SampleEntryCode::AC_4 => Ok(Codec::Ac4(get_rest(codec, rest)?.parse()?)),
+            SampleEntryCode::DRA1 => no_rest(codec, rest, Codec::Dra1),
+            #[cfg(feature = "text")]
+            SampleEntryCode::WVTT => no_rest(codec, rest, Codec::Wvtt),
+            #[cfg(feature = "text")]
+            SampleEntryCode::STPP => Ok(Codec::Stpp(get_optional_rest(rest)?)),
+            #[cfg(feature = "text")]
+            SampleEntryCode::TX3G => no_rest(codec, rest, Codec::Tx3g),
+            _ => Ok(Codec::Other(codec.into())),
         }
     }
 }
@@ -123,88 +619,432 @@ impl fmt::Display for Codec {
                 constraints,
                 level,
             }) => write!(f, "avc1.{:02X}{:02X}{:02X}", profile, constraints, level),
+            #[cfg(feature = "hevc")]
+            Codec::Hevc(hevc) => write!(f, "{}", hevc),
+            #[cfg(feature = "av1")]
+            Codec::Av01(av01) => write!(f, "av01.{}", av01),
+            #[cfg(feature = "vp9")]
+            Codec::Vp09(vp09) => write!(f, "vp09.{}", vp09),
             Codec::Mp4a(mp4a) => write!(f, "mp4a.{}", mp4a),
-            Codec::Unknown(val) => f.write_str(val),
+            Codec::Lvc1(lvc1) => write!(f, "lvc1.{}", lvc1),
+            Codec::Uncv(uncv) => write!(f, "uncv{}", uncv),
+            Codec::Avs3(avs3) => write!(f, "avs3.{}", avs3),
+            Codec::Av3a => f.write_str("av3a"),
+            Codec::Apv1(apv1) => write!(f, "apv1{}", apv1),
+            Codec::Opus => f.write_str("Opus"),
+            Codec::Flac => f.write_str("fLaC"),
+            Codec::Alac => f.write_str("alac"),
+            #[cfg(feature = "dolby")]
+            Codec::Ac3 => f.write_str("ac-3"),
+            #[cfg(feature = "dolby")]
+            Codec::Eac3 => f.write_str("ec-3"),
+            #[cfg(feature = "dolby")]
+            Codec::Ac4(ac4) => write!(f, "ac-4.{}", ac4),
+            #[cfg(feature = "dts")]
+            Codec::Dts(dts) => write!(f, "{}", dts),
+            #[cfg(feature = "mpegh")]
+            Codec::Mpegh(mpegh) => write!(f, "{}", mpegh),
+            Codec::Mp3 => f.write_str("mp3"),
+            Codec::Dra1 => f.write_str("dra1"),
+            Codec::Pcm(pcm) => write!(f, "{}", pcm),
+            #[cfg(feature = "text")]
+            Codec::Wvtt => f.write_str("wvtt"),
+            #[cfg(feature = "text")]
+            Codec::Stpp(stpp) => write!(f, "stpp{}", stpp),
+            #[cfg(feature = "text")]
+            Codec::Tx3g => f.write_str("tx3g"),
+            Codec::Other(other) => write!(f, "{}", other),
+            Codec::Extension(ext) => write!(f, "{}", ext),
         }
     }
 }
 
-fn get_rest(text: &str) -> Result<&str, CodecError> {
-    if text.is_empty() {
-        Ok(text)
-    } else if let Some(rest) = text.strip_prefix('.') {
-        Ok(rest)
+/// Returns the sub-elements following the initial `fourcc` of the given `codec` string, where
+/// the codec syntax requires at least one such element to be present.
+fn get_rest<'a>(codec: &str, rest: &'a str) -> Result<&'a str, CodecError> {
+    rest.strip_prefix('.')
+        .ok_or_else(|| CodecError::ExpectedHierarchySeparator(codec.into()))
+}
+
+/// Parses a sub-element consisting of exactly two hexadecimal digits.
+pub(crate) fn parse_hex_element(value: &str) -> Result<u8, CodecError> {
+    if value.len() != 2 {
+        return Err(CodecError::UnexpectedLength {
+            expected: 2,
+            got: value.into(),
+        });
+    }
+    if !value.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(CodecError::InvalidComponent(value.into()));
+    }
+    u8::from_str_radix(value, 16).map_err(|_| CodecError::InvalidComponent(value.into()))
+}
+
+/// Parses a variable-width decimal element, such as the `profile_idc` in `hvc1.1.6.L93`, which
+/// must be no greater than `max`.
+pub(crate) fn parse_decimal(value: &str, max: u8) -> Result<u8, CodecError> {
+    if value.is_empty() || !value.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(CodecError::InvalidComponent(value.into()));
+    }
+    u8::from_str(value)
+        .ok()
+        .filter(|v| *v <= max)
+        .ok_or_else(|| CodecError::InvalidComponent(value.into()))
+}
+
+/// Parses a fixed-width decimal element, such as the two-digit `level` in `av01.0.04M.08`.
+#[cfg(any(feature = "av1", feature = "vp9"))]
+pub(crate) fn parse_decimal_element(value: &str, len: usize) -> Result<u8, CodecError> {
+    if value.len() != len {
+        return Err(CodecError::UnexpectedLength {
+            expected: len,
+            got: value.into(),
+        });
+    }
+    if !value.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(CodecError::InvalidComponent(value.into()));
+    }
+    u8::from_str(value).map_err(|_| CodecError::InvalidComponent(value.into()))
+}
+
+/// Checks that no sub-elements follow the initial `fourcc`, for codecs which are identified by
+/// their sample entry code alone.
+fn no_rest(codec: &str, rest: &str, result: Codec) -> Result<Codec, CodecError> {
+    if rest.is_empty() {
+        Ok(result)
     } else {
-        Err(CodecError::ExpectedHierarchySeparator(text.to_string()))
+        Err(CodecError::InvalidComponent(codec.into()))
     }
 }
 
-#[derive(Debug)]
+/// Parses the sub-elements following the initial `fourcc`, for codec syntax where all
+/// sub-elements are optional and may be omitted along with the `'.'` separator.
+fn get_optional_rest<T>(rest: &str) -> Result<T, CodecError>
+where
+    T: FromStr<Err = CodecError> + Default,
+{
+    match rest.strip_prefix('.') {
+        Some(rest) => rest.parse(),
+        None => Ok(T::default()),
+    }
+}
+
+/// Problems found when parsing or creating codec values.
+///
+/// Variants which include text from the input hold it as an [`ErrorText`], so that creating an
+/// error does not allocate.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum CodecError {
     /// The given codec-string-component was not valid
-    InvalidComponent(String),
+    InvalidComponent(ErrorText),
     /// expected the '.', but instead found the text included in the variant
-    ExpectedHierarchySeparator(String),
+    ExpectedHierarchySeparator(ErrorText),
     /// The length of the given string did not match the expected length
-    UnexpectedLength { expected: usize, got: String },
+    UnexpectedLength { expected: usize, got: ErrorText },
+    /// The codec is not one that may be used with the given media type
+    UnexpectedForMediaType {
+        media_type: ErrorText,
+        codec: ErrorText,
+    },
+    /// The decoder configuration record was shorter than the given number of bytes required
+    ConfigurationRecordTooShort { expected: usize, got: usize },
+    /// The `configurationVersion` value of the decoder configuration record is not supported
+    UnsupportedConfigurationVersion(u8),
+    /// An MPEG-4 descriptor with the `expected` tag value was required, but a descriptor with
+    /// the tag value `got` was found instead
+    UnexpectedDescriptor { expected: u8, got: u8 },
+    /// The channel count is not valid for the channel mapping family given in the decoder
+    /// configuration record
+    InvalidChannelMapping {
+        channel_count: u8,
+        channel_mapping_family: u8,
+    },
+    /// The NAL unit given was not of the required type, having the `nal_unit_type` value
+    /// included in the variant
+    UnexpectedNalUnitType(u8),
+    /// The OBU given was not of the required type, having the `obu_type` value included in the
+    /// variant
+    UnexpectedObuType(u8),
+    /// The sample entry (described by the text included in the variant) does not provide the
+    /// information needed to create a codec value
+    UnsupportedSampleEntry(ErrorText),
+    /// The data given did not start with the sync word of the expected frame header
+    InvalidSyncWord,
+    /// No NAL unit with the `nal_unit_type` value included in the variant was found
+    MissingNalUnit(u8),
+    /// The text included in the variant was not a valid `codecs` parameter
+    InvalidParameter(ErrorText),
+    /// The codec string included in the variant is not one registered for use with WebCodecs
+    UnsupportedByWebCodecs(ErrorText),
 }
 
-#[derive(Debug)]
-pub struct Avc1 {
-    profile: u8,
-    constraints: u8,
-    level: u8,
+/// An error parsing one entry of a codecs list, produced by
+/// [`Codec::parse_codecs_with_positions()`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CodecListError {
+    index: usize,
+    offset: usize,
+    error: CodecError,
 }
-impl Avc1 {
-    pub fn profile(&self) -> u8 {
-        self.profile
+impl CodecListError {
+    /// The position of the failing entry within the list, counting from zero
+    pub fn index(&self) -> usize {
+        self.index
     }
-    pub fn constraints(&self) -> u8 {
-        self.constraints
+    /// The byte offset within the original string at which the failing entry starts (after
+    /// any leading whitespace)
+    pub fn offset(&self) -> usize {
+        self.offset
     }
-    pub fn level(&self) -> u8 {
-        self.level
+    pub fn error(&self) -> &CodecError {
+        &self.error
     }
-}
-impl FromStr for Avc1 {
-    type Err = CodecError;
-
-    fn from_str(value: &str) -> Result<Self, Self::Err> {
-        if value.len() != 6 {
-            return Err(CodecError::UnexpectedLength {
-                expected: 6,
-                got: value.to_string(),
-            });
-        }
-
-        let profile = u8::from_str_radix(&value[0..2], 16)
-            .map_err(|_| CodecError::InvalidComponent(value.to_string()))?;
-
-        let constraints = u8::from_str_radix(&value[2..4], 16)
-            .map_err(|_| CodecError::InvalidComponent(value.to_string()))?;
-
-        let level = u8::from_str_radix(&value[4..6], 16)
-            .map_err(|_| CodecError::InvalidComponent(value.to_string()))?;
-
-        Ok(Avc1 {
-            profile,
-            constraints,
-            level,
-        })
+    pub fn into_error(self) -> CodecError {
+        self.error
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[non_exhaustive]
 pub enum Mp4a {
     Mpeg4Audio {
         audio_object_type: Option<AudioObjectType>,
     },
+    /// MPEG-2 AAC (ISO/IEC 13818-7), object-type-indications `0x66` to `0x68`
+    Mpeg2Aac {
+        profile: Mpeg2AacProfile,
+        audio_object_type_indication: Option<u8>,
+    },
+    /// MPEG-1 Audio (ISO/IEC 11172-3), object-type-indication `0x6B`, used to signal MP3
+    Mpeg1Audio,
+    /// MPEG-2 Audio (ISO/IEC 13818-3), object-type-indication `0x69`, used to signal MP3 including
+    /// the lower sampling frequencies added by MPEG-2
+    Mpeg2Audio,
     Unknown {
         object_type_indication: ObjectTypeIdentifier,
         audio_object_type_indication: Option<u8>,
     },
 }
+impl Mp4a {
+    /// Creates a value signalling xHE-AAC, i.e. MPEG-4 Audio using the _Unified Speech and Audio
+    /// Coding_ audio object type, 42.
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::{Codec, Mp4a};
+    /// assert_eq!(Codec::Mp4a(Mp4a::usac()).to_string(), "mp4a.40.42");
+    /// ```
+    pub const fn usac() -> Mp4a {
+        Mp4a::Mpeg4Audio {
+            audio_object_type: Some(AudioObjectType::USAC),
+        }
+    }
+
+    /// Creates a value signalling AAC-LC (`mp4a.40.2`)
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::{Codec, Mp4a};
+    /// assert_eq!(Codec::Mp4a(Mp4a::aac_lc()).to_string(), "mp4a.40.2");
+    /// ```
+    pub const fn aac_lc() -> Mp4a {
+        Mp4a::Mpeg4Audio {
+            audio_object_type: Some(AudioObjectType::AAC_LC),
+        }
+    }
+
+    /// Creates a value signalling AAC Main (`mp4a.40.1`)
+    pub const fn aac_main() -> Mp4a {
+        Mp4a::Mpeg4Audio {
+            audio_object_type: Some(AudioObjectType::AAC_MAIN),
+        }
+    }
+
+    /// Creates a value signalling HE-AAC explicitly, using the SBR audio object type (`mp4a.40.5`)
+    pub const fn he_aac() -> Mp4a {
+        Mp4a::Mpeg4Audio {
+            audio_object_type: Some(AudioObjectType::SBR),
+        }
+    }
+
+    /// Creates a value signalling HE-AAC v2 explicitly, using the PS audio object type
+    /// (`mp4a.40.29`)
+    pub const fn he_aac_v2() -> Mp4a {
+        Mp4a::Mpeg4Audio {
+            audio_object_type: Some(AudioObjectType::PS),
+        }
+    }
+
+    /// Describes how the presence of SBR (and PS) is signalled for this codec value, or `None` if
+    /// this is not a value for which SBR is applicable.
+    ///
+    /// An AAC-LC value (`mp4a.40.2`) may still carry SBR in the bitstream, relying on _implicit_
+    /// signalling so that decoders without SBR support can play the AAC-LC core.  The HE-AAC
+    /// values `mp4a.40.5` and `mp4a.40.29` signal the SBR or PS tools _explicitly_, and decoders
+    /// without support for these tools may not be able to play the stream at all.
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::{Mp4a, SbrSignalling};
+    /// assert_eq!(Mp4a::he_aac_v2().sbr_signalling(), Some(SbrSignalling::ExplicitPs));
+    /// ```
+    pub fn sbr_signalling(&self) -> Option<SbrSignalling> {
+        match self {
+            Mp4a::Mpeg4Audio {
+                audio_object_type: Some(aot),
+            } => match *aot {
+                AudioObjectType::AAC_LC => Some(SbrSignalling::Implicit),
+                AudioObjectType::SBR => Some(SbrSignalling::ExplicitSbr),
+                AudioObjectType::PS => Some(SbrSignalling::ExplicitPs),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Returns true if this value signals xHE-AAC (`mp4a.40.42`)
+    pub fn is_xhe_aac(&self) -> bool {
+        matches!(
+            self,
+            Mp4a::Mpeg4Audio {
+                audio_object_type: Some(AudioObjectType::USAC)
+            }
+        )
+    }
+
+    /// Returns true if this value signals MPEG-1/2 Layer III audio, either via the
+    /// MPEG-1/MPEG-2 Audio object-type-indications, or via the MPEG-4 Audio _Layer-3_ audio object
+    /// type (`mp4a.40.34`)
+    pub fn is_mp3(&self) -> bool {
+        match self {
+            Mp4a::Mpeg1Audio | Mp4a::Mpeg2Audio => true,
+            Mp4a::Mpeg4Audio { audio_object_type } => {
+                *audio_object_type == Some(AudioObjectType::LAYER3)
+            }
+            Mp4a::Mpeg2Aac { .. } | Mp4a::Unknown { .. } => false,
+        }
+    }
+}
+
+/// How the use of the SBR tool (and the PS tool) is signalled, as given by
+/// [`Mp4a::sbr_signalling()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SbrSignalling {
+    /// The codec value indicates AAC-LC, and any SBR or PS data is signalled implicitly within
+    /// the bitstream
+    Implicit,
+    /// The codec value indicates HE-AAC, explicitly signalling the SBR tool
+    ExplicitSbr,
+    /// The codec value indicates HE-AAC v2, explicitly signalling the SBR and PS tools
+    ExplicitPs,
+}
+
+/// The MPEG-2 AAC profiles, each of which has its own object-type-indication value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Mpeg2AacProfile {
+    Main,
+    LowComplexity,
+    ScalableSamplingRate,
+}
+impl Mpeg2AacProfile {
+    fn from_object_type_indication(oti: ObjectTypeIdentifier) -> Option<Mpeg2AacProfile> {
+        match oti {
+            ObjectTypeIdentifier::AUDIO_ISO_IEC_13818_7_MAIN_PROFILE => Some(Mpeg2AacProfile::Main),
+            ObjectTypeIdentifier::AUDIO_ISO_IEC_13818_7_LOW_COMPLEXITY_PROFILE => {
+                Some(Mpeg2AacProfile::LowComplexity)
+            }
+            ObjectTypeIdentifier::AUDIO_ISO_IEC_13818_7_SCALEABLE_SAMPLING_RATE_PROFILE => {
+                Some(Mpeg2AacProfile::ScalableSamplingRate)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn object_type_indication(&self) -> ObjectTypeIdentifier {
+        match self {
+            Mpeg2AacProfile::Main => ObjectTypeIdentifier::AUDIO_ISO_IEC_13818_7_MAIN_PROFILE,
+            Mpeg2AacProfile::LowComplexity => {
+                ObjectTypeIdentifier::AUDIO_ISO_IEC_13818_7_LOW_COMPLEXITY_PROFILE
+            }
+            Mpeg2AacProfile::ScalableSamplingRate => {
+                ObjectTypeIdentifier::AUDIO_ISO_IEC_13818_7_SCALEABLE_SAMPLING_RATE_PROFILE
+            }
+        }
+    }
+}
+impl Mp4a {
+    /// Orders values by object-type-indication and then audio-object-type-indication, as they
+    /// appear in the codec string, with the variant only distinguishing otherwise equal values
+    fn order_key(&self) -> (u8, Option<u8>, u8) {
+        match self {
+            Mp4a::Mpeg4Audio { audio_object_type } => (
+                u8::from(ObjectTypeIdentifier::AUDIO_ISO_IEC_14496_3),
+                audio_object_type.map(u8::from),
+                0,
+            ),
+            Mp4a::Mpeg2Aac {
+                profile,
+                audio_object_type_indication,
+            } => (
+                u8::from(profile.object_type_indication()),
+                *audio_object_type_indication,
+                1,
+            ),
+            Mp4a::Mpeg1Audio => (
+                u8::from(ObjectTypeIdentifier::AUDIO_ISO_IEC_11172_3),
+                None,
+                2,
+            ),
+            Mp4a::Mpeg2Audio => (
+                u8::from(ObjectTypeIdentifier::AUDIO_ISO_IEC_13818_3),
+                None,
+                3,
+            ),
+            Mp4a::Unknown {
+                object_type_indication,
+                audio_object_type_indication,
+            } => (
+                u8::from(*object_type_indication),
+                *audio_object_type_indication,
+                4,
+            ),
+        }
+    }
+}
+// AudioObjectType and ObjectTypeIdentifier don't implement Ord, so compare their values instead
+impl PartialOrd for Mp4a {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for Mp4a {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.order_key().cmp(&other.order_key())
+    }
+}
+// AudioObjectType and ObjectTypeIdentifier don't implement Hash, so hash their values instead
+impl std::hash::Hash for Mp4a {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            Mp4a::Mpeg4Audio { audio_object_type } => {
+                audio_object_type.map(u8::from).hash(state);
+            }
+            Mp4a::Mpeg2Aac {
+                profile,
+                audio_object_type_indication,
+            } => {
+                profile.hash(state);
+                audio_object_type_indication.hash(state);
+            }
+            Mp4a::Mpeg1Audio | Mp4a::Mpeg2Audio => {}
+            Mp4a::Unknown {
+                object_type_indication,
+                audio_object_type_indication,
+            } => {
+                u8::from(*object_type_indication).hash(state);
+                audio_object_type_indication.hash(state);
+            }
+        }
+    }
+}
 impl fmt::Display for Mp4a {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -219,6 +1059,26 @@ impl fmt::Display for Mp4a {
                 }
                 Ok(())
             }
+            Mp4a::Mpeg2Aac {
+                profile,
+                audio_object_type_indication,
+            } => {
+                write!(f, "{:02x}", u8::from(profile.object_type_indication()))?;
+                if let Some(aoti) = audio_object_type_indication {
+                    write!(f, ".{}", aoti)?;
+                }
+                Ok(())
+            }
+            Mp4a::Mpeg1Audio => write!(
+                f,
+                "{:02x}",
+                u8::from(ObjectTypeIdentifier::AUDIO_ISO_IEC_11172_3)
+            ),
+            Mp4a::Mpeg2Audio => write!(
+                f,
+                "{:02x}",
+                u8::from(ObjectTypeIdentifier::AUDIO_ISO_IEC_13818_3)
+            ),
             Mp4a::Unknown {
                 object_type_indication,
                 audio_object_type_indication,
@@ -239,28 +1099,47 @@ impl FromStr for Mp4a {
     fn from_str(value: &str) -> Result<Self, Self::Err> {
         let mut i = value.splitn(2, '.');
         let s = i.next().unwrap();
-        let oti =
-            u8::from_str_radix(s, 16).map_err(|_| CodecError::InvalidComponent(s.to_string()))?;
+        let oti = u8::from_str_radix(s, 16).map_err(|_| CodecError::InvalidComponent(s.into()))?;
         let oti = ObjectTypeIdentifier::from(oti);
         let aoti = i
             .next()
-            .map(|v| u8::from_str(v))
+            .map(u8::from_str)
             .transpose()
-            .map_err(|e| CodecError::InvalidComponent(e.to_string()))?;
+            .map_err(|e| CodecError::InvalidComponent(format_args!("{}", e).into()))?;
+        Mp4a::from_indications(oti, aoti)
+    }
+}
+impl Mp4a {
+    /// Creates a value from the object-type-indication, and the audio-object-type-indication
+    /// if any.
+    pub(crate) fn from_indications(
+        oti: ObjectTypeIdentifier,
+        aoti: Option<u8>,
+    ) -> Result<Mp4a, CodecError> {
         match oti {
             ObjectTypeIdentifier::AUDIO_ISO_IEC_14496_3 => {
                 let aoti = aoti
                     .map(AudioObjectType::try_from)
                     .transpose()
-                    .map_err(|_e| CodecError::InvalidComponent(aoti.unwrap().to_string()))?;
+                    .map_err(|_e| {
+                        CodecError::InvalidComponent(format_args!("{}", aoti.unwrap()).into())
+                    })?;
                 Ok(Mp4a::Mpeg4Audio {
                     audio_object_type: aoti,
                 })
             }
-            _ => Ok(Mp4a::Unknown {
-                object_type_indication: oti,
-                audio_object_type_indication: aoti,
-            }),
+            ObjectTypeIdentifier::AUDIO_ISO_IEC_11172_3 if aoti.is_none() => Ok(Mp4a::Mpeg1Audio),
+            ObjectTypeIdentifier::AUDIO_ISO_IEC_13818_3 if aoti.is_none() => Ok(Mp4a::Mpeg2Audio),
+            _ => match Mpeg2AacProfile::from_object_type_indication(oti) {
+                Some(profile) => Ok(Mp4a::Mpeg2Aac {
+                    profile,
+                    audio_object_type_indication: aoti,
+                }),
+                None => Ok(Mp4a::Unknown {
+                    object_type_indication: oti,
+                    audio_object_type_indication: aoti,
+                }),
+            },
         }
     }
 }
@@ -298,6 +1177,84 @@ mod tests {
         roundtrip("mp4a.41");
     }
 
+    #[test]
+    fn mp3() {
+        assert_matches!(
+            Codec::from_str("mp4a.6B"),
+            Ok(Codec::Mp4a(Mp4a::Mpeg1Audio))
+        );
+        assert_matches!(
+            Codec::from_str("mp4a.69"),
+            Ok(Codec::Mp4a(Mp4a::Mpeg2Audio))
+        );
+        assert_matches!(Codec::from_str("mp3"), Ok(Codec::Mp3));
+        roundtrip("mp4a.6b");
+        roundtrip("mp4a.69");
+        roundtrip("mp3");
+        for s in &["mp4a.6B", "mp4a.69", "mp4a.40.34"] {
+            assert_matches!(Codec::from_str(s), Ok(Codec::Mp4a(m)) if m.is_mp3());
+        }
+        assert_matches!(Codec::from_str("mp4a.40.2"), Ok(Codec::Mp4a(m)) if !m.is_mp3());
+    }
+
+    #[test]
+    fn usac() {
+        assert_matches!(Codec::from_str("mp4a.40.42"), Ok(Codec::Mp4a(m)) if m.is_xhe_aac());
+        assert_matches!(Codec::from_str("mp4a.40.2"), Ok(Codec::Mp4a(m)) if !m.is_xhe_aac());
+        assert!(Mp4a::usac().is_xhe_aac());
+        roundtrip("mp4a.40.42");
+    }
+
+    #[test]
+    fn he_aac() {
+        assert_eq!(Codec::Mp4a(Mp4a::he_aac()).to_string(), "mp4a.40.5");
+        assert_eq!(Codec::Mp4a(Mp4a::he_aac_v2()).to_string(), "mp4a.40.29");
+        let sbr = |s| match Codec::from_str(s) {
+            Ok(Codec::Mp4a(mp4a)) => mp4a.sbr_signalling(),
+            _ => panic!("expected mp4a"),
+        };
+        assert_eq!(sbr("mp4a.40.2"), Some(SbrSignalling::Implicit));
+        assert_eq!(sbr("mp4a.40.5"), Some(SbrSignalling::ExplicitSbr));
+        assert_eq!(sbr("mp4a.40.29"), Some(SbrSignalling::ExplicitPs));
+        assert_eq!(sbr("mp4a.40.42"), None);
+        assert_eq!(sbr("mp4a.40"), None);
+        assert_eq!(sbr("mp4a.67"), None);
+    }
+
+    #[test]
+    fn aac_constructors() {
+        assert_eq!(Codec::aac_lc().to_string(), "mp4a.40.2");
+        assert_eq!(Codec::Mp4a(Mp4a::aac_main()).to_string(), "mp4a.40.1");
+        roundtrip("mp4a.40.1");
+    }
+
+    #[test]
+    fn mpeg2_aac() {
+        assert_matches!(
+            Codec::from_str("mp4a.67"),
+            Ok(Codec::Mp4a(Mp4a::Mpeg2Aac {
+                profile: Mpeg2AacProfile::LowComplexity,
+                audio_object_type_indication: None
+            }))
+        );
+        assert_matches!(
+            Codec::from_str("mp4a.66.1"),
+            Ok(Codec::Mp4a(Mp4a::Mpeg2Aac {
+                profile: Mpeg2AacProfile::Main,
+                audio_object_type_indication: Some(1)
+            }))
+        );
+        assert_matches!(
+            Codec::from_str("mp4a.68"),
+            Ok(Codec::Mp4a(Mp4a::Mpeg2Aac {
+                profile: Mpeg2AacProfile::ScalableSamplingRate,
+                ..
+            }))
+        );
+        roundtrip("mp4a.67");
+        roundtrip("mp4a.66.1");
+    }
+
     #[test]
     fn bad_oti_digit() {
         assert_matches!(Codec::from_str("mp4a.4g"), Err(_));
@@ -322,6 +1279,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn list_raw() {
+        let results: Vec<_> = Codec::parse_codecs_with_raw("opus,  avc1 ,alac").collect();
+        assert_matches!(results[0], ("opus", Ok(Codec::Opus)));
+        assert_matches!(
+            results[1],
+            ("  avc1 ", Err(CodecError::ExpectedHierarchySeparator(_)))
+        );
+        assert_matches!(results[2], ("alac", Ok(Codec::Alac)));
+    }
+
+    #[test]
+    fn list_positions() {
+        let results: Vec<_> =
+            Codec::parse_codecs_with_positions("mp4a.40.2,  avc1.4d40zz,avc1,opus").collect();
+        assert_eq!(results.len(), 4);
+        assert_matches!(results[0], Ok(Codec::Mp4a(_)));
+        assert_matches!(
+            &results[1],
+            Err(e) if e.index() == 1 && e.offset() == 12
+        );
+        assert_matches!(
+            &results[2],
+            Err(e) if e.index() == 2 && e.offset() == 24
+        );
+        assert_matches!(results[3], Ok(Codec::Opus));
+        let e = results.into_iter().nth(2).unwrap().unwrap_err();
+        assert_matches!(e.into_error(), CodecError::ExpectedHierarchySeparator(t) if t == "avc1");
+    }
+
     #[test]
     fn avc1() {
         assert_matches!(
@@ -343,14 +1330,209 @@ mod tests {
 
     #[test]
     fn unknown_fourcc() {
-        assert_matches!(Codec::from_str("badd.41"), Ok(Codec::Unknown(v)) if v == "badd.41");
+        assert_matches!(Codec::from_str("badd.41"), Ok(Codec::Other(v)) if v.as_str() == "badd.41");
         roundtrip("badd.41");
     }
 
+    #[test]
+    fn opus() {
+        assert_matches!(Codec::from_str("Opus"), Ok(Codec::Opus));
+        assert_matches!(Codec::from_str("opus"), Ok(Codec::Opus));
+        assert_eq!("Opus", Codec::from_str("opus").unwrap().to_string());
+        roundtrip("Opus");
+        assert_matches!(Codec::from_str("Opus.1"), Err(_));
+    }
+
+    #[test]
+    fn flac() {
+        assert_matches!(Codec::from_str("fLaC"), Ok(Codec::Flac));
+        roundtrip("fLaC");
+        // the lowercase token used by WebM
+        assert_matches!(Codec::from_str("flac"), Ok(Codec::Flac));
+        // sample entry codes are case sensitive
+        assert_matches!(Codec::from_str("FLAC"), Ok(Codec::Other(_)));
+    }
+
+    #[test]
+    fn alac() {
+        assert_matches!(Codec::from_str("alac"), Ok(Codec::Alac));
+        roundtrip("alac");
+        let mut i = Codec::parse_codecs("avc1.4d401e, alac");
+        assert_matches!(i.next(), Some(Ok(Codec::Avc1(_))));
+        assert_matches!(i.next(), Some(Ok(Codec::Alac)));
+    }
+
+    #[cfg(feature = "dolby")]
+    #[test]
+    fn ac3() {
+        assert_matches!(Codec::from_str("ac-3"), Ok(Codec::Ac3));
+        assert_matches!(Codec::from_str("ec-3"), Ok(Codec::Eac3));
+        roundtrip("ac-3");
+        roundtrip("ec-3");
+        let mut i = Codec::parse_codecs("avc1.640028,ec-3");
+        assert_matches!(i.next(), Some(Ok(Codec::Avc1(_))));
+        assert_matches!(i.next(), Some(Ok(Codec::Eac3)));
+        assert_matches!(Codec::from_str("ec-3.1"), Err(_));
+        assert_matches!(Codec::from_str("ec-"), Ok(Codec::Other(_)));
+    }
+
+    #[test]
+    fn dra1() {
+        assert_matches!(Codec::from_str("dra1"), Ok(Codec::Dra1));
+        roundtrip("dra1");
+        assert_matches!(Codec::from_str("dra1.1"), Err(_));
+    }
+
+    #[cfg(feature = "text")]
+    #[test]
+    fn wvtt() {
+        assert_matches!(Codec::from_str("wvtt"), Ok(Codec::Wvtt));
+        roundtrip("wvtt");
+        let kinds: Vec<_> = Codec::parse_codecs("avc1.640028,mp4a.40.2,wvtt")
+            .map(|c| c.unwrap().media_kind())
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![MediaKind::Video, MediaKind::Audio, MediaKind::Text]
+        );
+    }
+
+    #[cfg(feature = "text")]
+    #[test]
+    fn tx3g() {
+        assert_matches!(Codec::from_str("tx3g"), Ok(Codec::Tx3g));
+        roundtrip("tx3g");
+        assert_eq!(
+            Codec::from_str("tx3g").unwrap().media_kind(),
+            MediaKind::Text
+        );
+    }
+
+    #[test]
+    fn unknown_media_kind() {
+        let kind = |s| Codec::from_str(s).unwrap().media_kind();
+        assert_eq!(kind("hvc1.1.6.L93.B0"), MediaKind::Video);
+        assert_eq!(kind("samr.1"), MediaKind::Audio);
+        assert_eq!(kind("mett.1"), MediaKind::Metadata);
+        assert_eq!(kind("badd.41"), MediaKind::Unknown);
+        assert_eq!(kind("bad.41"), MediaKind::Unknown);
+    }
+
+    #[test]
+    fn four_cc() {
+        let four_cc = |s| Codec::from_str(s).unwrap().four_cc();
+        assert_eq!(four_cc("avc1.640028"), Some(FourCC(*b"avc1")));
+        assert_eq!(four_cc("opus"), Some(FourCC(*b"Opus")));
+        assert_eq!(four_cc("dtsx"), Some(FourCC(*b"dtsx")));
+        assert_eq!(four_cc("mp3"), None);
+        assert_eq!(four_cc("badd.41"), Some(FourCC(*b"badd")));
+        assert_eq!(four_cc("bad.41"), None);
+        let code = |s| Codec::from_str(s).unwrap().sample_entry_code();
+        assert_eq!(code("fLaC"), Some(FLAC));
+        assert_eq!(code("mhm1.0x0D"), Some(SampleEntryCode::MHM1));
+        assert_eq!(code("vp09.00.10.08"), Some(SampleEntryCode::VP09));
+        assert_eq!(code("badd.41"), None);
+        assert_eq!(code("mp3"), None);
+    }
+
     #[test]
     fn invalid_unicode_boundary() {
         // byte position 4 is in the middle of a unicode codepoint - if we naively split off the
-        // first 4 bytes this would panic.  We shouldn't panic, we should instead produce an Err.
-        assert!(Codec::from_str("cod👍ec").is_err())
+        // first 4 bytes this would panic.
+        assert_matches!(Codec::from_str("cod👍ec"), Ok(Codec::Other(_)));
+        assert_matches!(Codec::from_str("cod👍ec.1"), Ok(Codec::Other(_)));
+    }
+
+    #[test]
+    fn eq_and_hash() {
+        use std::collections::HashSet;
+        let codecs: HashSet<Codec> = Codec::parse_codecs(
+            "avc1.4d401e,avc1.4D401E,mp4a.40.2,mp4a.40.02,mp4a.6b,mp4a.a5,vorbis,Opus,opus",
+        )
+        .map(Result::unwrap)
+        .collect();
+        assert_eq!(codecs.len(), 6);
+        assert!(codecs.contains(&Codec::AVC1_MAIN_30));
+        assert!(codecs.contains(&Codec::Other("vorbis".into())));
+        let hevc = Codec::from_str("hvc1.1.6.L93.B0").unwrap();
+        assert_eq!(hevc.clone(), hevc);
+        assert_ne!(hevc, Codec::from_str("hev1.1.6.L93.B0").unwrap());
+    }
+
+    #[test]
+    fn ordering() {
+        let sorted = |list: &str| {
+            let mut codecs: Vec<Codec> = Codec::parse_codecs(list).map(Result::unwrap).collect();
+            codecs.sort();
+            codecs
+                .iter()
+                .map(Codec::to_string)
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        assert_eq!(
+            sorted("mp4a.6b,mp4a.40.5,mp4a.67,mp4a.40.2,mp4a.40"),
+            "mp4a.40,mp4a.40.2,mp4a.40.5,mp4a.67,mp4a.6b"
+        );
+        #[cfg(feature = "hevc")]
+        assert_eq!(
+            sorted("hvc1.1.6.L120.B0,hvc1.1.6.L93.B0,avc1.640028"),
+            "avc1.640028,hvc1.1.6.L93.B0,hvc1.1.6.L120.B0"
+        );
+        assert_eq!(
+            sorted("avc1.42E01E,avc1.42C01F,avc1.4D401E"),
+            "avc1.42C01F,avc1.42E01E,avc1.4D401E"
+        );
+        assert_eq!(
+            sorted("uncv.rgb3,uncv,uncv.abcd"),
+            "uncv,uncv.abcd,uncv.rgb3"
+        );
+        let mp4a = Mp4a::Unknown {
+            object_type_indication: ObjectTypeIdentifier::AUDIO_ISO_IEC_14496_3,
+            audio_object_type_indication: Some(2),
+        };
+        assert_ne!(Mp4a::aac_lc().cmp(&mp4a), std::cmp::Ordering::Equal);
+        assert!(AvcLevel::L3_1 > AvcLevel::L3_0);
+    }
+
+    #[test]
+    fn constants() {
+        for (codec, expected) in &[
+            (Codec::AVC1_CONSTRAINED_BASELINE_30, "avc1.42E01E"),
+            (Codec::AVC1_MAIN_30, "avc1.4D401E"),
+            (Codec::AVC1_MAIN_31, "avc1.4D401F"),
+            (Codec::AVC1_HIGH_40, "avc1.640028"),
+            (Codec::AVC1_HIGH_41, "avc1.640029"),
+            (Codec::AAC_LC, "mp4a.40.2"),
+            (Codec::HE_AAC, "mp4a.40.5"),
+            (Codec::HE_AAC_V2, "mp4a.40.29"),
+        ] {
+            assert_eq!(codec.to_string(), *expected);
+        }
+        #[cfg(feature = "vp9")]
+        const VP09: Vp09 = Vp09::new(0, 10, 8);
+        #[cfg(feature = "vp9")]
+        assert_eq!(Codec::Vp09(VP09).to_string(), "vp09.00.10.08");
+    }
+
+    #[test]
+    fn bare_tokens() {
+        for (token, kind) in &[
+            ("vorbis", MediaKind::Audio),
+            ("theora", MediaKind::Video),
+            ("vp8", MediaKind::Video),
+            ("vp9", MediaKind::Video),
+            ("av1", MediaKind::Video),
+            ("speex", MediaKind::Unknown),
+        ] {
+            let codec = Codec::from_str(token).unwrap();
+            assert_matches!(&codec, Codec::Other(v) if v.as_str() == *token);
+            assert_eq!(codec.media_kind(), *kind, "{}", token);
+            roundtrip(token);
+        }
+        assert_matches!(Codec::from_str("opus"), Ok(Codec::Opus));
+        assert_matches!(Codec::from_str(""), Err(_));
+        // known sample entries which require sub-elements are still rejected
+        assert_matches!(Codec::from_str("avc1"), Err(_));
     }
 }
@@ -0,0 +1,165 @@
+//! A list of codec values, as found in a `codecs` parameter.
+
+use crate::{Codec, CodecListError, MediaKind};
+use four_cc::FourCC;
+use std::fmt;
+use std::iter::FromIterator;
+use std::str::FromStr;
+
+/// A comma-separated list of codec values, like `avc1.4D401E,mp4a.40.2`.
+///
+/// Parsing fails if any entry of the list fails to parse, and whitespace after the commas is
+/// accepted.  The `Display` implementation joins the entries with `,` and no spaces.
+///
+/// ```rust
+/// # use rfc6381_codec::{Codec, CodecList};
+/// # use four_cc::FourCC;
+/// # use std::str::FromStr;
+/// let list = CodecList::from_str("avc1.4d401e, mp4a.40.2, wvtt").unwrap();
+/// assert_eq!(list.to_string(), "avc1.4D401E,mp4a.40.2,wvtt");
+/// assert_eq!(list.video().count(), 1);
+/// assert!(list.contains_family(FourCC(*b"mp4a")));
+/// assert!(!list.contains_family(FourCC(*b"hvc1")));
+///
+/// let err = CodecList::from_str("avc1.4d401e, mp4a.4g").unwrap_err();
+/// assert_eq!(err.index(), 1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct CodecList {
+    codecs: Vec<Codec>,
+}
+impl CodecList {
+    pub fn new(codecs: Vec<Codec>) -> CodecList {
+        CodecList { codecs }
+    }
+    pub fn codecs(&self) -> &[Codec] {
+        &self.codecs
+    }
+    pub fn into_codecs(self) -> Vec<Codec> {
+        self.codecs
+    }
+    pub fn len(&self) -> usize {
+        self.codecs.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.codecs.is_empty()
+    }
+    pub fn iter(&self) -> std::slice::Iter<'_, Codec> {
+        self.codecs.iter()
+    }
+    pub fn push(&mut self, codec: Codec) {
+        self.codecs.push(codec)
+    }
+    /// The entries of the list for the given kind of media, as given by
+    /// [`Codec::media_kind()`]
+    pub fn of_kind(&self, kind: MediaKind) -> impl Iterator<Item = &Codec> {
+        self.codecs.iter().filter(move |c| c.media_kind() == kind)
+    }
+    /// The video entries of the list
+    pub fn video(&self) -> impl Iterator<Item = &Codec> {
+        self.of_kind(MediaKind::Video)
+    }
+    /// The audio entries of the list
+    pub fn audio(&self) -> impl Iterator<Item = &Codec> {
+        self.of_kind(MediaKind::Audio)
+    }
+    /// The timed text entries of the list
+    pub fn text(&self) -> impl Iterator<Item = &Codec> {
+        self.of_kind(MediaKind::Text)
+    }
+    /// `true` if any entry of the list has the given leading four-character-code (as given by
+    /// [`Codec::four_cc()`]), whatever its profile, level or other elements.
+    ///
+    /// Note that `hvc1` and `hev1` are distinct, as are the other pairs of sample entry codes
+    /// for the same coding format.
+    pub fn contains_family(&self, four_cc: FourCC) -> bool {
+        self.codecs.iter().any(|c| c.four_cc() == Some(four_cc))
+    }
+}
+impl From<Vec<Codec>> for CodecList {
+    fn from(codecs: Vec<Codec>) -> Self {
+        CodecList { codecs }
+    }
+}
+impl FromIterator<Codec> for CodecList {
+    fn from_iter<I: IntoIterator<Item = Codec>>(iter: I) -> Self {
+        CodecList {
+            codecs: iter.into_iter().collect(),
+        }
+    }
+}
+impl IntoIterator for CodecList {
+    type Item = Codec;
+    type IntoIter = std::vec::IntoIter<Codec>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.codecs.into_iter()
+    }
+}
+impl<'a> IntoIterator for &'a CodecList {
+    type Item = &'a Codec;
+    type IntoIter = std::slice::Iter<'a, Codec>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.codecs.iter()
+    }
+}
+impl FromStr for CodecList {
+    type Err = CodecListError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Codec::parse_codecs_with_positions(value).collect()
+    }
+}
+impl fmt::Display for CodecList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, codec) in self.codecs.iter().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            write!(f, "{}", codec)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::*;
+
+    #[test]
+    fn parse() {
+        let list = CodecList::from_str("avc1.4d401e,mp4a.40.2,Opus").unwrap();
+        assert_eq!(list.len(), 3);
+        assert_matches!(list.video().collect::<Vec<_>>()[..], [Codec::Avc1(_)]);
+        assert_matches!(
+            list.audio().collect::<Vec<_>>()[..],
+            [Codec::Mp4a(_), Codec::Opus]
+        );
+        assert_eq!(list.text().count(), 0);
+        assert!(list.contains_family(FourCC(*b"Opus")));
+        assert_matches!(CodecList::from_str(""), Err(e) if e.index() == 0);
+        assert_matches!(CodecList::from_str("opus,"), Err(e) if e.index() == 1);
+    }
+
+    #[cfg(all(feature = "dolby", feature = "hevc", feature = "text"))]
+    #[test]
+    fn media_kinds() {
+        let list = CodecList::from_str("hvc1.1.6.L93.B0,ec-3,stpp.ttml.im1t").unwrap();
+        assert_matches!(list.video().collect::<Vec<_>>()[..], [Codec::Hevc(_)]);
+        assert_matches!(list.audio().collect::<Vec<_>>()[..], [Codec::Eac3]);
+        assert_matches!(list.text().collect::<Vec<_>>()[..], [Codec::Stpp(_)]);
+        assert!(list.contains_family(FourCC(*b"ec-3")));
+    }
+
+    #[test]
+    fn build() {
+        let mut list: CodecList = vec![Codec::avc1(0x64, 0, 0x28)].into();
+        list.push(Codec::Opus);
+        assert_eq!(list.to_string(), "avc1.640028,Opus");
+        let list: CodecList = list.into_iter().rev().collect();
+        assert_eq!(list.to_string(), "Opus,avc1.640028");
+        assert_eq!(CodecList::default().to_string(), "");
+    }
+}
@@ -0,0 +1,68 @@
+//! Integration with the `mime` crate, enabled by the `mime` cargo feature.
+
+use crate::{ContentType, ContentTypeError};
+use mime::Mime;
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+impl TryFrom<&Mime> for ContentType {
+    type Error = ContentTypeError;
+
+    /// Creates a value from a `mime::Mime`, parsing any `codecs` parameter it has.
+    ///
+    /// Together with the conversion back into `Mime`, this allows a codecs parameter to be
+    /// attached to an existing media type:
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::{Codec, ContentType};
+    /// # use std::convert::TryFrom;
+    /// let mime: mime::Mime = r#"video/mp4; codecs="avc1.4D401E""#.parse().unwrap();
+    /// let content_type = ContentType::try_from(&mime).unwrap();
+    /// assert_eq!(content_type.codecs().len(), 1);
+    ///
+    /// let mime: mime::Mime = "video/mp4".parse().unwrap();
+    /// let content_type = ContentType::try_from(&mime)
+    ///     .unwrap()
+    ///     .with_codecs(vec![Codec::avc1(0x4d, 0x40, 0x1e)]);
+    /// let mime = mime::Mime::try_from(&content_type).unwrap();
+    /// assert_eq!(mime.get_param("codecs").unwrap(), "avc1.4D401E");
+    /// ```
+    fn try_from(mime: &Mime) -> Result<Self, Self::Error> {
+        ContentType::from_str(mime.as_ref())
+    }
+}
+
+impl TryFrom<&ContentType> for Mime {
+    type Error = mime::FromStrError;
+
+    /// Creates a `mime::Mime` from the string form of the given value, failing only if the
+    /// value was created with an invalid media type.
+    fn try_from(content_type: &ContentType) -> Result<Self, Self::Error> {
+        Mime::from_str(&content_type.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Codec;
+    use assert_matches::*;
+
+    #[test]
+    fn convert() {
+        let mime = Mime::from_str(r#"audio/webm;codecs="opus";rate=48000"#).unwrap();
+        let content_type = ContentType::try_from(&mime).unwrap();
+        assert_matches!(content_type.codecs(), [Codec::Opus]);
+        assert_eq!(content_type.parameter("rate"), Some("48000"));
+        let mime = Mime::try_from(&content_type).unwrap();
+        assert_eq!(mime.as_ref(), r#"audio/webm; rate=48000; codecs="Opus""#);
+        assert_eq!(mime.get_param("codecs").unwrap(), "Opus");
+
+        let mime = Mime::from_str(r#"video/mp4; codecs="avc1.4D""#).unwrap();
+        assert_matches!(
+            ContentType::try_from(&mime),
+            Err(ContentTypeError::Codec(_))
+        );
+        assert!(Mime::try_from(&ContentType::new("video")).is_err());
+    }
+}
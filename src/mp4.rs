@@ -0,0 +1,176 @@
+//! Integration with the `mp4` crate, enabled by the `mp4` cargo feature.
+
+use crate::{Avc1, Codec, CodecError, Mp4a};
+#[cfg(feature = "vp9")]
+use crate::{Vp09, Vp9ChromaSubsampling};
+use mp4::Mp4Track;
+use mp4ra_rust::ObjectTypeIdentifier;
+use std::convert::TryFrom;
+
+impl TryFrom<&Mp4Track> for Codec {
+    type Error = CodecError;
+
+    /// Creates a value from the sample entry of a track that has been read by the `mp4`
+    /// crate.
+    ///
+    /// The `mp4` crate does not retain the fields of the `hvcC` box, so HEVC tracks give
+    /// [`CodecError::UnsupportedSampleEntry`].
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::Codec;
+    /// # use std::convert::TryFrom;
+    /// # use std::io::Cursor;
+    /// use mp4::{AacConfig, Mp4Config, Mp4Reader, Mp4Writer, TrackConfig};
+    ///
+    /// let config = Mp4Config {
+    ///     major_brand: "isom".parse().unwrap(),
+    ///     minor_version: 0,
+    ///     compatible_brands: vec![],
+    ///     timescale: 1000,
+    /// };
+    /// let mut writer = Mp4Writer::write_start(Cursor::new(vec![]), &config).unwrap();
+    /// writer.add_track(&TrackConfig::from(AacConfig::default())).unwrap();
+    /// writer.write_end().unwrap();
+    /// let data = writer.into_writer().into_inner();
+    ///
+    /// let size = data.len() as u64;
+    /// let reader = Mp4Reader::read_header(Cursor::new(data), size).unwrap();
+    /// let track = reader.tracks().values().next().unwrap();
+    /// assert_eq!(Codec::try_from(track).unwrap().to_string(), "mp4a.40.2");
+    /// ```
+    fn try_from(track: &Mp4Track) -> Result<Self, Self::Error> {
+        let stsd = &track.trak.mdia.minf.stbl.stsd;
+        if let Some(ref avc1) = stsd.avc1 {
+            Ok(Codec::Avc1(Avc1 {
+                profile: avc1.avcc.avc_profile_indication,
+                constraints: avc1.avcc.profile_compatibility,
+                level: avc1.avcc.avc_level_indication,
+            }))
+        } else if stsd.vp09.is_some() {
+            vp09_codec(track)
+        } else if let Some(ref mp4a) = stsd.mp4a {
+            let esds = mp4a
+                .esds
+                .as_ref()
+                .ok_or_else(|| CodecError::UnsupportedSampleEntry("mp4a".into()))?;
+            let config = &esds.es_desc.dec_config;
+            let oti = ObjectTypeIdentifier::from(config.object_type_indication);
+            // the mp4 crate gives a zero audioObjectType where there was no decoder specific
+            // info
+            let aoti = match config.dec_specific.profile {
+                0 => None,
+                aot if oti == ObjectTypeIdentifier::AUDIO_ISO_IEC_14496_3 => Some(aot),
+                _ => None,
+            };
+            Ok(Codec::Mp4a(Mp4a::from_indications(oti, aoti)?))
+        } else if stsd.tx3g.is_some() {
+            tx3g_codec()
+        } else if stsd.hev1.is_some() {
+            Err(CodecError::UnsupportedSampleEntry("hev1".into()))
+        } else {
+            Err(CodecError::UnsupportedSampleEntry("stsd".into()))
+        }
+    }
+}
+
+#[cfg(feature = "vp9")]
+fn vp09_codec(track: &Mp4Track) -> Result<Codec, CodecError> {
+    let vp09 = track.trak.mdia.minf.stbl.stsd.vp09.as_ref();
+    let vpcc = &vp09
+        .ok_or_else(|| CodecError::UnsupportedSampleEntry("vp09".into()))?
+        .vpcc;
+    let chroma_subsampling =
+        Vp9ChromaSubsampling::from_value(vpcc.chroma_subsampling).ok_or_else(|| {
+            CodecError::InvalidComponent(format_args!("{:02}", vpcc.chroma_subsampling).into())
+        })?;
+    Ok(Codec::Vp09(Vp09 {
+        profile: vpcc.profile,
+        level: vpcc.level,
+        bit_depth: vpcc.bit_depth,
+        chroma_subsampling: Some(chroma_subsampling),
+        color_primaries: Some(vpcc.color_primaries.into()),
+        transfer_characteristics: Some(vpcc.transfer_characteristics.into()),
+        matrix_coefficients: Some(vpcc.matrix_coefficients.into()),
+        video_full_range_flag: Some(vpcc.video_full_range_flag),
+    }))
+}
+#[cfg(not(feature = "vp9"))]
+fn vp09_codec(_track: &Mp4Track) -> Result<Codec, CodecError> {
+    Err(CodecError::UnsupportedSampleEntry("vp09".into()))
+}
+
+#[cfg(feature = "text")]
+fn tx3g_codec() -> Result<Codec, CodecError> {
+    Ok(Codec::Tx3g)
+}
+#[cfg(not(feature = "text"))]
+fn tx3g_codec() -> Result<Codec, CodecError> {
+    Err(CodecError::UnsupportedSampleEntry("tx3g".into()))
+}
+
+/// Writes a file with a single track of the given configuration, as test data for these
+/// conversions and for those of the `mp4parse` support
+#[cfg(test)]
+pub(crate) fn single_track_file(media_conf: mp4::MediaConfig) -> Vec<u8> {
+    use mp4::{Mp4Config, Mp4Writer, TrackConfig};
+    let config = Mp4Config {
+        major_brand: "isom".parse().unwrap(),
+        minor_version: 0,
+        compatible_brands: vec![],
+        timescale: 1000,
+    };
+    let mut writer = Mp4Writer::write_start(std::io::Cursor::new(vec![]), &config).unwrap();
+    writer.add_track(&TrackConfig::from(media_conf)).unwrap();
+    writer.write_end().unwrap();
+    writer.into_writer().into_inner()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::*;
+    #[cfg(feature = "vp9")]
+    use mp4::Vp9Config;
+    use mp4::{AvcConfig, HevcConfig, MediaConfig, Mp4Reader};
+    use std::io::Cursor;
+
+    fn track_codec(media_conf: MediaConfig) -> Result<Codec, CodecError> {
+        let data = single_track_file(media_conf);
+        let size = data.len() as u64;
+        let reader = Mp4Reader::read_header(Cursor::new(data), size).unwrap();
+        let track = reader.tracks().values().next().unwrap();
+        Codec::try_from(track)
+    }
+
+    #[test]
+    fn tracks() {
+        let avc = AvcConfig {
+            width: 320,
+            height: 240,
+            seq_param_set: vec![0x67, 0x4d, 0x40, 0x1f, 0x9a],
+            pic_param_set: vec![0x68, 0xeb, 0xe3, 0xcb],
+        };
+        assert_eq!(
+            track_codec(MediaConfig::AvcConfig(avc))
+                .unwrap()
+                .to_string(),
+            "avc1.4D401F"
+        );
+        #[cfg(feature = "vp9")]
+        let vp9 = Vp9Config {
+            width: 320,
+            height: 240,
+        };
+        #[cfg(feature = "vp9")]
+        assert_eq!(
+            track_codec(MediaConfig::Vp9Config(vp9))
+                .unwrap()
+                .to_string(),
+            "vp09.00.31.08.00.00.00.00.00"
+        );
+        assert_matches!(
+            track_codec(MediaConfig::HevcConfig(HevcConfig::default())),
+            Err(CodecError::UnsupportedSampleEntry(_))
+        );
+    }
+}
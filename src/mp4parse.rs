@@ -0,0 +1,109 @@
+//! Integration with the `mp4parse` crate, enabled by the `mp4parse` cargo feature.
+
+#[cfg(feature = "av1")]
+use crate::Av01;
+use crate::{Avc1, Codec, CodecError, Mp4a};
+use mp4parse::{
+    AudioCodecSpecific, AudioSampleEntry, SampleEntry, VideoCodecSpecific, VideoSampleEntry,
+};
+use std::convert::TryFrom;
+
+impl TryFrom<&VideoSampleEntry> for Codec {
+    type Error = CodecError;
+
+    /// Creates a value from the codec configuration of the sample entry.
+    ///
+    /// The `mp4parse` crate does not expose the profile and level of a `vpcC` box, so VP9
+    /// sample entries give [`CodecError::UnsupportedSampleEntry`].
+    fn try_from(entry: &VideoSampleEntry) -> Result<Self, Self::Error> {
+        match entry.codec_specific {
+            VideoCodecSpecific::AVCConfig(ref avcc) => Ok(Codec::Avc1(Avc1::from_avcc(avcc)?)),
+            #[cfg(feature = "av1")]
+            VideoCodecSpecific::AV1Config(ref av1c) => {
+                Ok(Codec::Av01(Av01::from_av1c(&av1c.raw_config)?))
+            }
+            _ => Err(CodecError::UnsupportedSampleEntry(
+                format_args!("{:?}", entry.codec_type).into(),
+            )),
+        }
+    }
+}
+
+impl TryFrom<&AudioSampleEntry> for Codec {
+    type Error = CodecError;
+
+    /// Creates a value from the codec configuration of the sample entry.
+    fn try_from(entry: &AudioSampleEntry) -> Result<Self, Self::Error> {
+        match entry.codec_specific {
+            AudioCodecSpecific::ES_Descriptor(ref es) => {
+                // mp4parse keeps the ES_Descriptor without the version and flags of the esds box
+                let mut esds = vec![0; 4];
+                esds.extend_from_slice(&es.codec_esds);
+                Ok(Codec::Mp4a(Mp4a::from_esds(&esds)?))
+            }
+            AudioCodecSpecific::FLACSpecificBox(_) => Ok(Codec::Flac),
+            AudioCodecSpecific::OpusSpecificBox(_) => Ok(Codec::Opus),
+            AudioCodecSpecific::ALACSpecificBox(_) => Ok(Codec::Alac),
+            AudioCodecSpecific::MP3 => Ok(Codec::Mp3),
+            // covers further variants present when mp4parse features are enabled
+            #[allow(unreachable_patterns)]
+            _ => Err(CodecError::UnsupportedSampleEntry(
+                format_args!("{:?}", entry.codec_type).into(),
+            )),
+        }
+    }
+}
+
+impl TryFrom<&SampleEntry> for Codec {
+    type Error = CodecError;
+
+    /// Creates a value from the codec configuration of the given audio or video sample entry.
+    fn try_from(entry: &SampleEntry) -> Result<Self, Self::Error> {
+        match entry {
+            SampleEntry::Video(video) => Codec::try_from(video),
+            SampleEntry::Audio(audio) => Codec::try_from(audio),
+            SampleEntry::Unknown => Err(CodecError::UnsupportedSampleEntry(
+                "unknown sample entry".into(),
+            )),
+        }
+    }
+}
+
+// the test data is produced by the writer of the `mp4` crate
+#[cfg(all(test, feature = "mp4"))]
+mod tests {
+    use super::*;
+    use crate::mp4::single_track_file;
+    use mp4::{AacConfig, AvcConfig, MediaConfig};
+    use std::io::Cursor;
+
+    fn track_codec(media_conf: MediaConfig) -> Result<Codec, CodecError> {
+        let data = single_track_file(media_conf);
+        let context = mp4parse::read_mp4(&mut Cursor::new(data)).unwrap();
+        let stsd = context.tracks[0].stsd.as_ref().unwrap();
+        Codec::try_from(&stsd.descriptions[0])
+    }
+
+    #[test]
+    fn sample_entries() {
+        let aac = AacConfig::default();
+        assert_eq!(
+            track_codec(MediaConfig::AacConfig(aac))
+                .unwrap()
+                .to_string(),
+            "mp4a.40.2"
+        );
+        let avc = AvcConfig {
+            width: 320,
+            height: 240,
+            seq_param_set: vec![0x67, 0x64, 0x00, 0x0a, 0xac, 0x72, 0x84, 0x44, 0x26, 0x84],
+            pic_param_set: vec![0x68, 0xeb, 0xe3, 0xcb],
+        };
+        assert_eq!(
+            track_codec(MediaConfig::AvcConfig(avc))
+                .unwrap()
+                .to_string(),
+            "avc1.64000A"
+        );
+    }
+}
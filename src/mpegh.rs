@@ -0,0 +1,265 @@
+//! Support for MPEG-H 3D Audio codec strings.
+
+use crate::{parse_hex_element, CodecError};
+use mp4ra_rust::SampleEntryCode;
+use std::fmt;
+
+/// The MPEG-H 3D Audio sample entry codes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MpeghSampleEntry {
+    /// Raw MPEG-H audio, with configuration in the sample entry
+    Mha1,
+    /// Raw MPEG-H audio, for multi-stream use
+    Mha2,
+    /// MPEG-H Audio Stream (MHAS) packets, with configuration in-band
+    Mhm1,
+    /// MPEG-H Audio Stream (MHAS) packets, for multi-stream use
+    Mhm2,
+}
+impl MpeghSampleEntry {
+    pub(crate) fn from_sample_entry(sample_entry: SampleEntryCode) -> Option<MpeghSampleEntry> {
+        match sample_entry {
+            SampleEntryCode::MHA1 => Some(MpeghSampleEntry::Mha1),
+            SampleEntryCode::MHA2 => Some(MpeghSampleEntry::Mha2),
+            SampleEntryCode::MHM1 => Some(MpeghSampleEntry::Mhm1),
+            SampleEntryCode::MHM2 => Some(MpeghSampleEntry::Mhm2),
+            _ => None,
+        }
+    }
+
+    pub fn sample_entry_code(&self) -> SampleEntryCode {
+        match self {
+            MpeghSampleEntry::Mha1 => SampleEntryCode::MHA1,
+            MpeghSampleEntry::Mha2 => SampleEntryCode::MHA2,
+            MpeghSampleEntry::Mhm1 => SampleEntryCode::MHM1,
+            MpeghSampleEntry::Mhm2 => SampleEntryCode::MHM2,
+        }
+    }
+}
+
+/// Interpretation of the `mpegh3daProfileLevelIndication` value, per ISO/IEC 23008-3.
+///
+/// Each variant other than `Reserved` gives the level number (`1` to `5`) within the profile.
+/// Values holding a level outside that range, or a `Reserved` value which the specification
+/// does assign, are not valid; [`Mpegh::new()`] rejects them, and converting them to `u8` gives
+/// a value which reads back as a different variant.
+///
+/// ```rust
+/// # use rfc6381_codec::MpeghProfileLevel;
+/// assert_eq!(MpeghProfileLevel::from(0x0D), MpeghProfileLevel::LowComplexity(3));
+/// assert_eq!(u8::from(MpeghProfileLevel::Baseline(1)), 0x10);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MpeghProfileLevel {
+    /// Main profile, at the given level (indications `0x01` to `0x05`)
+    Main(u8),
+    /// High profile, at the given level (indications `0x06` to `0x0A`)
+    High(u8),
+    /// Low Complexity profile, at the given level (indications `0x0B` to `0x0F`)
+    LowComplexity(u8),
+    /// Baseline profile, at the given level (indications `0x10` to `0x14`)
+    Baseline(u8),
+    /// A value which the specification does not assign to any profile and level
+    Reserved(u8),
+}
+impl From<u8> for MpeghProfileLevel {
+    fn from(value: u8) -> Self {
+        match value {
+            0x01..=0x05 => MpeghProfileLevel::Main(value),
+            0x06..=0x0A => MpeghProfileLevel::High(value - 0x05),
+            0x0B..=0x0F => MpeghProfileLevel::LowComplexity(value - 0x0A),
+            0x10..=0x14 => MpeghProfileLevel::Baseline(value - 0x0F),
+            _ => MpeghProfileLevel::Reserved(value),
+        }
+    }
+}
+impl MpeghProfileLevel {
+    const fn value(self) -> u8 {
+        match self {
+            MpeghProfileLevel::Main(level) => level,
+            MpeghProfileLevel::High(level) => level.wrapping_add(0x05),
+            MpeghProfileLevel::LowComplexity(level) => level.wrapping_add(0x0A),
+            MpeghProfileLevel::Baseline(level) => level.wrapping_add(0x0F),
+            MpeghProfileLevel::Reserved(value) => value,
+        }
+    }
+
+    /// Whether the value survives conversion to `u8` and back, i.e. the level is in the range
+    /// `1` to `5`, or a `Reserved` value is one the specification doesn't assign
+    pub const fn is_valid(&self) -> bool {
+        match *self {
+            MpeghProfileLevel::Main(level)
+            | MpeghProfileLevel::High(level)
+            | MpeghProfileLevel::LowComplexity(level)
+            | MpeghProfileLevel::Baseline(level) => level >= 1 && level <= 5,
+            MpeghProfileLevel::Reserved(value) => value == 0 || value > 0x14,
+        }
+    }
+}
+impl From<MpeghProfileLevel> for u8 {
+    fn from(value: MpeghProfileLevel) -> Self {
+        value.value()
+    }
+}
+
+/// An MPEG-H 3D Audio codec parameter, like `mhm1.0x0D`.
+///
+/// The profile-level indication is given as a hexadecimal value with a `0x` prefix.  It may be
+/// omitted, in which case `profile_level_indication()` returns `None`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Mpegh {
+    pub(crate) sample_entry: MpeghSampleEntry,
+    pub(crate) profile_level_indication: Option<u8>,
+}
+impl Mpegh {
+    /// Creates a value with the given profile and level, if any.
+    ///
+    /// Returns `None` if the profile and level is not valid (see
+    /// [`MpeghProfileLevel::is_valid()`]).
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::{Codec, Mpegh, MpeghProfileLevel, MpeghSampleEntry};
+    /// let lc3 = Some(MpeghProfileLevel::LowComplexity(3));
+    /// let mpegh = Mpegh::new(MpeghSampleEntry::Mhm1, lc3).unwrap();
+    /// assert_eq!(Codec::Mpegh(mpegh).to_string(), "mhm1.0x0D");
+    /// assert!(Mpegh::new(MpeghSampleEntry::Mhm1, Some(MpeghProfileLevel::Main(9))).is_none());
+    /// ```
+    pub const fn new(
+        sample_entry: MpeghSampleEntry,
+        profile_level: Option<MpeghProfileLevel>,
+    ) -> Option<Mpegh> {
+        let profile_level_indication = match profile_level {
+            Some(profile_level) if profile_level.is_valid() => Some(profile_level.value()),
+            Some(_) => return None,
+            None => None,
+        };
+        Some(Mpegh {
+            sample_entry,
+            profile_level_indication,
+        })
+    }
+    pub fn sample_entry(&self) -> MpeghSampleEntry {
+        self.sample_entry
+    }
+    /// The raw `mpegh3daProfileLevelIndication` value
+    pub fn profile_level_indication(&self) -> Option<u8> {
+        self.profile_level_indication
+    }
+    pub fn profile_level(&self) -> Option<MpeghProfileLevel> {
+        self.profile_level_indication.map(MpeghProfileLevel::from)
+    }
+
+    /// Parses the sub-elements following the sample entry code, if any.
+    pub(crate) fn parse(
+        sample_entry: MpeghSampleEntry,
+        rest: Option<&str>,
+    ) -> Result<Mpegh, CodecError> {
+        let profile_level_indication = rest
+            .map(|value| {
+                let hex = value
+                    .strip_prefix("0x")
+                    .ok_or_else(|| CodecError::InvalidComponent(value.into()))?;
+                parse_hex_element(hex)
+            })
+            .transpose()?;
+        Ok(Mpegh {
+            sample_entry,
+            profile_level_indication,
+        })
+    }
+}
+impl fmt::Display for Mpegh {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.sample_entry.sample_entry_code().0)?;
+        if let Some(pli) = self.profile_level_indication {
+            write!(f, ".0x{:02X}", pli)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Codec;
+    use assert_matches::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn mpegh() {
+        assert_matches!(
+            Codec::from_str("mhm1.0x0D"),
+            Ok(Codec::Mpegh(Mpegh {
+                sample_entry: MpeghSampleEntry::Mhm1,
+                profile_level_indication: Some(0x0d)
+            }))
+        );
+        assert_matches!(
+            Codec::from_str("mha2"),
+            Ok(Codec::Mpegh(Mpegh {
+                sample_entry: MpeghSampleEntry::Mha2,
+                profile_level_indication: None
+            }))
+        );
+        for s in &["mhm1.0x0D", "mhm2.0x10", "mha1.0x0B", "mha1"] {
+            assert_eq!(*s, Codec::from_str(s).unwrap().to_string());
+        }
+    }
+
+    #[test]
+    fn bad_mpegh() {
+        assert_matches!(Codec::from_str("mhm1.0D"), Err(_));
+        assert_matches!(Codec::from_str("mhm1.0x0"), Err(_));
+        assert_matches!(Codec::from_str("mhm1.0x0G"), Err(_));
+        assert_matches!(Codec::from_str("mhm1."), Err(_));
+    }
+
+    #[test]
+    fn profile_level() {
+        for v in 0..=255u8 {
+            assert_eq!(v, u8::from(MpeghProfileLevel::from(v)));
+        }
+        assert_eq!(
+            MpeghProfileLevel::from(0x00),
+            MpeghProfileLevel::Reserved(0)
+        );
+        assert_eq!(MpeghProfileLevel::from(0x01), MpeghProfileLevel::Main(1));
+        assert_eq!(MpeghProfileLevel::from(0x0A), MpeghProfileLevel::High(5));
+        assert_eq!(
+            MpeghProfileLevel::from(0x15),
+            MpeghProfileLevel::Reserved(0x15)
+        );
+    }
+
+    #[test]
+    fn profile_level_round_trip() {
+        for level in 1..=5 {
+            for profile_level in &[
+                MpeghProfileLevel::Main(level),
+                MpeghProfileLevel::High(level),
+                MpeghProfileLevel::LowComplexity(level),
+                MpeghProfileLevel::Baseline(level),
+            ] {
+                assert!(profile_level.is_valid());
+                assert_eq!(
+                    *profile_level,
+                    MpeghProfileLevel::from(u8::from(*profile_level))
+                );
+            }
+        }
+        for profile_level in &[
+            MpeghProfileLevel::Main(9),
+            MpeghProfileLevel::High(0),
+            MpeghProfileLevel::Baseline(6),
+            MpeghProfileLevel::Reserved(0x05),
+        ] {
+            assert!(!profile_level.is_valid());
+            assert_ne!(
+                *profile_level,
+                MpeghProfileLevel::from(u8::from(*profile_level))
+            );
+            assert!(Mpegh::new(MpeghSampleEntry::Mhm1, Some(*profile_level)).is_none());
+        }
+        assert!(Mpegh::new(MpeghSampleEntry::Mha1, None).is_some());
+    }
+}
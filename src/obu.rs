@@ -0,0 +1,422 @@
+//! Creating `av01` codec values from AV1 sequence header OBUs.
+
+use crate::{
+    Av01, Av1ChromaSamplePosition, Av1ChromaSubsampling, Av1ColorConfig, Av1Tier, CodecError,
+};
+use crate::{ColorPrimaries, MatrixCoefficients, TransferCharacteristics};
+use std::convert::TryFrom;
+
+const OBU_SEQUENCE_HEADER: u8 = 1;
+
+impl Av01 {
+    /// Creates a value from the given AV1 sequence header OBU, for pipelines which have the
+    /// OBU stream but no `av1C` box.
+    ///
+    /// The `obu` data must start with the OBU header, and the length of the OBU is taken from
+    /// its `obu_size` field if present.  The level and tier are those of the first operating
+    /// point, and since the sequence header gives them, all the optional colour elements are
+    /// populated (unless they would only repeat the default values).
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::{Av01, Codec};
+    /// let obu = [
+    ///     0x0a, 0x0b, 0x00, 0x00, 0x00, 0x42, 0xa7, 0xbf, 0xe4, 0x60, 0x0d, 0x00, 0x40,
+    /// ];
+    /// let av01 = Av01::from_sequence_header_obu(&obu).unwrap();
+    /// assert_eq!(Codec::Av01(av01).to_string(), "av01.0.08M.08.0.110.02.02.02.0");
+    /// ```
+    pub fn from_sequence_header_obu(obu: &[u8]) -> Result<Av01, CodecError> {
+        let mut r = BitReader::new(obu);
+        r.skip(1)?; // obu_forbidden_bit
+        let obu_type = r.bits(4)? as u8;
+        if obu_type != OBU_SEQUENCE_HEADER {
+            return Err(CodecError::UnexpectedObuType(obu_type));
+        }
+        let extension_flag = r.flag()?;
+        let has_size_field = r.flag()?;
+        r.skip(1)?; // obu_reserved_1bit
+        if extension_flag {
+            r.skip(8)?;
+        }
+        if has_size_field {
+            let size = r.leb128()?;
+            let end = usize::try_from(size)
+                .ok()
+                .and_then(|size| r.byte_pos().checked_add(size))
+                .ok_or_else(|| CodecError::InvalidComponent(format_args!("{}", size).into()))?;
+            if end > obu.len() {
+                return Err(CodecError::ConfigurationRecordTooShort {
+                    expected: end,
+                    got: obu.len(),
+                });
+            }
+            r.data = &obu[..end];
+        }
+        sequence_header(&mut r)
+    }
+}
+
+fn sequence_header(r: &mut BitReader<'_>) -> Result<Av01, CodecError> {
+    let profile = r.bits(3)? as u8;
+    r.skip(1)?; // still_picture
+    let reduced_still_picture_header = r.flag()?;
+    let level;
+    let mut tier = Av1Tier::Main;
+    if reduced_still_picture_header {
+        level = r.bits(5)? as u8;
+    } else {
+        let mut decoder_model_info_present = false;
+        let mut buffer_delay_length = 0;
+        if r.flag()? {
+            // timing_info()
+            r.skip(64)?; // num_units_in_display_tick, time_scale
+            if r.flag()? {
+                r.uvlc()?; // num_ticks_per_picture_minus_1
+            }
+            decoder_model_info_present = r.flag()?;
+            if decoder_model_info_present {
+                // decoder_model_info()
+                buffer_delay_length = r.bits(5)? + 1;
+                r.skip(32 + 5 + 5)?;
+            }
+        }
+        let initial_display_delay_present = r.flag()?;
+        let operating_points_cnt = r.bits(5)? + 1;
+        let mut first = None;
+        for _ in 0..operating_points_cnt {
+            r.skip(12)?; // operating_point_idc
+            let seq_level_idx = r.bits(5)? as u8;
+            let seq_tier = seq_level_idx > 7 && r.flag()?;
+            if decoder_model_info_present && r.flag()? {
+                // operating_parameters_info()
+                r.skip(buffer_delay_length * 2 + 1)?;
+            }
+            if initial_display_delay_present && r.flag()? {
+                r.skip(4)?;
+            }
+            first.get_or_insert((seq_level_idx, seq_tier));
+        }
+        let (seq_level_idx, seq_tier) = first.expect("at least one operating point");
+        level = seq_level_idx;
+        if seq_tier {
+            tier = Av1Tier::High;
+        }
+    }
+    let frame_width_bits = r.bits(4)? + 1;
+    let frame_height_bits = r.bits(4)? + 1;
+    r.skip(frame_width_bits + frame_height_bits)?;
+    if !reduced_still_picture_header && r.flag()? {
+        // delta_frame_id_length_minus_2, additional_frame_id_length_minus_1
+        r.skip(4 + 3)?;
+    }
+    // use_128x128_superblock, enable_filter_intra, enable_intra_edge_filter
+    r.skip(3)?;
+    if !reduced_still_picture_header {
+        // enable_interintra_compound, enable_masked_compound, enable_warped_motion,
+        // enable_dual_filter
+        r.skip(4)?;
+        let enable_order_hint = r.flag()?;
+        if enable_order_hint {
+            r.skip(2)?; // enable_jnt_comp, enable_ref_frame_mvs
+        }
+        let seq_force_screen_content_tools = if r.flag()? { 2 } else { r.bits(1)? };
+        if seq_force_screen_content_tools > 0 && !r.flag()? {
+            r.skip(1)?; // seq_force_integer_mv
+        }
+        if enable_order_hint {
+            r.skip(3)?; // order_hint_bits_minus_1
+        }
+    }
+    // enable_superres, enable_cdef, enable_restoration
+    r.skip(3)?;
+    let (bit_depth, color) = color_config(r, profile)?;
+    Ok(Av01 {
+        profile,
+        level,
+        tier,
+        bit_depth,
+        color: if color == Av1ColorConfig::default() {
+            None
+        } else {
+            Some(color)
+        },
+    })
+}
+
+fn color_config(r: &mut BitReader<'_>, profile: u8) -> Result<(u8, Av1ColorConfig), CodecError> {
+    let high_bitdepth = r.flag()?;
+    let bit_depth = match (high_bitdepth, profile == 2 && high_bitdepth && r.flag()?) {
+        (false, _) => 8,
+        (true, false) => 10,
+        (true, true) => 12,
+    };
+    let mono_chrome = profile != 1 && r.flag()?;
+    let (color_primaries, transfer_characteristics, matrix_coefficients) = if r.flag()? {
+        (r.bits(8)? as u8, r.bits(8)? as u8, r.bits(8)? as u8)
+    } else {
+        (2, 2, 2)
+    };
+    let mut color = Av1ColorConfig {
+        chroma_subsampling: Av1ChromaSubsampling::Yuv420,
+        chroma_sample_position: Av1ChromaSamplePosition::Unknown,
+        color_primaries: ColorPrimaries::from(color_primaries),
+        transfer_characteristics: TransferCharacteristics::from(transfer_characteristics),
+        matrix_coefficients: MatrixCoefficients::from(matrix_coefficients),
+        video_full_range_flag: false,
+    };
+    if mono_chrome {
+        color.chroma_subsampling = Av1ChromaSubsampling::Monochrome;
+        color.video_full_range_flag = r.flag()?;
+    } else if (
+        color_primaries,
+        transfer_characteristics,
+        matrix_coefficients,
+    ) == (1, 13, 0)
+    {
+        // sRGB, which is always full range 4:4:4
+        color.chroma_subsampling = Av1ChromaSubsampling::Yuv444;
+        color.video_full_range_flag = true;
+    } else {
+        color.video_full_range_flag = r.flag()?;
+        let (subsampling_x, subsampling_y) = match profile {
+            0 => (true, true),
+            1 => (false, false),
+            _ if bit_depth == 12 => {
+                let x = r.flag()?;
+                (x, x && r.flag()?)
+            }
+            _ => (true, false),
+        };
+        color.chroma_subsampling = match (subsampling_x, subsampling_y) {
+            (true, true) => Av1ChromaSubsampling::Yuv420,
+            (true, false) => Av1ChromaSubsampling::Yuv422,
+            _ => Av1ChromaSubsampling::Yuv444,
+        };
+        if subsampling_x && subsampling_y {
+            color.chroma_sample_position = match r.bits(2)? {
+                0 => Av1ChromaSamplePosition::Unknown,
+                1 => Av1ChromaSamplePosition::Vertical,
+                2 => Av1ChromaSamplePosition::Colocated,
+                _ => Av1ChromaSamplePosition::Reserved,
+            };
+        }
+    }
+    Ok((bit_depth, color))
+}
+
+/// Reads the fixed-width and variable-length fields used by the AV1 bitstream syntax
+struct BitReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+impl<'a> BitReader<'a> {
+    fn new(data: &'a [u8]) -> BitReader<'a> {
+        BitReader { data, pos: 0 }
+    }
+
+    fn byte_pos(&self) -> usize {
+        self.pos.div_ceil(8)
+    }
+
+    fn too_short(&self, bits: u32) -> CodecError {
+        CodecError::ConfigurationRecordTooShort {
+            expected: (self.pos + bits as usize).div_ceil(8),
+            got: self.data.len(),
+        }
+    }
+
+    fn skip(&mut self, bits: u32) -> Result<(), CodecError> {
+        if self.pos + bits as usize > self.data.len() * 8 {
+            return Err(self.too_short(bits));
+        }
+        self.pos += bits as usize;
+        Ok(())
+    }
+
+    /// Reads an `f(n)` value of up to 32 bits
+    fn bits(&mut self, bits: u32) -> Result<u32, CodecError> {
+        if self.pos + bits as usize > self.data.len() * 8 {
+            return Err(self.too_short(bits));
+        }
+        let mut value = 0u32;
+        for _ in 0..bits {
+            let byte = self.data[self.pos / 8];
+            let bit = byte >> (7 - self.pos % 8) & 1;
+            value = value << 1 | u32::from(bit);
+            self.pos += 1;
+        }
+        Ok(value)
+    }
+
+    fn flag(&mut self) -> Result<bool, CodecError> {
+        Ok(self.bits(1)? == 1)
+    }
+
+    fn uvlc(&mut self) -> Result<u32, CodecError> {
+        let mut leading_zeros = 0;
+        while !self.flag()? {
+            leading_zeros += 1;
+        }
+        if leading_zeros >= 32 {
+            return Ok(u32::MAX);
+        }
+        Ok(self.bits(leading_zeros)? + ((1u64 << leading_zeros) - 1) as u32)
+    }
+
+    /// Reads a byte-aligned `leb128()` value, which the AV1 specification limits to
+    /// `2^32 - 1` even when (zero-padded) it takes all eight bytes
+    fn leb128(&mut self) -> Result<u32, CodecError> {
+        let mut value = 0u64;
+        for i in 0..8 {
+            let byte = self.bits(8)?;
+            value |= u64::from(byte & 0x7f) << (i * 7);
+            if byte & 0x80 == 0 {
+                break;
+            }
+        }
+        u32::try_from(value)
+            .map_err(|_| CodecError::InvalidComponent(format_args!("{}", value).into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Codec;
+    use assert_matches::*;
+
+    /// Packs the given strings of `0` and `1` characters into bytes, padding the final byte
+    /// with zeros
+    fn bits(fields: &[&str]) -> Vec<u8> {
+        let bits: String = fields.concat();
+        bits.as_bytes()
+            .chunks(8)
+            .map(|chunk| {
+                chunk
+                    .iter()
+                    .enumerate()
+                    .fold(0, |acc, (i, b)| acc | u8::from(*b == b'1') << (7 - i))
+            })
+            .collect()
+    }
+
+    fn obu(payload: &[u8]) -> Vec<u8> {
+        // a sequence header OBU with obu_has_size_field set
+        let mut obu = vec![0b0000_1010, payload.len() as u8];
+        obu.extend_from_slice(payload);
+        obu
+    }
+
+    fn to_string(obu: &[u8]) -> String {
+        Codec::Av01(Av01::from_sequence_header_obu(obu).unwrap()).to_string()
+    }
+
+    #[test]
+    fn sequence_header() {
+        let header = bits(&[
+            "010",                      // seq_profile
+            "0",                        // still_picture
+            "0",                        // reduced_still_picture_header
+            "1",                        // timing_info_present_flag
+            &"0".repeat(64),            // num_units_in_display_tick, time_scale
+            "1",                        // equal_picture_interval
+            "010",                      // num_ticks_per_picture_minus_1
+            "1",                        // decoder_model_info_present_flag
+            "00100",                    // buffer_delay_length_minus_1
+            &"0".repeat(42),            // num_units_in_decoding_tick, and two lengths
+            "1",                        // initial_display_delay_present_flag
+            "00001",                    // operating_points_cnt_minus_1
+            "000000000000",             // operating_point_idc[0]
+            "01101",                    // seq_level_idx[0]
+            "1",                        // seq_tier[0]
+            "1",                        // decoder_model_present_for_this_op[0]
+            "00000000000",              // decoder/encoder buffer delay, low_delay_mode_flag
+            "10000",        // initial_display_delay_present_for_this_op[0], and the delay
+            "000000000001", // operating_point_idc[1]
+            "00100",        // seq_level_idx[1]
+            "0",            // decoder_model_present_for_this_op[1]
+            "0",            // initial_display_delay_present_for_this_op[1]
+            "0000000000",   // frame dimensions, each one bit
+            "10000000",     // frame_id_numbers_present_flag and lengths
+            "0000000",      // superblock size, filter and compound tools
+            "100",          // enable_order_hint and dependent flags
+            "011",          // screen content and integer mv
+            "110",          // order_hint_bits_minus_1
+            "000",          // superres, cdef, restoration
+            "11",           // high_bitdepth, twelve_bit
+            "0",            // mono_chrome
+            "1",            // color_description_present_flag
+            "000010010001000000001001", // color_primaries, transfer_characteristics, matrix_coefficients
+            "1",                        // color_range
+            "11",                       // subsampling_x, subsampling_y
+            "10",                       // chroma_sample_position
+            "0",                        // separate_uv_delta_q
+        ]);
+        assert_eq!(to_string(&obu(&header)), "av01.2.13H.12.0.112.09.16.09.1");
+
+        // 8-bit 4:2:0 with no colour description, which are the defaults except for the
+        // colour values being 'unspecified'
+        let header = bits(&[
+            "000000",       // seq_profile to timing_info_present_flag
+            "0",            // initial_display_delay_present_flag
+            "00000",        // operating_points_cnt_minus_1
+            "000000000000", // operating_point_idc[0]
+            "010000",       // seq_level_idx[0] and seq_tier[0]
+            "0000000000",   // frame dimensions, each one bit
+            "00000000",     // frame_id_numbers_present_flag to enable_order_hint
+            "00",           // screen content tools
+            "000",          // superres, cdef, restoration
+            "000",          // high_bitdepth, mono_chrome, color_description_present_flag
+            "0",            // color_range
+            "00",           // chroma_sample_position
+            "0",            // separate_uv_delta_q
+        ]);
+        assert_eq!(to_string(&obu(&header)), "av01.0.08M.08.0.110.02.02.02.0");
+        // the same, with obu_size zero-padded to the full eight bytes of a leb128()
+        let mut padded = vec![0b0000_1010, 0x80 | header.len() as u8];
+        padded.extend_from_slice(&[0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x00]);
+        padded.extend_from_slice(&header);
+        assert_eq!(to_string(&padded), "av01.0.08M.08.0.110.02.02.02.0");
+
+        // a reduced still picture header, for 4:4:4 BT.709 in the High profile
+        let header = bits(&[
+            "00111",                    // seq_profile to reduced_still_picture_header
+            "00101",                    // seq_level_idx[0]
+            "0000000000",               // frame dimensions, each one bit
+            "000",                      // superblock size and intra filters
+            "000",                      // superres, cdef, restoration
+            "0",                        // high_bitdepth
+            "1",                        // color_description_present_flag
+            "000000010000000100000001", // BT.709
+            "0",                        // color_range
+            "0",                        // separate_uv_delta_q
+        ]);
+        assert_eq!(to_string(&obu(&header)), "av01.1.05M.08.0.000.01.01.01.0");
+    }
+
+    #[test]
+    fn bad_obus() {
+        // a temporal delimiter
+        assert_matches!(
+            Av01::from_sequence_header_obu(&[0x12, 0x00]),
+            Err(CodecError::UnexpectedObuType(2))
+        );
+        assert_matches!(
+            Av01::from_sequence_header_obu(&[0x0a, 0x0b, 0x00, 0x00]),
+            Err(CodecError::ConfigurationRecordTooShort {
+                expected: 13,
+                got: 4
+            })
+        );
+        // an obu_size above 2^32 - 1
+        assert_matches!(
+            Av01::from_sequence_header_obu(&[0x0a, 0xff, 0xff, 0xff, 0xff, 0x10, 0x00]),
+            Err(CodecError::InvalidComponent(_))
+        );
+        // no obu_size, so the truncated data is read up to its end
+        assert_matches!(
+            Av01::from_sequence_header_obu(&[0x08, 0x00, 0x00]),
+            Err(CodecError::ConfigurationRecordTooShort { .. })
+        );
+    }
+}
@@ -0,0 +1,196 @@
+//! Choosing how tolerant parsing should be of deviations from the codec string syntax.
+
+use crate::{Codec, CodecError, CodecListError, LenientFix};
+
+/// How tolerant parsing should be of input which deviates from the codec string syntax.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum Strictness {
+    /// Only accept values following the RFC 6381 syntax and the syntax registered for each
+    /// codec, exactly as the [`FromStr`](std::str::FromStr) implementation of [`Codec`] does
+    #[default]
+    Strict,
+    /// Additionally accept the whitespace and quotes surrounding a value which web browsers
+    /// discard before checking codec strings
+    BrowserCompatible,
+    /// Accept every repair made by [`Codec::parse_lenient()`]
+    Permissive,
+}
+impl Strictness {
+    /// Whether a value needing the given repair is accepted at this level of strictness
+    pub fn permits(self, fix: LenientFix) -> bool {
+        match self {
+            Strictness::Strict => false,
+            Strictness::BrowserCompatible => matches!(
+                fix,
+                LenientFix::TrimmedWhitespace | LenientFix::RemovedQuotes
+            ),
+            Strictness::Permissive => true,
+        }
+    }
+}
+
+/// Options controlling [`Codec::parse_with_options()`] and
+/// [`Codec::parse_codecs_with_options()`].
+///
+/// ```rust
+/// # use rfc6381_codec::{Codec, ParseOptions, Strictness};
+/// let browser = ParseOptions::new().with_strictness(Strictness::BrowserCompatible);
+/// assert!(Codec::parse_with_options("\"mp4a.40.2\"", &browser).is_ok());
+/// assert!(matches!(
+///     Codec::parse_with_options("MP4A.40.2", &browser),
+///     Ok(Codec::Other(_))
+/// ));
+///
+/// let permissive = ParseOptions::new().with_strictness(Strictness::Permissive);
+/// assert!(matches!(
+///     Codec::parse_with_options("MP4A.40.2", &permissive),
+///     Ok(Codec::Mp4a(_))
+/// ));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ParseOptions {
+    strictness: Strictness,
+}
+impl ParseOptions {
+    /// Creates options for [`Strictness::Strict`] parsing
+    pub fn new() -> ParseOptions {
+        ParseOptions::default()
+    }
+    pub fn with_strictness(mut self, strictness: Strictness) -> ParseOptions {
+        self.strictness = strictness;
+        self
+    }
+    pub fn strictness(&self) -> Strictness {
+        self.strictness
+    }
+}
+
+impl Codec {
+    /// Parses a single codec string, accepting the deviations permitted by the given options.
+    ///
+    /// Repairs are only considered when strict parsing fails or gives a [`Codec::Other`]
+    /// value.  If the value needs a repair which the options do not permit, the result is the
+    /// one that strict parsing gives.
+    pub fn parse_with_options(codec: &str, options: &ParseOptions) -> Result<Codec, CodecError> {
+        let strict = codec.parse();
+        if options.strictness == Strictness::Strict || !may_need_repair(&strict) {
+            return strict;
+        }
+        match Codec::parse_lenient(codec) {
+            Ok(lenient)
+                if lenient
+                    .fixes()
+                    .iter()
+                    .all(|f| options.strictness.permits(*f)) =>
+            {
+                Ok(lenient.into_value())
+            }
+            _ => strict,
+        }
+    }
+
+    /// Parses a comma-separated list of codec strings, accepting the deviations permitted by
+    /// the given options.
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::{Codec, ParseOptions, Strictness};
+    /// let options = ParseOptions::new().with_strictness(Strictness::BrowserCompatible);
+    /// let codecs = Codec::parse_codecs_with_options("'avc1.4D401E', 'mp4a.40.2'", &options);
+    /// assert_eq!(codecs.unwrap().len(), 2);
+    /// ```
+    pub fn parse_codecs_with_options(
+        codecs: &str,
+        options: &ParseOptions,
+    ) -> Result<Vec<Codec>, CodecListError> {
+        let strict: Result<Vec<_>, _> = Codec::parse_codecs_with_positions(codecs).collect();
+        let may_need_repair = match &strict {
+            Ok(list) => list.iter().any(|c| matches!(c, Codec::Other(_))),
+            Err(_) => true,
+        };
+        if options.strictness != Strictness::Strict && may_need_repair {
+            if let Ok(lenient) = Codec::parse_codecs_lenient(codecs) {
+                if lenient
+                    .fixes()
+                    .iter()
+                    .all(|f| options.strictness.permits(*f))
+                {
+                    return Ok(lenient.into_value());
+                }
+            }
+        }
+        strict
+    }
+}
+
+fn may_need_repair(strict: &Result<Codec, CodecError>) -> bool {
+    matches!(strict, Ok(Codec::Other(_)) | Err(_))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::*;
+
+    fn options(strictness: Strictness) -> ParseOptions {
+        ParseOptions::new().with_strictness(strictness)
+    }
+
+    #[test]
+    fn levels() {
+        let strict = options(Strictness::Strict);
+        let browser = options(Strictness::BrowserCompatible);
+        #[cfg(feature = "vp9")]
+        let permissive = options(Strictness::Permissive);
+        assert_eq!(ParseOptions::new(), strict);
+
+        assert_matches!(Codec::parse_with_options("avc1.4d401e", &strict), Ok(_));
+        assert_matches!(
+            Codec::parse_with_options(" avc1.4d401e", &strict),
+            Ok(Codec::Other(_))
+        );
+        assert_matches!(
+            Codec::parse_with_options(" avc1.4d401e", &browser),
+            Ok(Codec::Avc1(_))
+        );
+        #[cfg(feature = "vp9")]
+        assert_matches!(
+            Codec::parse_with_options("vp09.0.10.8", &browser),
+            Err(CodecError::UnexpectedLength { .. })
+        );
+        #[cfg(feature = "vp9")]
+        assert_matches!(
+            Codec::parse_with_options("vp09.0.10.8", &permissive),
+            Ok(Codec::Vp09(_))
+        );
+    }
+
+    #[test]
+    fn lists() {
+        let browser = options(Strictness::BrowserCompatible);
+        assert_matches!(
+            Codec::parse_codecs_with_options("avc1.4d401e, opus", &options(Strictness::Strict)),
+            Ok(v) if v.len() == 2
+        );
+        assert_matches!(
+            Codec::parse_codecs_with_options("\"avc1.4d401e, opus\"", &browser),
+            Ok(v) if v.len() == 2
+        );
+        assert_matches!(
+            Codec::parse_codecs_with_options("avc1.4d401e, avc1", &browser),
+            Err(e) if e.index() == 1
+        );
+        assert_matches!(
+            Codec::parse_codecs_with_options("'avc1.4d401e', 'opus'", &browser),
+            Ok(v) if matches!(v[..], [Codec::Avc1(_), Codec::Opus])
+        );
+        assert_matches!(
+            Codec::parse_codecs_with_options("avc1.4d401e, OPUS", &browser),
+            Ok(v) if matches!(v[1], Codec::Other(_))
+        );
+        assert_matches!(
+            Codec::parse_codecs_with_options("avc1.4d401e, OPUS,", &options(Strictness::Permissive)),
+            Ok(v) if v.len() == 2
+        );
+    }
+}
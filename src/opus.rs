@@ -0,0 +1,118 @@
+//! Creating `Opus` codec values from Opus-in-ISOBMFF decoder configuration data.
+
+use crate::{Codec, CodecError};
+
+/// The length of the `dOps` fields preceding the optional channel mapping table
+const DOPS_HEADER_LEN: usize = 11;
+
+impl Codec {
+    /// Creates a [`Codec::Opus`] value from the payload of a `dOps` box (i.e. the
+    /// `OpusSpecificBox` defined by _Encapsulation of Opus in ISO Base Media File Format_,
+    /// without the box header).
+    ///
+    /// The `Opus` codecs parameter has no sub-elements, so this only validates the `Version`
+    /// and the channel mapping given in the box.
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::Codec;
+    /// // stereo, channel mapping family 0
+    /// let dops = [0x00, 0x02, 0x01, 0x38, 0x00, 0x00, 0xbb, 0x80, 0x00, 0x00, 0x00];
+    /// assert_eq!(Codec::from_dops(&dops).unwrap().to_string(), "Opus");
+    /// ```
+    pub fn from_dops(dops: &[u8]) -> Result<Codec, CodecError> {
+        if dops.len() < DOPS_HEADER_LEN {
+            return Err(CodecError::ConfigurationRecordTooShort {
+                expected: DOPS_HEADER_LEN,
+                got: dops.len(),
+            });
+        }
+        if dops[0] != 0 {
+            return Err(CodecError::UnsupportedConfigurationVersion(dops[0]));
+        }
+        let channel_count = dops[1];
+        let channel_mapping_family = dops[10];
+        let valid = match channel_mapping_family {
+            0 => channel_count == 1 || channel_count == 2,
+            1 => (1..=8).contains(&channel_count),
+            _ => channel_count > 0,
+        };
+        if !valid {
+            return Err(CodecError::InvalidChannelMapping {
+                channel_count,
+                channel_mapping_family,
+            });
+        }
+        if channel_mapping_family != 0 {
+            // StreamCount, CoupledCount and then one ChannelMapping entry per output channel
+            let expected = DOPS_HEADER_LEN + 2 + usize::from(channel_count);
+            if dops.len() < expected {
+                return Err(CodecError::ConfigurationRecordTooShort {
+                    expected,
+                    got: dops.len(),
+                });
+            }
+        }
+        Ok(Codec::Opus)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::*;
+
+    fn dops(channel_count: u8, family: u8, table: &[u8]) -> Vec<u8> {
+        let mut dops = vec![
+            0x00,
+            channel_count,
+            0x01,
+            0x38,
+            0,
+            0,
+            0xbb,
+            0x80,
+            0,
+            0,
+            family,
+        ];
+        dops.extend_from_slice(table);
+        dops
+    }
+
+    #[test]
+    fn from_dops() {
+        assert_matches!(Codec::from_dops(&dops(1, 0, &[])), Ok(Codec::Opus));
+        assert_matches!(
+            Codec::from_dops(&dops(6, 1, &[4, 2, 0, 4, 1, 2, 3, 5])),
+            Ok(Codec::Opus)
+        );
+        assert_matches!(
+            Codec::from_dops(&dops(6, 0, &[])),
+            Err(CodecError::InvalidChannelMapping {
+                channel_count: 6,
+                channel_mapping_family: 0
+            })
+        );
+        assert_matches!(
+            Codec::from_dops(&dops(9, 1, &[])),
+            Err(CodecError::InvalidChannelMapping { .. })
+        );
+        assert_matches!(
+            Codec::from_dops(&dops(6, 1, &[4, 2, 0, 4])),
+            Err(CodecError::ConfigurationRecordTooShort {
+                expected: 19,
+                got: 15
+            })
+        );
+        let mut bad_version = dops(2, 0, &[]);
+        bad_version[0] = 1;
+        assert_matches!(
+            Codec::from_dops(&bad_version),
+            Err(CodecError::UnsupportedConfigurationVersion(1))
+        );
+        assert_matches!(
+            Codec::from_dops(&dops(2, 0, &[])[..10]),
+            Err(CodecError::ConfigurationRecordTooShort { .. })
+        );
+    }
+}
@@ -0,0 +1,117 @@
+//! Codec values which this crate does not interpret.
+
+use four_cc::FourCC;
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+/// A codec value which this crate does not interpret, held as [`Codec::Other`](crate::Codec::Other).
+///
+/// The value is split at the `.` hierarchy separators, so that the leading four-character-code
+/// and the remaining elements can still be inspected.  The first element may not be a
+/// four-character-code at all, as for the bare `vorbis` or `vp8` tokens used in WebM and Ogg
+/// media types.
+///
+/// ```rust
+/// # use rfc6381_codec::Codec;
+/// # use four_cc::FourCC;
+/// # use std::str::FromStr;
+/// if let Ok(Codec::Other(other)) = Codec::from_str("vvc1.1.L51.CQA") {
+///     assert_eq!(other.four_cc(), Some(FourCC(*b"vvc1")));
+///     assert_eq!(other.elements().collect::<Vec<_>>(), ["1", "L51", "CQA"]);
+///     assert_eq!(other.as_str(), "vvc1.1.L51.CQA");
+/// } else {
+///     panic!("unexpected codec type");
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OtherCodec {
+    four_cc: Option<FourCC>,
+    raw: String,
+}
+impl OtherCodec {
+    pub fn new(raw: String) -> OtherCodec {
+        let first = raw.split('.').next().unwrap();
+        let four_cc = if first.len() == 4 {
+            Some(FourCC::from(first.as_bytes()))
+        } else {
+            None
+        };
+        OtherCodec { four_cc, raw }
+    }
+    /// The leading four-character-code, or `None` if the first element isn't four bytes long
+    pub fn four_cc(&self) -> Option<FourCC> {
+        self.four_cc
+    }
+    /// The first element, like `vvc1` in `vvc1.1.L51.CQA`, or the whole of a bare token like
+    /// `vorbis`
+    pub fn first_element(&self) -> &str {
+        self.raw.split('.').next().unwrap()
+    }
+    /// The elements following the first, like `1`, `L51` and `CQA` in `vvc1.1.L51.CQA`
+    pub fn elements(&self) -> impl Iterator<Item = &str> {
+        self.raw.split('.').skip(1)
+    }
+    /// The complete codec value text
+    pub fn as_str(&self) -> &str {
+        &self.raw
+    }
+    pub fn into_string(self) -> String {
+        self.raw
+    }
+}
+impl From<String> for OtherCodec {
+    fn from(raw: String) -> Self {
+        OtherCodec::new(raw)
+    }
+}
+impl From<&str> for OtherCodec {
+    fn from(raw: &str) -> Self {
+        OtherCodec::new(raw.to_owned())
+    }
+}
+// FourCC doesn't implement Hash or Ord, but is derived from the text anyway, so the text alone
+// is used
+impl Hash for OtherCodec {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.raw.hash(state)
+    }
+}
+impl PartialOrd for OtherCodec {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for OtherCodec {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.raw.cmp(&other.raw)
+    }
+}
+impl fmt::Display for OtherCodec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.raw)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn elements() {
+        let other = OtherCodec::from("vorbis");
+        assert_eq!(other.four_cc(), None);
+        assert_eq!(other.first_element(), "vorbis");
+        assert_eq!(other.elements().count(), 0);
+
+        let other = OtherCodec::from("badd.41");
+        assert_eq!(other.four_cc(), Some(FourCC(*b"badd")));
+        assert_eq!(other.elements().collect::<Vec<_>>(), ["41"]);
+        assert_eq!(other.to_string(), "badd.41");
+
+        let other = OtherCodec::from("cod👍ec.1.");
+        assert_eq!(other.four_cc(), None);
+        assert_eq!(other.elements().collect::<Vec<_>>(), ["1", ""]);
+        assert_eq!(other.into_string(), "cod👍ec.1.");
+    }
+}
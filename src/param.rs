@@ -0,0 +1,330 @@
+//! Parsing the whole `codecs` parameter, as given by the `cod-param` production of RFC 6381.
+
+use crate::content_type::parameter_value;
+use crate::{Codec, CodecError};
+use std::fmt;
+use std::str::FromStr;
+
+impl Codec {
+    /// Parses a complete `codecs` parameter, including the parameter name, like
+    /// `codecs="avc1.4D401E, mp4a.40.2"`.
+    ///
+    /// This implements the `cod-simple` form from RFC 6381, where the value is either a
+    /// quoted, comma-separated list of codec values, or a single unquoted codec value.  The
+    /// parameter name is matched case-insensitively, and whitespace is allowed around the `=`.
+    /// The `cod-fancy` form (`codecs*=...`) is accepted too; use [`CodecsParameter`] to also
+    /// get the language tag which that form may carry.
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::Codec;
+    /// let codecs = Codec::parse_codecs_parameter(r#"codecs="avc1.4D401E, mp4a.40.2""#).unwrap();
+    /// assert_eq!(codecs.len(), 2);
+    /// let codecs = Codec::parse_codecs_parameter("Codecs=Opus").unwrap();
+    /// assert!(matches!(codecs[..], [Codec::Opus]));
+    /// ```
+    pub fn parse_codecs_parameter(param: &str) -> Result<Vec<Codec>, CodecError> {
+        CodecsParameter::from_str(param).map(CodecsParameter::into_codecs)
+    }
+}
+
+/// A parsed `codecs` parameter, in either the `cod-simple` or the `cod-fancy` form of RFC 6381.
+///
+/// The `cod-fancy` form uses the RFC 2231 extended parameter syntax, where the value is
+/// prefixed with an optional charset and language tag, and may use percent-encoding:
+///
+/// ```rust
+/// # use rfc6381_codec::{Codec, CodecsParameter};
+/// # use std::str::FromStr;
+/// let param = CodecsParameter::from_str("codecs*=UTF-8'en-gb'%61%76%63%31.4D401E").unwrap();
+/// assert_eq!(param.language(), Some("en-gb"));
+/// assert!(matches!(param.codecs(), [Codec::Avc1(_)]));
+///
+/// let param = CodecsParameter::from_str(r#"codecs*="''avc1.4D401E, mp4a.40.2""#).unwrap();
+/// assert_eq!(param.language(), None);
+/// assert_eq!(param.codecs().len(), 2);
+/// ```
+///
+/// The `Display` implementation generates the parameter again, in whichever form it was parsed
+/// from or created with.  The `id-list` of the `cod-fancy` form allows no whitespace, so its
+/// entries are separated by a bare `,`:
+///
+/// ```rust
+/// # use rfc6381_codec::{Codec, CodecsParameter};
+/// let param = CodecsParameter::new(vec![Codec::avc1(0x4d, 0x40, 0x1e), Codec::aac_lc()]);
+/// assert_eq!(param.to_string(), r#"codecs="avc1.4D401E, mp4a.40.2""#);
+/// let param = param.with_fancy_syntax(Some("UTF-8"), Some("en")).unwrap();
+/// assert_eq!(param.to_string(), r#"codecs*="UTF-8'en'avc1.4D401E,mp4a.40.2""#);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CodecsParameter {
+    fancy: bool,
+    charset: Option<String>,
+    language: Option<String>,
+    codecs: Vec<Codec>,
+}
+impl CodecsParameter {
+    /// Creates a value which will be written in the `cod-simple` form.
+    pub fn new(codecs: Vec<Codec>) -> CodecsParameter {
+        CodecsParameter {
+            fancy: false,
+            charset: None,
+            language: None,
+            codecs,
+        }
+    }
+    /// Switches to the `cod-fancy` form, with the given (optional) charset and language tag.
+    ///
+    /// The codec values themselves are always ASCII, so the charset only affects how the value
+    /// is labelled.  As when parsing, only the `US-ASCII` and `UTF-8` charsets are supported,
+    /// and any other gives `CodecError::InvalidParameter`.
+    pub fn with_fancy_syntax(
+        mut self,
+        charset: Option<&str>,
+        language: Option<&str>,
+    ) -> Result<CodecsParameter, CodecError> {
+        if let Some(charset) = charset {
+            if !is_supported_charset(charset) {
+                return Err(CodecError::InvalidParameter(charset.into()));
+            }
+        }
+        self.fancy = true;
+        self.charset = charset.map(str::to_string);
+        self.language = language.map(str::to_string);
+        Ok(self)
+    }
+    /// Whether this value uses the `cod-fancy` (`codecs*=`) form
+    pub fn is_fancy(&self) -> bool {
+        self.fancy
+    }
+    /// The charset given in the `cod-fancy` form, if any
+    pub fn charset(&self) -> Option<&str> {
+        self.charset.as_deref()
+    }
+    /// The language tag given in the `cod-fancy` form, if any
+    pub fn language(&self) -> Option<&str> {
+        self.language.as_deref()
+    }
+    pub fn codecs(&self) -> &[Codec] {
+        &self.codecs
+    }
+    pub fn into_codecs(self) -> Vec<Codec> {
+        self.codecs
+    }
+}
+impl FromStr for CodecsParameter {
+    type Err = CodecError;
+
+    fn from_str(param: &str) -> Result<Self, Self::Err> {
+        let invalid = || CodecError::InvalidParameter(param.into());
+        let (name, value) = param.split_once('=').ok_or_else(invalid)?;
+        let name = name.trim();
+        let fancy = if name.eq_ignore_ascii_case("codecs") {
+            false
+        } else if name.eq_ignore_ascii_case("codecs*") {
+            true
+        } else {
+            return Err(invalid());
+        };
+        let (value, rest) = parameter_value(value.trim_start()).ok_or_else(invalid)?;
+        if !rest.trim().is_empty() {
+            return Err(invalid());
+        }
+        let (charset, language, value) = if fancy {
+            decode_extended_value(&value).ok_or_else(invalid)?
+        } else {
+            (None, None, value)
+        };
+        Ok(CodecsParameter {
+            fancy,
+            charset,
+            language,
+            codecs: Codec::parse_codecs(&value).collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/// Decodes a value of the form `charset'language'value`, returning the charset and language tag
+/// (if any) and the value with percent-encoding removed.  Only the `US-ASCII` and `UTF-8`
+/// charsets are supported.
+pub(crate) fn decode_extended_value(
+    value: &str,
+) -> Option<(Option<String>, Option<String>, String)> {
+    let mut parts = value.splitn(3, '\'');
+    let charset = parts.next()?;
+    let language = parts.next()?;
+    let value = parts.next()?;
+    if !charset.is_empty() && !is_supported_charset(charset) {
+        return None;
+    }
+    let ascii = !charset.eq_ignore_ascii_case("utf-8");
+    let mut bytes = Vec::with_capacity(value.len());
+    let mut i = value.bytes();
+    while let Some(b) = i.next() {
+        if b == b'%' {
+            let hex = [i.next()?, i.next()?];
+            if !hex.iter().all(u8::is_ascii_hexdigit) {
+                return None;
+            }
+            let hex = std::str::from_utf8(&hex).ok()?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+        } else {
+            bytes.push(b);
+        }
+    }
+    if ascii && !bytes.is_ascii() {
+        return None;
+    }
+    let value = String::from_utf8(bytes).ok()?;
+    let non_empty = |s: &str| {
+        if s.is_empty() {
+            None
+        } else {
+            Some(s.to_string())
+        }
+    };
+    Some((non_empty(charset), non_empty(language), value))
+}
+
+/// The charsets which may label a `cod-fancy` value, matched case-insensitively
+fn is_supported_charset(charset: &str) -> bool {
+    charset.eq_ignore_ascii_case("us-ascii") || charset.eq_ignore_ascii_case("utf-8")
+}
+
+/// The RFC 2231 `attribute-char` set, which may appear in an extended value without
+/// percent-encoding
+fn is_attribute_char(b: u8) -> bool {
+    b.is_ascii_graphic() && !b"*'%()<>@,;:\\\"/[]?=".contains(&b)
+}
+
+impl fmt::Display for CodecsParameter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.fancy {
+            f.write_str("codecs=\"")?;
+            for (i, codec) in self.codecs.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(", ")?;
+                }
+                write!(f, "{}", codec)?;
+            }
+            return f.write_str("\"");
+        }
+        // a single value may be given without quotes, but a list needs the quoted fancy-list
+        let quote = if self.codecs.len() == 1 { "" } else { "\"" };
+        write!(
+            f,
+            "codecs*={}{}'{}'",
+            quote,
+            self.charset().unwrap_or(""),
+            self.language().unwrap_or("")
+        )?;
+        for (i, codec) in self.codecs.iter().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            for b in codec.to_string().bytes() {
+                if is_attribute_char(b) {
+                    write!(f, "{}", b as char)?;
+                } else {
+                    write!(f, "%{:02X}", b)?;
+                }
+            }
+        }
+        f.write_str(quote)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::*;
+
+    #[test]
+    fn cod_simple() {
+        let codecs = Codec::parse_codecs_parameter(r#"codecs = "avc1.4D401E,mp4a.40.2" "#).unwrap();
+        assert_matches!(codecs[..], [Codec::Avc1(_), Codec::Mp4a(_)]);
+        #[cfg(feature = "hevc")]
+        let codecs = Codec::parse_codecs_parameter("CODECS=hvc1.1.6.L93.B0").unwrap();
+        #[cfg(feature = "hevc")]
+        assert_matches!(codecs[..], [Codec::Hevc(_)]);
+        for s in &[
+            "avc1.4D401E",
+            "codec=avc1.4D401E",
+            "codecs=",
+            r#"codecs="avc1.4D401E"#,
+            "codecs=avc1.4D401E, mp4a.40.2",
+        ] {
+            assert_matches!(
+                Codec::parse_codecs_parameter(s),
+                Err(CodecError::InvalidParameter(_)),
+                "{}",
+                s
+            );
+        }
+        assert_matches!(
+            Codec::parse_codecs_parameter(r#"codecs="avc1.4D401E, avc1.4D""#),
+            Err(CodecError::UnexpectedLength { .. })
+        );
+    }
+
+    #[test]
+    fn cod_fancy() {
+        let param = CodecsParameter::from_str("codecs*=''%61%76%63%31.4D401E").unwrap();
+        assert_eq!(param.language(), None);
+        assert_matches!(param.codecs(), [Codec::Avc1(_)]);
+        let param = CodecsParameter::from_str(r#"CODECS*="us-ascii'de'Opus,%66LaC""#).unwrap();
+        assert_eq!(param.language(), Some("de"));
+        assert_matches!(param.codecs(), [Codec::Opus, Codec::Flac]);
+        let param = CodecsParameter::from_str("codecs=mp4a.40.2").unwrap();
+        assert_eq!(param.language(), None);
+        for s in &[
+            "codecs*=avc1.4D401E",
+            "codecs*='avc1.4D401E",
+            "codecs*=latin1''avc1.4D401E",
+            "codecs*=''avc1.4D401E%",
+            "codecs*=''avc1.4D401E%4",
+            "codecs*=''avc1.4D401E%G0",
+            "codecs*=''%+f",
+            "codecs*=''avc1.4D401E%ff",
+            "codecs*=us-ascii''mp4a.40.2%c3%a9",
+        ] {
+            assert_matches!(
+                CodecsParameter::from_str(s),
+                Err(CodecError::InvalidParameter(_)),
+                "{}",
+                s
+            );
+        }
+        assert_matches!(
+            CodecsParameter::from_str("codecs*=utf-8''mp4a.40.2%c3%a9"),
+            Err(CodecError::InvalidComponent(_))
+        );
+    }
+
+    #[test]
+    fn generate() {
+        let param = CodecsParameter::new(vec![Codec::Opus]);
+        assert_eq!(param.to_string(), r#"codecs="Opus""#);
+        let param = param.with_fancy_syntax(None, None).unwrap();
+        assert_eq!(param.to_string(), "codecs*=''Opus");
+        let param = CodecsParameter::new(vec![Codec::Other("a'b c".into()), Codec::Flac])
+            .with_fancy_syntax(Some("us-ascii"), Some("en-gb"))
+            .unwrap();
+        assert_eq!(
+            param.to_string(),
+            r#"codecs*="us-ascii'en-gb'a%27b%20c,fLaC""#
+        );
+        assert_matches!(
+            CodecsParameter::new(vec![Codec::Opus]).with_fancy_syntax(Some("latin1"), None),
+            Err(CodecError::InvalidParameter(_))
+        );
+        for s in &[
+            r#"codecs="avc1.4D401E, mp4a.40.2""#,
+            "codecs*=''Opus",
+            "codecs*=UTF-8'en'hvc1.1.6.L93.B0",
+            r#"codecs*="'de'avc1.4D401E,mp4a.40.2""#,
+        ] {
+            let param = CodecsParameter::from_str(s).unwrap();
+            assert_eq!(param.to_string(), *s);
+        }
+    }
+}
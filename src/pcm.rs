@@ -0,0 +1,57 @@
+//! Support for ISO/IEC 23003-5 uncompressed audio codec strings.
+
+use four_cc::FourCC;
+use mp4ra_rust::SampleEntryCode;
+use std::fmt;
+
+pub(crate) const IPCM: SampleEntryCode = SampleEntryCode(FourCC(*b"ipcm"));
+pub(crate) const FPCM: SampleEntryCode = SampleEntryCode(FourCC(*b"fpcm"));
+
+/// The PCM sample entry codes defined by ISO/IEC 23003-5.
+///
+/// Neither takes any sub-parameters, so the codec string is just the sample entry code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Pcm {
+    /// Integer PCM samples (`ipcm`)
+    Integer,
+    /// Floating point PCM samples (`fpcm`)
+    FloatingPoint,
+}
+impl Pcm {
+    pub(crate) fn from_sample_entry(sample_entry: SampleEntryCode) -> Option<Pcm> {
+        match sample_entry {
+            IPCM => Some(Pcm::Integer),
+            FPCM => Some(Pcm::FloatingPoint),
+            _ => None,
+        }
+    }
+
+    pub fn sample_entry_code(&self) -> SampleEntryCode {
+        match self {
+            Pcm::Integer => IPCM,
+            Pcm::FloatingPoint => FPCM,
+        }
+    }
+}
+impl fmt::Display for Pcm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.sample_entry_code().0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Codec;
+    use assert_matches::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn pcm() {
+        assert_matches!(Codec::from_str("ipcm"), Ok(Codec::Pcm(Pcm::Integer)));
+        assert_matches!(Codec::from_str("fpcm"), Ok(Codec::Pcm(Pcm::FloatingPoint)));
+        assert_eq!("ipcm", Codec::from_str("ipcm").unwrap().to_string());
+        assert_eq!("fpcm", Codec::from_str("fpcm").unwrap().to_string());
+        assert_matches!(Codec::from_str("ipcm.24"), Err(_));
+    }
+}
@@ -0,0 +1,116 @@
+//! Support for the `profiles` parameter, listing the brands a file conforms to.
+
+use crate::content_type::parameter_value;
+use crate::CodecError;
+use four_cc::FourCC;
+use std::fmt;
+use std::str::FromStr;
+
+/// The `profiles` parameter defined by RFC 6381, like `profiles="isom,mp42"`, which lists
+/// the ISO Base Media File Format brands that a file is compatible with.
+///
+/// ```rust
+/// # use rfc6381_codec::Profiles;
+/// # use four_cc::FourCC;
+/// # use std::str::FromStr;
+/// let profiles = Profiles::from_str(r#"profiles="isom, mp42""#).unwrap();
+/// assert!(profiles.contains(FourCC(*b"mp42")));
+/// assert_eq!(profiles.to_string(), r#"profiles="isom,mp42""#);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Profiles {
+    brands: Vec<FourCC>,
+}
+impl Profiles {
+    pub fn new(brands: Vec<FourCC>) -> Profiles {
+        Profiles { brands }
+    }
+    pub fn brands(&self) -> &[FourCC] {
+        &self.brands
+    }
+    pub fn contains(&self, brand: FourCC) -> bool {
+        self.brands.contains(&brand)
+    }
+
+    /// Parses the parameter value alone, with any quotes already removed, like `isom,mp42`.
+    pub(crate) fn from_value(value: &str) -> Result<Profiles, CodecError> {
+        let brands = value
+            .split(',')
+            .map(|brand| {
+                // brands may end with a space, as in 'M4A ', so only leading spaces are removed
+                let brand = brand.trim_start();
+                if brand.len() == 4 {
+                    Ok(FourCC::from(brand.as_bytes()))
+                } else {
+                    Err(CodecError::UnexpectedLength {
+                        expected: 4,
+                        got: brand.into(),
+                    })
+                }
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Profiles { brands })
+    }
+}
+impl FromStr for Profiles {
+    type Err = CodecError;
+
+    /// Parses the whole parameter, including the `profiles=` name, which is matched
+    /// case-insensitively.
+    fn from_str(param: &str) -> Result<Self, Self::Err> {
+        let invalid = || CodecError::InvalidParameter(param.into());
+        let (name, value) = param.split_once('=').ok_or_else(invalid)?;
+        if !name.trim().eq_ignore_ascii_case("profiles") {
+            return Err(invalid());
+        }
+        let (value, rest) = parameter_value(value.trim_start()).ok_or_else(invalid)?;
+        if !rest.trim().is_empty() {
+            return Err(invalid());
+        }
+        Profiles::from_value(&value)
+    }
+}
+impl fmt::Display for Profiles {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("profiles=\"")?;
+        for (i, brand) in self.brands.iter().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            write!(f, "{}", brand)?;
+        }
+        f.write_str("\"")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::*;
+
+    #[test]
+    fn parse() {
+        let profiles = Profiles::from_str("Profiles=cmfc").unwrap();
+        assert_eq!(profiles.brands(), [FourCC(*b"cmfc")].as_slice());
+        let profiles = Profiles::from_str(r#"profiles="M4A ,mp42""#).unwrap();
+        assert!(profiles.contains(FourCC(*b"M4A ")));
+        assert_eq!(profiles.to_string(), r#"profiles="M4A ,mp42""#);
+        assert_matches!(
+            Profiles::from_str(r#"profiles="isom,mp4""#),
+            Err(CodecError::UnexpectedLength { expected: 4, .. })
+        );
+        for s in &[
+            "isom",
+            "codecs=isom",
+            r#"profiles="isom"#,
+            "profiles=isom,mp42",
+        ] {
+            assert_matches!(
+                Profiles::from_str(s),
+                Err(CodecError::InvalidParameter(_)),
+                "{}",
+                s
+            );
+        }
+    }
+}
@@ -0,0 +1,266 @@
+//! Registering parsers for sample entry codes which this crate does not itself interpret.
+
+use crate::{Codec, CodecError, MediaKind};
+use four_cc::FourCC;
+use std::any::Any;
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+/// A codec value produced by a [`CodecFamilyParser`], held as
+/// [`Codec::Extension`](crate::Codec::Extension).
+///
+/// The `Display` implementation must write the codec string form of the value, which is used
+/// when comparing and hashing [`ExtensionCodec`] values.
+pub trait CodecExtension: fmt::Debug + fmt::Display + Send + Sync + 'static {
+    /// The kind of media which this codec encodes, `MediaKind::Unknown` unless overridden
+    fn media_kind(&self) -> MediaKind {
+        MediaKind::Unknown
+    }
+    /// Describes the codec for [`Codec::description()`], the codec string form unless overridden
+    fn description(&self) -> String {
+        self.to_string()
+    }
+    /// The value as `Any`, so that [`ExtensionCodec::downcast_ref()`] can give the concrete type
+    fn as_any(&self) -> &dyn Any;
+}
+
+/// Parses codec strings for some family of sample entry codes, for use with a
+/// [`CodecRegistry`].
+pub trait CodecFamilyParser: Send + Sync + 'static {
+    /// The sample entry codes which this parser handles
+    fn four_ccs(&self) -> &[FourCC];
+    /// Parses the complete codec value, including the leading sample entry code
+    fn parse(&self, codec: &str) -> Result<Box<dyn CodecExtension>, CodecError>;
+}
+
+/// A shared handle to a value produced by a [`CodecFamilyParser`].
+///
+/// Values are compared, ordered and hashed using their codec string form.
+#[derive(Debug, Clone)]
+pub struct ExtensionCodec(Arc<dyn CodecExtension>);
+impl ExtensionCodec {
+    pub fn new<E: CodecExtension>(extension: E) -> ExtensionCodec {
+        ExtensionCodec(Arc::new(extension))
+    }
+    pub fn get(&self) -> &dyn CodecExtension {
+        &*self.0
+    }
+    /// The value produced by the parser, if it is of the given type
+    pub fn downcast_ref<E: CodecExtension>(&self) -> Option<&E> {
+        self.0.as_any().downcast_ref()
+    }
+    /// The leading four-character-code of the codec string form
+    pub fn four_cc(&self) -> Option<FourCC> {
+        let value = self.0.to_string();
+        let first = value.split('.').next().unwrap();
+        if first.len() == 4 {
+            Some(FourCC::from(first.as_bytes()))
+        } else {
+            None
+        }
+    }
+}
+impl From<Box<dyn CodecExtension>> for ExtensionCodec {
+    fn from(extension: Box<dyn CodecExtension>) -> Self {
+        ExtensionCodec(Arc::from(extension))
+    }
+}
+impl PartialEq for ExtensionCodec {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.to_string() == other.0.to_string()
+    }
+}
+impl Eq for ExtensionCodec {}
+impl Hash for ExtensionCodec {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.to_string().hash(state)
+    }
+}
+impl PartialOrd for ExtensionCodec {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for ExtensionCodec {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.to_string().cmp(&other.0.to_string())
+    }
+}
+impl fmt::Display for ExtensionCodec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// A set of [`CodecFamilyParser`] implementations, consulted before the parsing built in to this
+/// crate.
+///
+/// Values whose leading element is one of the sample entry codes of a registered parser are
+/// parsed by that parser, giving [`Codec::Extension`] values, and any other values are parsed as
+/// by the [`FromStr`](std::str::FromStr) implementation of [`Codec`].  Since registered parsers
+/// take precedence, an application's parser for a code which a later version of this crate
+/// supports will continue to be used.
+///
+/// ```rust
+/// # use rfc6381_codec::{Codec, CodecError, CodecExtension, CodecFamilyParser, CodecRegistry, MediaKind};
+/// # use four_cc::FourCC;
+/// # use std::any::Any;
+/// # use std::fmt;
+/// #[derive(Debug)]
+/// struct Vvc {
+///     profile: u8,
+/// }
+/// impl fmt::Display for Vvc {
+///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         write!(f, "vvc1.{}", self.profile)
+///     }
+/// }
+/// impl CodecExtension for Vvc {
+///     fn media_kind(&self) -> MediaKind {
+///         MediaKind::Video
+///     }
+///     fn as_any(&self) -> &dyn Any {
+///         self
+///     }
+/// }
+///
+/// struct VvcParser;
+/// impl CodecFamilyParser for VvcParser {
+///     fn four_ccs(&self) -> &[FourCC] {
+///         const CODES: &[FourCC] = &[FourCC(*b"vvc1"), FourCC(*b"vvi1")];
+///         CODES
+///     }
+///     fn parse(&self, codec: &str) -> Result<Box<dyn CodecExtension>, CodecError> {
+///         let profile = codec[5..]
+///             .parse()
+///             .map_err(|_| CodecError::InvalidComponent(codec.into()))?;
+///         Ok(Box::new(Vvc { profile }))
+///     }
+/// }
+///
+/// let mut registry = CodecRegistry::new();
+/// registry.register(VvcParser);
+/// let codec = registry.parse("vvc1.1").unwrap();
+/// assert_eq!(codec.media_kind(), MediaKind::Video);
+/// if let Codec::Extension(ext) = &codec {
+///     assert_eq!(ext.downcast_ref::<Vvc>().unwrap().profile, 1);
+/// }
+/// assert!(matches!(registry.parse("mp4a.40.2"), Ok(Codec::Mp4a(_))));
+/// ```
+#[derive(Clone, Default)]
+pub struct CodecRegistry {
+    parsers: Vec<Arc<dyn CodecFamilyParser>>,
+}
+impl CodecRegistry {
+    pub fn new() -> CodecRegistry {
+        CodecRegistry::default()
+    }
+    /// Adds a parser; where parsers handle the same sample entry code, the one registered first
+    /// is used
+    pub fn register<P: CodecFamilyParser>(&mut self, parser: P) {
+        self.parsers.push(Arc::new(parser))
+    }
+    /// The parser registered for the given sample entry code, if any
+    pub fn parser_for(&self, four_cc: FourCC) -> Option<&dyn CodecFamilyParser> {
+        self.parsers
+            .iter()
+            .find(|p| p.four_ccs().contains(&four_cc))
+            .map(|p| &**p)
+    }
+    /// Parses a single codec value, using a registered parser if one handles the value's leading
+    /// sample entry code
+    pub fn parse(&self, codec: &str) -> Result<Codec, CodecError> {
+        let first = codec.split('.').next().unwrap();
+        if first.len() == 4 {
+            if let Some(parser) = self.parser_for(FourCC::from(first.as_bytes())) {
+                return parser.parse(codec).map(|e| Codec::Extension(e.into()));
+            }
+        }
+        codec.parse()
+    }
+    /// Like [`Codec::parse_codecs()`], but using [`parse()`](Self::parse) for each entry
+    pub fn parse_codecs<'a>(
+        &'a self,
+        codecs: &'a str,
+    ) -> impl Iterator<Item = Result<Codec, CodecError>> + 'a {
+        codecs.split(',').map(move |s| self.parse(s.trim()))
+    }
+}
+impl fmt::Debug for CodecRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.parsers.iter().flat_map(|p| p.four_ccs()))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::*;
+
+    /// A parsed value, along with the tag of the parser which produced it
+    #[derive(Debug)]
+    struct Custom(String, &'static str);
+    impl fmt::Display for Custom {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str(&self.0)
+        }
+    }
+    impl CodecExtension for Custom {
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+    }
+
+    struct CustomParser(FourCC, &'static str);
+    impl CodecFamilyParser for CustomParser {
+        fn four_ccs(&self) -> &[FourCC] {
+            std::slice::from_ref(&self.0)
+        }
+        fn parse(&self, codec: &str) -> Result<Box<dyn CodecExtension>, CodecError> {
+            if codec.ends_with('.') {
+                return Err(CodecError::InvalidComponent(codec.into()));
+            }
+            Ok(Box::new(Custom(codec.to_owned(), self.1)))
+        }
+    }
+
+    #[test]
+    fn dispatch() {
+        let mut registry = CodecRegistry::new();
+        registry.register(CustomParser(FourCC(*b"xyz1"), "first"));
+        // registered parsers take precedence over built-in support
+        registry.register(CustomParser(FourCC(*b"Opus"), "opus"));
+        registry.register(CustomParser(FourCC(*b"xyz1"), "second"));
+        let parsed = registry
+            .parser_for(FourCC(*b"xyz1"))
+            .unwrap()
+            .parse("xyz1.1")
+            .unwrap();
+        assert_eq!(parsed.as_any().downcast_ref::<Custom>().unwrap().1, "first");
+
+        let codecs: Vec<_> = registry.parse_codecs("xyz1.2.3, Opus, xyz2.1").collect();
+        assert_matches!(&codecs[0], Ok(Codec::Extension(e)) if e.to_string() == "xyz1.2.3");
+        assert_matches!(&codecs[1], Ok(Codec::Extension(_)));
+        assert_matches!(&codecs[2], Ok(Codec::Other(_)));
+        assert_matches!(
+            registry.parse("xyz1."),
+            Err(CodecError::InvalidComponent(_))
+        );
+
+        let codec = registry.parse("xyz1.2.3").unwrap();
+        assert_eq!(codec.to_string(), "xyz1.2.3");
+        assert_eq!(codec.four_cc(), Some(FourCC(*b"xyz1")));
+        assert_eq!(codec.media_kind(), MediaKind::Unknown);
+        assert_eq!(codec, registry.parse("xyz1.2.3").unwrap());
+        assert_ne!(codec, registry.parse("xyz1.2.4").unwrap());
+        if let Codec::Extension(e) = &codec {
+            let custom = e.downcast_ref::<Custom>().unwrap();
+            assert_eq!(custom.0, "xyz1.2.3");
+            assert_eq!(custom.1, "first");
+        }
+    }
+}
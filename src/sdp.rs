@@ -0,0 +1,224 @@
+//! Conversion to and from the SDP `fmtp` parameters of the RTP payload formats for H.264
+//! (RFC 6184) and H.265 (RFC 7798).
+
+use crate::{Avc1, CodecError};
+#[cfg(feature = "hevc")]
+use crate::{Hevc, HevcSampleEntry, HevcTier};
+use std::str::FromStr;
+
+/// The `profile-level-id` value implied when the parameter is absent (Baseline profile,
+/// level 1.0)
+const DEFAULT_PROFILE_LEVEL_ID: &str = "420010";
+
+/// Splits the parameters of an `fmtp` attribute value like `profile-id=1; level-id=93` into
+/// name and value pairs
+fn fmtp_parameters(fmtp: &str) -> impl Iterator<Item = Result<(&str, &str), CodecError>> {
+    fmtp.split(';')
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .map(|p| {
+            p.split_once('=')
+                .map(|(name, value)| (name.trim(), value.trim()))
+                .ok_or_else(|| CodecError::InvalidParameter(p.into()))
+        })
+}
+
+impl Avc1 {
+    /// Creates a value from the `profile-level-id` SDP parameter, which holds the same three
+    /// bytes as the `avc1` codec string, as six hexadecimal digits.
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::{Avc1, Codec};
+    /// let avc1 = Avc1::from_profile_level_id("42e01f").unwrap();
+    /// assert_eq!(Codec::Avc1(avc1).to_string(), "avc1.42E01F");
+    /// ```
+    pub fn from_profile_level_id(profile_level_id: &str) -> Result<Avc1, CodecError> {
+        Avc1::from_str(profile_level_id)
+    }
+
+    /// Creates a value from the parameters of an H.264 SDP `fmtp` attribute, like
+    /// `packetization-mode=1;profile-level-id=42e01f`.
+    ///
+    /// As RFC 6184 specifies, a missing `profile-level-id` implies `420010` (Baseline profile,
+    /// level 1.0).  Other parameters are ignored.
+    pub fn from_sdp_fmtp(fmtp: &str) -> Result<Avc1, CodecError> {
+        let mut profile_level_id = DEFAULT_PROFILE_LEVEL_ID;
+        for param in fmtp_parameters(fmtp) {
+            let (name, value) = param?;
+            if name.eq_ignore_ascii_case("profile-level-id") {
+                profile_level_id = value;
+            }
+        }
+        Avc1::from_profile_level_id(profile_level_id)
+    }
+
+    /// The value for the `profile-level-id` SDP parameter, using the lowercase hexadecimal
+    /// digits which RFC 6184 examples use
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::Codec;
+    /// # use std::str::FromStr;
+    /// if let Ok(Codec::Avc1(avc1)) = Codec::from_str("avc1.4D401E") {
+    ///     assert_eq!(avc1.profile_level_id(), "4d401e");
+    /// }
+    /// ```
+    pub fn profile_level_id(&self) -> String {
+        format!(
+            "{:02x}{:02x}{:02x}",
+            self.profile, self.constraints, self.level
+        )
+    }
+}
+
+#[cfg(feature = "hevc")]
+impl Hevc {
+    /// Creates a value from the parameters of an H.265 SDP `fmtp` attribute, like
+    /// `profile-id=1;level-id=93;sprop-vps=...`.
+    ///
+    /// Absent parameters take the defaults given by RFC 7798 (so for example Main profile and
+    /// level 3.1), and where `profile-compatibility-indicator` is absent, only the flag for the
+    /// `profile-id` value is set.  Parameters other than the profile, tier and level ones are
+    /// ignored.  SDP does not indicate the sample entry code, so this must be given too.
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::{Codec, Hevc, HevcSampleEntry};
+    /// let fmtp = "profile-id=2; tier-flag=0; level-id=153; interop-constraints=B00000000000";
+    /// let hevc = Hevc::from_sdp_fmtp(HevcSampleEntry::Hvc1, fmtp).unwrap();
+    /// assert_eq!(Codec::Hevc(hevc).to_string(), "hvc1.2.4.L153.B0");
+    /// ```
+    pub fn from_sdp_fmtp(sample_entry: HevcSampleEntry, fmtp: &str) -> Result<Hevc, CodecError> {
+        let mut profile_space = 0;
+        let mut tier = HevcTier::Main;
+        let mut profile_idc = 1;
+        let mut level_idc = 93;
+        let mut constraint_indicator_flags = [0; 6];
+        let mut profile_compatibility_flags = None;
+        for param in fmtp_parameters(fmtp) {
+            let (name, value) = param?;
+            let invalid = || CodecError::InvalidComponent(value.into());
+            let decimal = |max: u8| match u8::from_str(value) {
+                Ok(v) if v <= max => Ok(v),
+                _ => Err(invalid()),
+            };
+            match name.to_ascii_lowercase().as_str() {
+                "profile-space" => profile_space = decimal(3)?,
+                "tier-flag" => {
+                    tier = if decimal(1)? == 1 {
+                        HevcTier::High
+                    } else {
+                        HevcTier::Main
+                    }
+                }
+                "profile-id" => profile_idc = decimal(31)?,
+                "level-id" => level_idc = decimal(255)?,
+                "interop-constraints" => {
+                    let v = hex(value, 12).ok_or_else(invalid)?;
+                    constraint_indicator_flags.copy_from_slice(&v.to_be_bytes()[2..]);
+                }
+                "profile-compatibility-indicator" => {
+                    let v = hex(value, 8).ok_or_else(invalid)? as u32;
+                    // the indicator gives general_profile_compatibility_flag[0] first
+                    profile_compatibility_flags = Some(v.reverse_bits());
+                }
+                _ => {}
+            }
+        }
+        Ok(Hevc::new(
+            sample_entry,
+            profile_space,
+            profile_idc,
+            profile_compatibility_flags.unwrap_or(1 << profile_idc),
+            tier,
+            level_idc,
+            constraint_indicator_flags,
+        ))
+    }
+
+    /// The profile, tier and level parameters for an H.265 SDP `fmtp` attribute, like
+    /// `profile-space=0;profile-id=1;tier-flag=0;level-id=93;interop-constraints=B00000000000;profile-compatibility-indicator=60000000`
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::Codec;
+    /// # use std::str::FromStr;
+    /// if let Ok(Codec::Hevc(hevc)) = Codec::from_str("hev1.2.4.H153.90") {
+    ///     assert_eq!(
+    ///         hevc.sdp_fmtp(),
+    ///         "profile-space=0;profile-id=2;tier-flag=1;level-id=153;\
+    ///          interop-constraints=900000000000;profile-compatibility-indicator=20000000"
+    ///     );
+    /// }
+    /// ```
+    pub fn sdp_fmtp(&self) -> String {
+        let mut constraints = String::new();
+        for b in &self.constraint_indicator_flags {
+            constraints.push_str(&format!("{:02X}", b));
+        }
+        format!(
+            "profile-space={};profile-id={};tier-flag={};level-id={};interop-constraints={};profile-compatibility-indicator={:08X}",
+            self.profile_space,
+            self.profile_idc,
+            (self.tier == HevcTier::High) as u8,
+            self.level_idc,
+            constraints,
+            self.profile_compatibility_flags.reverse_bits()
+        )
+    }
+}
+
+/// Parses exactly `len` hexadecimal digits
+#[cfg(feature = "hevc")]
+fn hex(value: &str, len: usize) -> Option<u64> {
+    if value.len() != len || !value.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    u64::from_str_radix(value, 16).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Codec;
+    use assert_matches::*;
+
+    #[test]
+    fn avc() {
+        let avc1 = Avc1::from_sdp_fmtp("packetization-mode=1; profile-level-id=64001F").unwrap();
+        assert_eq!(avc1.profile_level_id(), "64001f");
+        let avc1 = Avc1::from_sdp_fmtp("packetization-mode=1").unwrap();
+        assert_eq!(Codec::Avc1(avc1).to_string(), "avc1.420010");
+        assert_matches!(
+            Avc1::from_profile_level_id("42e01"),
+            Err(CodecError::UnexpectedLength { .. })
+        );
+        assert_matches!(
+            Avc1::from_sdp_fmtp("packetization-mode"),
+            Err(CodecError::InvalidParameter(_))
+        );
+    }
+
+    #[cfg(feature = "hevc")]
+    #[test]
+    fn hevc() {
+        let hevc = Hevc::from_sdp_fmtp(HevcSampleEntry::Hev1, "").unwrap();
+        assert_eq!(Codec::Hevc(hevc).to_string(), "hev1.1.2.L93");
+        let fmtp = "profile-space=0;profile-id=1;tier-flag=0;level-id=120;\
+            interop-constraints=B00000000000;profile-compatibility-indicator=60000000";
+        let hevc = Hevc::from_sdp_fmtp(HevcSampleEntry::Hvc1, fmtp).unwrap();
+        assert_eq!(hevc.sdp_fmtp(), fmtp);
+        assert_eq!(Codec::Hevc(hevc).to_string(), "hvc1.1.6.L120.B0");
+        for fmtp in &[
+            "profile-id=32",
+            "tier-flag=2",
+            "level-id=x",
+            "interop-constraints=B0",
+            "profile-compatibility-indicator=6000000G",
+        ] {
+            assert_matches!(
+                Hevc::from_sdp_fmtp(HevcSampleEntry::Hvc1, fmtp),
+                Err(CodecError::InvalidComponent(_)),
+                "{}",
+                fmtp
+            );
+        }
+    }
+}
@@ -0,0 +1,145 @@
+//! Serde support, enabled by the `serde` cargo feature.
+//!
+//! All values are serialized using the codec string form, so for example an [`Avc1`] value is
+//! written as `"avc1.4D401E"`.  Deserializing one of the sub-types (rather than [`Codec`])
+//! fails if the string is a valid codec string for some other kind of codec.
+
+#[cfg(feature = "dolby")]
+use crate::Ac4;
+#[cfg(feature = "av1")]
+use crate::Av01;
+#[cfg(feature = "dts")]
+use crate::Dts;
+#[cfg(feature = "hevc")]
+use crate::Hevc;
+#[cfg(feature = "mpegh")]
+use crate::Mpegh;
+#[cfg(feature = "text")]
+use crate::Stpp;
+#[cfg(feature = "vp9")]
+use crate::Vp09;
+use crate::{Apv1, Avc1, Avs3, Codec, Lvc1, Mp4a, Pcm, Uncv};
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+use serde::ser::{Serialize, Serializer};
+use std::fmt;
+
+struct CodecVisitor;
+impl<'de> Visitor<'de> for CodecVisitor {
+    type Value = Codec;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a codec string")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Codec, E> {
+        value
+            .parse()
+            .map_err(|e| E::custom(format!("invalid codec string {:?}: {:?}", value, e)))
+    }
+}
+
+impl Serialize for Codec {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+impl<'de> Deserialize<'de> for Codec {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(CodecVisitor)
+    }
+}
+
+impl Serialize for Avc1 {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&format_args!(
+            "avc1.{:02X}{:02X}{:02X}",
+            self.profile, self.constraints, self.level
+        ))
+    }
+}
+
+/// Implements `Serialize` by writing the given prefix followed by the `Display` form of the
+/// sub-type, and `Deserialize` by parsing a `Codec` and requiring the given variant
+macro_rules! codec_variant_serde {
+    ($t:ident, $prefix:expr) => {
+        impl Serialize for $t {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_str(&format_args!("{}{}", $prefix, self))
+            }
+        }
+        codec_variant_serde!($t);
+    };
+    ($t:ident) => {
+        impl<'de> Deserialize<'de> for $t {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                match deserializer.deserialize_str(CodecVisitor)? {
+                    Codec::$t(value) => Ok(value),
+                    other => Err(de::Error::custom(format!(
+                        "expected {} codec string, got {:?}",
+                        stringify!($t),
+                        other.to_string()
+                    ))),
+                }
+            }
+        }
+    };
+}
+
+codec_variant_serde!(Avc1);
+#[cfg(feature = "hevc")]
+codec_variant_serde!(Hevc, "");
+#[cfg(feature = "av1")]
+codec_variant_serde!(Av01, "av01.");
+#[cfg(feature = "vp9")]
+codec_variant_serde!(Vp09, "vp09.");
+codec_variant_serde!(Mp4a, "mp4a.");
+codec_variant_serde!(Lvc1, "lvc1.");
+codec_variant_serde!(Uncv, "uncv");
+codec_variant_serde!(Avs3, "avs3.");
+codec_variant_serde!(Apv1, "apv1");
+#[cfg(feature = "dolby")]
+codec_variant_serde!(Ac4, "ac-4.");
+#[cfg(feature = "dts")]
+codec_variant_serde!(Dts, "");
+#[cfg(feature = "mpegh")]
+codec_variant_serde!(Mpegh, "");
+codec_variant_serde!(Pcm, "");
+#[cfg(feature = "text")]
+codec_variant_serde!(Stpp, "stpp");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codec() {
+        let codecs: Vec<Codec> =
+            serde_json::from_str(r#"["avc1.4d401e", "mp4a.40.2", "vvc1.1.L51.CQA"]"#).unwrap();
+        assert_eq!(
+            serde_json::to_string(&codecs).unwrap(),
+            r#"["avc1.4D401E","mp4a.40.2","vvc1.1.L51.CQA"]"#
+        );
+        assert!(serde_json::from_str::<Codec>(r#""avc1.4D40""#).is_err());
+        assert!(serde_json::from_str::<Codec>("3").is_err());
+    }
+
+    #[test]
+    fn sub_types() {
+        let avc1: Avc1 = serde_json::from_str(r#""avc1.64001f""#).unwrap();
+        assert_eq!(serde_json::to_string(&avc1).unwrap(), r#""avc1.64001F""#);
+        #[cfg(feature = "hevc")]
+        let hevc: Hevc = serde_json::from_str(r#""hev1.1.6.L93.B0""#).unwrap();
+        #[cfg(feature = "hevc")]
+        assert_eq!(
+            serde_json::to_string(&hevc).unwrap(),
+            r#""hev1.1.6.L93.B0""#
+        );
+        let mp4a: Mp4a = serde_json::from_str(r#""mp4a.40.5""#).unwrap();
+        assert_eq!(serde_json::to_string(&mp4a).unwrap(), r#""mp4a.40.5""#);
+        #[cfg(feature = "text")]
+        let stpp: Stpp = serde_json::from_str(r#""stpp.ttml.im1t""#).unwrap();
+        #[cfg(feature = "text")]
+        assert_eq!(serde_json::to_string(&stpp).unwrap(), r#""stpp.ttml.im1t""#);
+        assert!(serde_json::from_str::<Avc1>(r#""mp4a.40.2""#).is_err());
+    }
+}
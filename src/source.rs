@@ -0,0 +1,110 @@
+//! Keeping the original text of a parsed codec value.
+
+use crate::{Codec, CodecError};
+use std::fmt;
+use std::ops::Deref;
+use std::str::FromStr;
+
+/// A parsed codec value which retains the exact text it was parsed from.
+///
+/// Parsing into a [`Codec`] normalises some details of the input (for example the letter case
+/// of hexadecimal elements), so tools which need to echo the input back verbatim can parse
+/// into this type instead.  The value dereferences to the parsed [`Codec`], and the `Display`
+/// implementation writes the original text.
+///
+/// ```rust
+/// # use rfc6381_codec::{Codec, SourcedCodec};
+/// # use std::str::FromStr;
+/// let codec = SourcedCodec::from_str("avc1.4d401e").unwrap();
+/// assert_eq!(codec.source(), "avc1.4d401e");
+/// assert_eq!(codec.to_string(), "avc1.4d401e");
+/// assert_eq!(codec.codec().to_string(), "avc1.4D401E");
+/// assert!(!codec.is_normalised());
+/// assert!(matches!(*codec, Codec::Avc1(_)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SourcedCodec {
+    codec: Codec,
+    source: String,
+}
+impl SourcedCodec {
+    /// The text from which the value was parsed
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+    pub fn codec(&self) -> &Codec {
+        &self.codec
+    }
+    pub fn into_codec(self) -> Codec {
+        self.codec
+    }
+    /// Splits the value into the parsed codec and the text it was parsed from
+    pub fn into_parts(self) -> (Codec, String) {
+        (self.codec, self.source)
+    }
+    /// `true` if the original text is the same as the `Display` form of the parsed codec
+    pub fn is_normalised(&self) -> bool {
+        self.codec.to_string() == self.source
+    }
+}
+impl Deref for SourcedCodec {
+    type Target = Codec;
+
+    fn deref(&self) -> &Codec {
+        &self.codec
+    }
+}
+impl FromStr for SourcedCodec {
+    type Err = CodecError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Ok(SourcedCodec {
+            codec: value.parse()?,
+            source: value.to_owned(),
+        })
+    }
+}
+impl fmt::Display for SourcedCodec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+impl Codec {
+    /// Like [`parse_codecs()`](Self::parse_codecs), but each entry retains the text it was
+    /// parsed from (with the whitespace surrounding list entries removed).
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::Codec;
+    /// let sources: Vec<_> = Codec::parse_codecs_sourced("hvc1.1.6.L93.b0, mp4a.40.02")
+    ///     .map(|c| c.unwrap().source().to_owned())
+    ///     .collect();
+    /// assert_eq!(sources, ["hvc1.1.6.L93.b0", "mp4a.40.02"]);
+    /// ```
+    pub fn parse_codecs_sourced(
+        codecs: &str,
+    ) -> impl Iterator<Item = Result<SourcedCodec, CodecError>> + '_ {
+        codecs.split(',').map(|s| s.trim().parse())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::*;
+
+    #[test]
+    fn sourced() {
+        let codec = SourcedCodec::from_str("mp4a.6b").unwrap();
+        assert_matches!(codec.codec(), Codec::Mp4a(_));
+        assert_eq!(codec.to_string(), "mp4a.6b");
+        assert!(SourcedCodec::from_str("mp4a.40.2").unwrap().is_normalised());
+        let (codec, source) = SourcedCodec::from_str("Opus").unwrap().into_parts();
+        assert_matches!(codec, Codec::Opus);
+        assert_eq!(source, "Opus");
+        assert_matches!(
+            SourcedCodec::from_str("avc1.4d40"),
+            Err(CodecError::UnexpectedLength { .. })
+        );
+    }
+}
@@ -0,0 +1,441 @@
+//! Creating codec values from the sample entries of an ISO BMFF `stsd` box.
+
+#[cfg(feature = "av1")]
+use crate::Av01;
+#[cfg(feature = "vp9")]
+use crate::Vp09;
+use crate::{Avc1, Codec, CodecError, CodecList, ErrorText, Mp4a};
+#[cfg(feature = "hevc")]
+use crate::{Hevc, HevcSampleEntry};
+use four_cc::FourCC;
+use mp4ra_rust::{HandlerCode, SampleEntryCode};
+use std::str::FromStr;
+
+/// The length of the `VisualSampleEntry` fields preceding any child boxes
+const VISUAL_SAMPLE_ENTRY_LEN: usize = 78;
+/// The length of the `AudioSampleEntry` fields preceding any child boxes
+const AUDIO_SAMPLE_ENTRY_LEN: usize = 28;
+
+impl Codec {
+    /// Creates a value for each sample entry in the payload of an `stsd` box (i.e. the full box
+    /// version and flags, followed by the `entry_count` and the sample entries themselves).
+    ///
+    /// The sub-elements of the codec string are taken from the codec configuration box held by
+    /// the sample entry (for example `avcC` within `avc1`), and for encrypted `encv` / `enca`
+    /// entries, the original sample entry code is taken from the `frma` box.  Sample entries
+    /// which this crate can't describe give [`CodecError::UnsupportedSampleEntry`].  Since
+    /// there is no `avc3` variant, `avc3` entries give `avc3.PPCCLL` values as [`Codec::Other`].
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::Codec;
+    /// let stsd = [
+    ///     0, 0, 0, 0, 0, 0, 0, 1, // version, flags and entry_count
+    ///     0, 0, 0, 16, b'f', b'L', b'a', b'C', 0, 0, 0, 0, 0, 0, 0, 1,
+    /// ];
+    /// let codecs = Codec::from_stsd(&stsd).unwrap();
+    /// assert_eq!(codecs.codecs()[0].to_string(), "fLaC");
+    /// ```
+    pub fn from_stsd(stsd: &[u8]) -> Result<CodecList, CodecError> {
+        if stsd.len() < 8 {
+            return Err(CodecError::ConfigurationRecordTooShort {
+                expected: 8,
+                got: stsd.len(),
+            });
+        }
+        sample_entries(stsd, None)
+    }
+
+    /// Creates a value for each sample entry of each track in the given initialization
+    /// segment (or any other file having a `moov` box), in the order of the tracks.
+    ///
+    /// The `Display` implementation of the resulting list gives the comma-separated codecs
+    /// parameter value for a manifest.  The handler type in each track's `hdlr` box decides
+    /// whether its sample entries are laid out as visual or audio sample entries.
+    pub fn from_init_segment(data: &[u8]) -> Result<CodecList, CodecError> {
+        let moov = find_box(data, b"moov")?
+            .ok_or_else(|| CodecError::UnsupportedSampleEntry("no moov box".into()))?;
+        let mut codecs = CodecList::default();
+        for b in Boxes::new(moov) {
+            let (box_type, trak) = b?;
+            if &box_type != b"trak" {
+                continue;
+            }
+            let mdia = find_box(trak, b"mdia")?;
+            let handler = match mdia {
+                Some(mdia) => find_box(mdia, b"hdlr")?.and_then(handler_type),
+                None => None,
+            };
+            let mut stsd = mdia;
+            for path in &[b"minf", b"stbl", b"stsd"] {
+                stsd = match stsd {
+                    Some(data) => find_box(data, path)?,
+                    None => None,
+                };
+            }
+            if let Some(stsd) = stsd {
+                if stsd.len() < 8 {
+                    return Err(CodecError::ConfigurationRecordTooShort {
+                        expected: 8,
+                        got: stsd.len(),
+                    });
+                }
+                for codec in sample_entries(stsd, handler)? {
+                    codecs.push(codec);
+                }
+            }
+        }
+        Ok(codecs)
+    }
+}
+
+/// Describes each sample entry in the given `stsd` payload, which must be at least 8 bytes long.
+/// The sample entry layout is chosen from the track's handler type if known, and otherwise from
+/// the sample entry code.
+fn sample_entries(stsd: &[u8], handler: Option<HandlerCode>) -> Result<CodecList, CodecError> {
+    Boxes::new(&stsd[8..])
+        .map(|entry| {
+            let (box_type, body) = entry?;
+            let visual = match handler {
+                Some(handler) => is_visual_handler(handler),
+                None => is_visual(&box_type),
+            };
+            sample_entry(box_type, body, visual)
+        })
+        .collect()
+}
+
+fn sample_entry(box_type: [u8; 4], body: &[u8], visual: bool) -> Result<Codec, CodecError> {
+    let fourcc = ErrorText::from(&*String::from_utf8_lossy(&box_type));
+    let unsupported = || CodecError::UnsupportedSampleEntry(fourcc);
+    let entry_len = if visual {
+        VISUAL_SAMPLE_ENTRY_LEN
+    } else {
+        audio_entry_len(body)
+    };
+    let children = body.get(entry_len..).unwrap_or(&[]);
+    let format = match &box_type {
+        b"encv" | b"enca" => original_format(children)?,
+        _ => box_type,
+    };
+    let config = |config_type: &[u8; 4]| find_box(children, config_type)?.ok_or_else(unsupported);
+    match &format {
+        b"avc1" => Ok(Codec::Avc1(Avc1::from_avcc(config(b"avcC")?)?)),
+        // there is no separate variant for avc3, so as for parse_webcodecs(), the value is given
+        // as Codec::Other
+        b"avc3" => {
+            let avc = Avc1::from_avcc(config(b"avcC")?)?;
+            let value = format!(
+                "avc3.{:02X}{:02X}{:02X}",
+                avc.profile(),
+                avc.constraints(),
+                avc.level()
+            );
+            Ok(Codec::Other(value.into()))
+        }
+        #[cfg(feature = "hevc")]
+        b"hvc1" => Ok(Codec::Hevc(Hevc::from_hvcc(
+            HevcSampleEntry::Hvc1,
+            config(b"hvcC")?,
+        )?)),
+        #[cfg(feature = "hevc")]
+        b"hev1" => Ok(Codec::Hevc(Hevc::from_hvcc(
+            HevcSampleEntry::Hev1,
+            config(b"hvcC")?,
+        )?)),
+        #[cfg(feature = "av1")]
+        b"av01" => Ok(Codec::Av01(Av01::from_av1c(config(b"av1C")?)?)),
+        #[cfg(feature = "vp9")]
+        b"vp09" => Ok(Codec::Vp09(Vp09::from_vpcc(config(b"vpcC")?)?)),
+        b"mp4a" => Ok(Codec::Mp4a(Mp4a::from_esds(config(b"esds")?)?)),
+        b"Opus" => Codec::from_dops(config(b"dOps")?),
+        _ => {
+            // sample entries identified by the sample entry code alone
+            let fourcc = String::from_utf8_lossy(&format);
+            match Codec::from_str(&fourcc) {
+                Ok(Codec::Other(_)) | Err(_) => Err(unsupported()),
+                Ok(codec) => Ok(codec),
+            }
+        }
+    }
+}
+
+/// Whether the sample entry code is one registered for use with a visual handler type, and so
+/// has a `VisualSampleEntry` layout.  Some visual codes this crate knows about are missing from
+/// the MP4RA data, so are listed here.
+fn is_visual(box_type: &[u8; 4]) -> bool {
+    matches!(
+        box_type,
+        b"encv" | b"vvc1" | b"vvi1" | b"apv1" | b"uncv" | b"lvc1"
+    ) || matches!(
+        SampleEntryCode::from(FourCC(*box_type)).handler(),
+        Some(handler) if is_visual_handler(handler)
+    )
+}
+
+fn is_visual_handler(handler: HandlerCode) -> bool {
+    matches!(
+        handler,
+        HandlerCode::VIDE | HandlerCode::AUXV | HandlerCode::PICT
+    )
+}
+
+/// Reads the `handler_type` from the body of an `hdlr` box
+fn handler_type(hdlr: &[u8]) -> Option<HandlerCode> {
+    let handler_type = hdlr.get(8..12)?;
+    Some(HandlerCode::from(FourCC::from(handler_type)))
+}
+
+/// The length of the `AudioSampleEntry` fields for the given sample entry body, allowing for
+/// the additional fields of QuickTime version 1 and 2 sound sample descriptions
+fn audio_entry_len(body: &[u8]) -> usize {
+    match body.get(8..10) {
+        Some([0, 1]) => AUDIO_SAMPLE_ENTRY_LEN + 16,
+        Some([0, 2]) => AUDIO_SAMPLE_ENTRY_LEN + 36,
+        _ => AUDIO_SAMPLE_ENTRY_LEN,
+    }
+}
+
+/// Finds the sample entry code of the unencrypted media, from `sinf` / `frma`
+fn original_format(children: &[u8]) -> Result<[u8; 4], CodecError> {
+    let frma = match find_box(children, b"sinf")? {
+        Some(sinf) => find_box(sinf, b"frma")?,
+        None => None,
+    };
+    let mut format = [0; 4];
+    match frma {
+        Some(frma) if frma.len() >= 4 => {
+            format.copy_from_slice(&frma[..4]);
+            Ok(format)
+        }
+        _ => Err(CodecError::UnsupportedSampleEntry("no frma box".into())),
+    }
+}
+
+/// Returns the body of the first box of the given type
+fn find_box<'a>(data: &'a [u8], box_type: &[u8; 4]) -> Result<Option<&'a [u8]>, CodecError> {
+    for b in Boxes::new(data) {
+        let (t, body) = b?;
+        if &t == box_type {
+            return Ok(Some(body));
+        }
+    }
+    Ok(None)
+}
+
+/// Iterates over the type and body of each box within the given data
+struct Boxes<'a> {
+    data: &'a [u8],
+}
+impl<'a> Boxes<'a> {
+    fn new(data: &'a [u8]) -> Boxes<'a> {
+        Boxes { data }
+    }
+}
+impl<'a> Iterator for Boxes<'a> {
+    type Item = Result<([u8; 4], &'a [u8]), CodecError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.data.is_empty() {
+            return None;
+        }
+        let data = self.data;
+        let too_short = |expected| {
+            Some(Err(CodecError::ConfigurationRecordTooShort {
+                expected,
+                got: data.len(),
+            }))
+        };
+        if data.len() < 8 {
+            self.data = &[];
+            return too_short(8);
+        }
+        let mut box_type = [0; 4];
+        box_type.copy_from_slice(&data[4..8]);
+        let (header_len, size) = match u32::from_be_bytes([data[0], data[1], data[2], data[3]]) {
+            // the box extends to the end of the data
+            0 => (8, data.len() as u64),
+            1 => {
+                if data.len() < 16 {
+                    self.data = &[];
+                    return too_short(16);
+                }
+                let mut size = [0; 8];
+                size.copy_from_slice(&data[8..16]);
+                (16, u64::from_be_bytes(size))
+            }
+            size => (8, u64::from(size)),
+        };
+        if size < header_len as u64 || size > data.len() as u64 {
+            self.data = &[];
+            return too_short(size.max(header_len as u64) as usize);
+        }
+        let size = size as usize;
+        self.data = &data[size..];
+        Some(Ok((box_type, &data[header_len..size])))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::*;
+
+    fn make_box(box_type: &[u8; 4], body: &[u8]) -> Vec<u8> {
+        let mut b = (body.len() as u32 + 8).to_be_bytes().to_vec();
+        b.extend_from_slice(box_type);
+        b.extend_from_slice(body);
+        b
+    }
+
+    fn visual_entry(box_type: &[u8; 4], children: &[u8]) -> Vec<u8> {
+        let mut body = vec![0; VISUAL_SAMPLE_ENTRY_LEN];
+        // 72 dpi horizresolution and vertresolution, which would be read as an oversized box
+        // if the entry were mistaken for an audio sample entry
+        body[28..32].copy_from_slice(&0x0048_0000u32.to_be_bytes());
+        body[32..36].copy_from_slice(&0x0048_0000u32.to_be_bytes());
+        body.extend_from_slice(children);
+        make_box(box_type, &body)
+    }
+
+    fn audio_entry(box_type: &[u8; 4], children: &[u8]) -> Vec<u8> {
+        let mut body = vec![0; AUDIO_SAMPLE_ENTRY_LEN];
+        body.extend_from_slice(children);
+        make_box(box_type, &body)
+    }
+
+    fn stsd(entries: &[Vec<u8>]) -> Vec<u8> {
+        let mut stsd = vec![0, 0, 0, 0];
+        stsd.extend_from_slice(&(entries.len() as u32).to_be_bytes());
+        for entry in entries {
+            stsd.extend_from_slice(entry);
+        }
+        stsd
+    }
+
+    fn to_strings(codecs: CodecList) -> Vec<String> {
+        codecs.iter().map(Codec::to_string).collect()
+    }
+
+    #[cfg(all(feature = "dolby", feature = "hevc"))]
+    #[test]
+    fn sample_entries() {
+        let avcc = make_box(b"avcC", &[0x01, 0x64, 0x00, 0x1f, 0xff, 0xe0, 0x00]);
+        let hvcc = make_box(
+            b"hvcC",
+            &[0x01, 0x01, 0x60, 0, 0, 0, 0x90, 0, 0, 0, 0, 0, 0x5d],
+        );
+        let esds = make_box(
+            b"esds",
+            &[
+                0, 0, 0, 0, 0x03, 0x16, 0x00, 0x01, 0x00, 0x04, 0x11, 0x40, 0x15, 0, 0, 0, 0, 0, 0,
+                0, 0, 0, 0, 0, 0x05, 0x02, 0x12, 0x10,
+            ],
+        );
+        let stsd = stsd(&[
+            visual_entry(b"avc1", &avcc),
+            visual_entry(b"avc3", &avcc),
+            visual_entry(b"hev1", &hvcc),
+            audio_entry(b"mp4a", &esds),
+            audio_entry(b"ac-3", &make_box(b"dac3", &[0x10, 0x3d, 0xe0])),
+        ]);
+        assert_eq!(
+            to_strings(Codec::from_stsd(&stsd).unwrap()),
+            [
+                "avc1.64001F",
+                "avc3.64001F",
+                "hev1.1.6.L93.90",
+                "mp4a.40.2",
+                "ac-3"
+            ]
+        );
+    }
+
+    #[test]
+    fn encrypted() {
+        let avcc = make_box(b"avcC", &[0x01, 0x4d, 0x40, 0x1e]);
+        let sinf = make_box(b"sinf", &make_box(b"frma", b"avc1"));
+        let entry = visual_entry(b"encv", &[sinf, avcc].concat());
+        assert_eq!(
+            to_strings(Codec::from_stsd(&stsd(&[entry])).unwrap()),
+            ["avc1.4D401E"]
+        );
+        let entry = visual_entry(b"encv", &make_box(b"avcC", &[0x01, 0x4d, 0x40, 0x1e]));
+        assert_matches!(
+            Codec::from_stsd(&stsd(&[entry])),
+            Err(CodecError::UnsupportedSampleEntry(_))
+        );
+    }
+
+    #[test]
+    fn unsupported() {
+        // no avcC box
+        assert_matches!(
+            Codec::from_stsd(&stsd(&[visual_entry(b"avc1", &[])])),
+            Err(CodecError::UnsupportedSampleEntry(_))
+        );
+        assert_matches!(
+            Codec::from_stsd(&stsd(&[visual_entry(b"vvc1", &[])])),
+            Err(CodecError::UnsupportedSampleEntry(_))
+        );
+        // visual sample entries with codes that the original list of visual codes missed
+        assert_eq!(
+            to_strings(Codec::from_stsd(&stsd(&[visual_entry(b"apv1", &[])])).unwrap()),
+            ["apv1"]
+        );
+        for code in &[b"avs3", b"dvh1"] {
+            assert_matches!(
+                Codec::from_stsd(&stsd(&[visual_entry(code, &[])])),
+                Err(CodecError::UnsupportedSampleEntry(_))
+            );
+        }
+        let mut truncated = stsd(&[audio_entry(b"fLaC", &[])]);
+        truncated.pop();
+        assert_matches!(
+            Codec::from_stsd(&truncated),
+            Err(CodecError::ConfigurationRecordTooShort { .. })
+        );
+    }
+
+    #[test]
+    fn init_segment() {
+        let track = |handler: &[u8; 4], entry: Vec<u8>| {
+            let stsd = make_box(b"stsd", &stsd(&[entry]));
+            let stbl = make_box(b"stbl", &stsd);
+            let minf = make_box(b"minf", &[make_box(b"vmhd", &[0; 12]), stbl].concat());
+            let hdlr = make_box(b"hdlr", &[&[0; 8][..], handler, &[0; 13]].concat());
+            let mdia = make_box(b"mdia", &[make_box(b"mdhd", &[0; 24]), hdlr, minf].concat());
+            make_box(b"trak", &[make_box(b"tkhd", &[0; 84]), mdia].concat())
+        };
+        let avcc = make_box(b"avcC", &[0x01, 0x64, 0x00, 0x28]);
+        let moov = make_box(
+            b"moov",
+            &[
+                make_box(b"mvhd", &[0; 100]),
+                track(b"vide", visual_entry(b"avc1", &avcc)),
+                track(
+                    b"soun",
+                    audio_entry(
+                        b"Opus",
+                        &make_box(b"dOps", &[0, 2, 1, 56, 0, 0, 0xbb, 0x80, 0, 0, 0]),
+                    ),
+                ),
+            ]
+            .concat(),
+        );
+        let init = [make_box(b"ftyp", b"iso6\0\0\0\0"), moov].concat();
+        assert_eq!(
+            Codec::from_init_segment(&init).unwrap().to_string(),
+            "avc1.640028,Opus"
+        );
+        assert_matches!(
+            Codec::from_init_segment(&make_box(b"ftyp", b"iso6\0\0\0\0")),
+            Err(CodecError::UnsupportedSampleEntry(_))
+        );
+        // the handler type decides the layout of a sample entry with an unregistered code
+        let moov = make_box(b"moov", &track(b"vide", visual_entry(b"xyz1", &[])));
+        assert_matches!(
+            Codec::from_init_segment(&moov),
+            Err(CodecError::UnsupportedSampleEntry(_))
+        );
+    }
+}
@@ -0,0 +1,124 @@
+//! Support for timed-text codec strings which carry sub-parameters.
+
+use crate::CodecError;
+use std::fmt;
+use std::str::FromStr;
+
+/// The `stpp` (XML subtitle, usually TTML / IMSC) codec parameter.
+///
+/// A bare `stpp` value is valid, but the value may also identify the document namespace and
+/// the profiles to which the documents conform, as in `stpp.ttml.im1t`.  Multiple profiles are
+/// separated by `'+'`, as in `stpp.ttml.im1t+etd1`.
+///
+/// ```rust
+/// # use rfc6381_codec::Codec;
+/// # use std::str::FromStr;
+/// if let Ok(Codec::Stpp(stpp)) = Codec::from_str("stpp.ttml.im1t") {
+///     assert_eq!(stpp.namespace(), Some("ttml"));
+///     assert_eq!(stpp.profiles(), &["im1t".to_string()]);
+/// } else {
+///     panic!("unexpected codec type");
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Stpp {
+    pub(crate) namespace: Option<String>,
+    pub(crate) profiles: Vec<String>,
+}
+impl Stpp {
+    /// Creates a value for TTML documents conforming to the given profiles, like `im1t`.
+    pub fn ttml(profiles: &[&str]) -> Stpp {
+        Stpp {
+            namespace: Some("ttml".to_string()),
+            profiles: profiles.iter().map(|p| p.to_string()).collect(),
+        }
+    }
+    /// The short name of the document namespace, like `ttml`
+    pub fn namespace(&self) -> Option<&str> {
+        self.namespace.as_deref()
+    }
+    /// The short names of the document profiles, like `im1t`
+    pub fn profiles(&self) -> &[String] {
+        &self.profiles
+    }
+}
+impl FromStr for Stpp {
+    type Err = CodecError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut i = value.split('.');
+        let namespace = check_element(i.next().unwrap())?;
+        let profiles = match i.next() {
+            Some(profiles) => profiles
+                .split('+')
+                .map(|p| check_element(p).map(str::to_string))
+                .collect::<Result<_, _>>()?,
+            None => vec![],
+        };
+        if let Some(extra) = i.next() {
+            return Err(CodecError::InvalidComponent(extra.into()));
+        }
+        Ok(Stpp {
+            namespace: Some(namespace.to_string()),
+            profiles,
+        })
+    }
+}
+/// Formats the optional sub-elements, including the leading `'.'` separator if any are present.
+impl fmt::Display for Stpp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(ref namespace) = self.namespace {
+            write!(f, ".{}", namespace)?;
+            if !self.profiles.is_empty() {
+                write!(f, ".{}", self.profiles.join("+"))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn check_element(value: &str) -> Result<&str, CodecError> {
+    if value.is_empty() || !value.bytes().all(|b| b.is_ascii_alphanumeric()) {
+        Err(CodecError::InvalidComponent(value.into()))
+    } else {
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Codec;
+    use assert_matches::*;
+
+    #[test]
+    fn stpp() {
+        assert_matches!(
+            Codec::from_str("stpp"),
+            Ok(Codec::Stpp(Stpp { namespace: None, ref profiles })) if profiles.is_empty()
+        );
+        assert_matches!(
+            Codec::from_str("stpp.ttml"),
+            Ok(Codec::Stpp(Stpp { namespace: Some(ref ns), ref profiles })) if ns == "ttml" && profiles.is_empty()
+        );
+        assert_matches!(
+            Codec::from_str("stpp.ttml.im1t+etd1"),
+            Ok(Codec::Stpp(Stpp { ref profiles, .. })) if profiles == &["im1t", "etd1"]
+        );
+        for s in &["stpp", "stpp.ttml", "stpp.ttml.im1t", "stpp.ttml.im1t+im1i"] {
+            assert_eq!(*s, Codec::from_str(s).unwrap().to_string());
+        }
+        assert_eq!(
+            "stpp.ttml.im1t",
+            Codec::Stpp(Stpp::ttml(&["im1t"])).to_string()
+        );
+    }
+
+    #[test]
+    fn bad_stpp() {
+        assert_matches!(Codec::from_str("stpp."), Err(_));
+        assert_matches!(Codec::from_str("stpp.ttml."), Err(_));
+        assert_matches!(Codec::from_str("stpp.ttml.im1t+"), Err(_));
+        assert_matches!(Codec::from_str("stpp.ttml.im1t.x"), Err(_));
+    }
+}
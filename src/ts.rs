@@ -0,0 +1,164 @@
+//! Approximating codec values from the stream types of an MPEG-2 Transport Stream program map
+//! table.
+
+use crate::{Avc1, Codec, Mp4a};
+#[cfg(feature = "hevc")]
+use crate::{Hevc, HevcSampleEntry};
+
+const REGISTRATION_DESCRIPTOR_TAG: u8 = 0x05;
+const AVC_VIDEO_DESCRIPTOR_TAG: u8 = 0x28;
+#[cfg(feature = "hevc")]
+const HEVC_VIDEO_DESCRIPTOR_TAG: u8 = 0x38;
+#[cfg(feature = "dolby")]
+const AC3_DESCRIPTOR_TAG: u8 = 0x6a;
+#[cfg(feature = "dolby")]
+const ENHANCED_AC3_DESCRIPTOR_TAG: u8 = 0x7a;
+
+impl Codec {
+    /// Gives a codec value for an elementary stream of the given PMT `stream_type`, taking the
+    /// details from the stream's descriptors (the `ES_info` descriptor loop) where possible.
+    ///
+    /// This allows a codecs attribute to be produced for a transport stream before it has been
+    /// remuxed, but the result is only an approximation:
+    ///
+    ///  - AVC and HEVC streams give `None` unless the `AVC_video_descriptor` or
+    ///    `HEVC_video_descriptor` is present, since the profile and level are otherwise only
+    ///    available from the stream's parameter sets (see [`Avc1::from_sps_nal()`]).  HEVC
+    ///    streams are given the `hvc1` sample entry code.
+    ///  - ADTS AAC streams are assumed to be AAC-LC, and LATM streams are given as `mp4a.40`
+    ///    with no audio-object-type.
+    ///  - Private data streams (`stream_type` `0x06`) are identified from the DVB AC-3 and
+    ///    enhanced AC-3 descriptors, or from a registration descriptor.
+    ///
+    /// Returns `None` for stream types that are not understood.
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::Codec;
+    /// // AVC_video_descriptor giving High profile, level 3.1
+    /// let es_info = [0x28, 0x04, 0x64, 0x00, 0x1f, 0x3f];
+    /// let codec = Codec::from_stream_type(0x1b, &es_info).unwrap();
+    /// assert_eq!(codec.to_string(), "avc1.64001F");
+    /// # #[cfg(feature = "dolby")]
+    /// assert_eq!(Codec::from_stream_type(0x81, &[]).unwrap().to_string(), "ac-3");
+    /// assert!(Codec::from_stream_type(0x1b, &[]).is_none());
+    /// ```
+    pub fn from_stream_type(stream_type: u8, es_info: &[u8]) -> Option<Codec> {
+        let descriptor = |tag| {
+            descriptors(es_info)
+                .find(|(t, _)| *t == tag)
+                .map(|(_, body)| body)
+        };
+        match stream_type {
+            0x03 => Some(Codec::Mp4a(Mp4a::Mpeg1Audio)),
+            0x04 => Some(Codec::Mp4a(Mp4a::Mpeg2Audio)),
+            0x0f => Some(Codec::aac_lc()),
+            0x11 => Some(Codec::Mp4a(Mp4a::Mpeg4Audio {
+                audio_object_type: None,
+            })),
+            0x1b => {
+                let avc = descriptor(AVC_VIDEO_DESCRIPTOR_TAG).filter(|d| d.len() >= 3)?;
+                Some(Codec::Avc1(Avc1 {
+                    profile: avc[0],
+                    // the AVC_compatible_flags share the byte with the constraint flags
+                    constraints: avc[1],
+                    level: avc[2],
+                }))
+            }
+            #[cfg(feature = "hevc")]
+            0x24 => {
+                let hevc = descriptor(HEVC_VIDEO_DESCRIPTOR_TAG).filter(|d| d.len() >= 12)?;
+                Some(Codec::Hevc(Hevc::from_profile_tier_level(
+                    HevcSampleEntry::Hvc1,
+                    &hevc[..12],
+                )))
+            }
+            #[cfg(feature = "dolby")]
+            0x81 => Some(Codec::Ac3),
+            #[cfg(feature = "dolby")]
+            0x87 => Some(Codec::Eac3),
+            0x06 => {
+                #[cfg(feature = "dolby")]
+                if descriptor(AC3_DESCRIPTOR_TAG).is_some() {
+                    return Some(Codec::Ac3);
+                }
+                #[cfg(feature = "dolby")]
+                if descriptor(ENHANCED_AC3_DESCRIPTOR_TAG).is_some() {
+                    return Some(Codec::Eac3);
+                }
+                match descriptor(REGISTRATION_DESCRIPTOR_TAG)?.get(..4)? {
+                    #[cfg(feature = "dolby")]
+                    b"AC-3" => Some(Codec::Ac3),
+                    #[cfg(feature = "dolby")]
+                    b"EAC3" => Some(Codec::Eac3),
+                    b"Opus" => Some(Codec::Opus),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Iterates over the tag and body of each descriptor in a descriptor loop, stopping at any
+/// descriptor that is truncated
+fn descriptors(mut data: &[u8]) -> impl Iterator<Item = (u8, &[u8])> {
+    std::iter::from_fn(move || {
+        let tag = *data.first()?;
+        let len = usize::from(*data.get(1)?);
+        let body = data.get(2..2 + len)?;
+        data = &data[2 + len..];
+        Some((tag, body))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn codec(stream_type: u8, es_info: &[u8]) -> Option<String> {
+        Codec::from_stream_type(stream_type, es_info).map(|c| c.to_string())
+    }
+
+    #[test]
+    fn stream_types() {
+        assert_eq!(codec(0x0f, &[]).unwrap(), "mp4a.40.2");
+        assert_eq!(codec(0x11, &[]).unwrap(), "mp4a.40");
+        assert_eq!(codec(0x03, &[]).unwrap(), "mp4a.6b");
+        assert_eq!(codec(0x04, &[]).unwrap(), "mp4a.69");
+        #[cfg(feature = "dolby")]
+        assert_eq!(codec(0x87, &[]).unwrap(), "ec-3");
+        assert_eq!(codec(0x02, &[]), None);
+        // a language descriptor before the AVC_video_descriptor
+        let es_info = [
+            0x0a, 0x04, b'e', b'n', b'g', 0x00, 0x28, 0x04, 0x4d, 0x40, 0x1e, 0x3f,
+        ];
+        assert_eq!(codec(0x1b, &es_info).unwrap(), "avc1.4D401E");
+        // truncated AVC_video_descriptor
+        assert_eq!(codec(0x1b, &es_info[..10]), None);
+    }
+
+    #[cfg(feature = "hevc")]
+    #[test]
+    fn hevc() {
+        let es_info = [
+            0x38, 0x0d, 0x22, 0x20, 0x00, 0x00, 0x00, 0xb0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x99,
+            0x00,
+        ];
+        assert_eq!(codec(0x24, &es_info).unwrap(), "hvc1.2.4.H153.B0");
+        assert_eq!(codec(0x24, &[]), None);
+    }
+
+    #[test]
+    fn private_data() {
+        #[cfg(feature = "dolby")]
+        assert_eq!(codec(0x06, &[0x6a, 0x01, 0x00]).unwrap(), "ac-3");
+        #[cfg(feature = "dolby")]
+        assert_eq!(codec(0x06, &[0x7a, 0x01, 0x00]).unwrap(), "ec-3");
+        assert_eq!(
+            codec(0x06, &[0x05, 0x04, b'O', b'p', b'u', b's']).unwrap(),
+            "Opus"
+        );
+        assert_eq!(codec(0x06, &[0x05, 0x04, b'K', b'L', b'V', b'A']), None);
+        assert_eq!(codec(0x06, &[]), None);
+    }
+}
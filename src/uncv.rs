@@ -0,0 +1,114 @@
+//! Support for ISO/IEC 23001-17 uncompressed video codec strings.
+
+use crate::CodecError;
+use four_cc::FourCC;
+use mp4ra_rust::SampleEntryCode;
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+pub(crate) const UNCV: SampleEntryCode = SampleEntryCode(FourCC(*b"uncv"));
+
+/// The `uncv` codec parameter.
+///
+/// The optional sub-element gives the four-character-code of the uncompressed video profile
+/// (for example `uncv.rgb3` or `uncv.2vuy`).  A bare `uncv` value, with no profile, is also valid.
+///
+/// ```rust
+/// # use rfc6381_codec::Codec;
+/// # use std::str::FromStr;
+/// let codec = Codec::from_str("uncv.rgb3");
+/// if let Ok(Codec::Uncv(uncv)) = codec {
+///     assert_eq!(uncv.profile().unwrap().to_string(), "rgb3");
+/// } else {
+///     panic!("unexpected codec type");
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Uncv {
+    pub(crate) profile: Option<FourCC>,
+}
+impl Uncv {
+    pub const fn new(profile: Option<FourCC>) -> Uncv {
+        Uncv { profile }
+    }
+    pub fn profile(&self) -> Option<FourCC> {
+        self.profile
+    }
+}
+// FourCC doesn't implement Ord, so compare the bytes instead
+impl PartialOrd for Uncv {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for Uncv {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.profile.map(|p| p.0).cmp(&other.profile.map(|p| p.0))
+    }
+}
+impl FromStr for Uncv {
+    type Err = CodecError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if value.len() != 4 {
+            return Err(CodecError::UnexpectedLength {
+                expected: 4,
+                got: value.into(),
+            });
+        }
+        if !value.bytes().all(|b| b.is_ascii_alphanumeric()) {
+            return Err(CodecError::InvalidComponent(value.into()));
+        }
+        Ok(Uncv {
+            profile: Some(FourCC::from(value.as_bytes())),
+        })
+    }
+}
+/// Formats the optional profile sub-element, including the leading `'.'` separator if a profile
+/// is present.
+impl fmt::Display for Uncv {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(profile) = self.profile {
+            write!(f, ".{}", profile)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Codec;
+    use assert_matches::*;
+
+    #[test]
+    fn uncv() {
+        assert_matches!(
+            Codec::from_str("uncv"),
+            Ok(Codec::Uncv(Uncv { profile: None }))
+        );
+        assert_matches!(
+            Codec::from_str("uncv.2vuy"),
+            Ok(Codec::Uncv(Uncv { profile: Some(FourCC(p)) })) if &p == b"2vuy"
+        );
+        assert_eq!(
+            "uncv.rgb3",
+            Codec::from_str("uncv.rgb3").unwrap().to_string()
+        );
+        assert_eq!("uncv", Codec::from_str("uncv").unwrap().to_string());
+    }
+
+    #[test]
+    fn bad_profile() {
+        assert_matches!(Codec::from_str("uncv."), Err(_));
+        assert_matches!(
+            Codec::from_str("uncv.rgb"),
+            Err(CodecError::UnexpectedLength { expected: 4, .. })
+        );
+        assert_matches!(
+            Codec::from_str("uncv.r-b3"),
+            Err(CodecError::InvalidComponent(_))
+        );
+    }
+}
@@ -0,0 +1,310 @@
+//! Support for VP9 codec strings, as specified by the _VP Codec ISO Media File Format Binding_.
+
+use crate::{parse_decimal_element, CodecError};
+use crate::{ColorPrimaries, MatrixCoefficients, TransferCharacteristics};
+use std::fmt;
+use std::str::FromStr;
+
+/// The `vp09` codec parameter, like `vp09.02.10.10.01.09.16.09.01`.
+///
+/// The mandatory elements give the profile, level and bit depth.  These may be followed by the
+/// optional elements giving chroma subsampling, colour primaries, transfer characteristics,
+/// matrix coefficients and the video full range flag.  The optional elements must either all be
+/// present or all be absent, and when absent the accessors for these values give the default.
+///
+/// ```rust
+/// # use rfc6381_codec::{Codec, TransferCharacteristics, Vp9ChromaSubsampling};
+/// # use std::str::FromStr;
+/// if let Ok(Codec::Vp09(vp09)) = Codec::from_str("vp09.02.10.10.01.09.16.09.01") {
+///     assert_eq!(vp09.profile(), 2);
+///     assert_eq!(vp09.bit_depth(), 10);
+///     assert_eq!(vp09.chroma_subsampling(), Vp9ChromaSubsampling::Yuv420Colocated);
+///     assert_eq!(vp09.transfer_characteristics(), TransferCharacteristics::Pq);
+///     assert!(vp09.video_full_range_flag());
+/// } else {
+///     panic!("unexpected codec type");
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Vp09 {
+    pub(crate) profile: u8,
+    pub(crate) level: u8,
+    pub(crate) bit_depth: u8,
+    pub(crate) chroma_subsampling: Option<Vp9ChromaSubsampling>,
+    pub(crate) color_primaries: Option<ColorPrimaries>,
+    pub(crate) transfer_characteristics: Option<TransferCharacteristics>,
+    pub(crate) matrix_coefficients: Option<MatrixCoefficients>,
+    pub(crate) video_full_range_flag: Option<bool>,
+}
+impl Vp09 {
+    /// Creates a value giving only the mandatory elements.
+    ///
+    /// The `level` value is ten times the VP9 level number, so for example `31` for level 3.1.
+    pub const fn new(profile: u8, level: u8, bit_depth: u8) -> Vp09 {
+        Vp09 {
+            profile,
+            level,
+            bit_depth,
+            chroma_subsampling: None,
+            color_primaries: None,
+            transfer_characteristics: None,
+            matrix_coefficients: None,
+            video_full_range_flag: None,
+        }
+    }
+    pub fn profile(&self) -> u8 {
+        self.profile
+    }
+    pub fn level(&self) -> u8 {
+        self.level
+    }
+    pub fn bit_depth(&self) -> u8 {
+        self.bit_depth
+    }
+    pub fn chroma_subsampling(&self) -> Vp9ChromaSubsampling {
+        self.chroma_subsampling
+            .unwrap_or(Vp9ChromaSubsampling::Yuv420Colocated)
+    }
+    pub fn color_primaries(&self) -> ColorPrimaries {
+        self.color_primaries.unwrap_or(ColorPrimaries::Bt709)
+    }
+    pub fn transfer_characteristics(&self) -> TransferCharacteristics {
+        self.transfer_characteristics
+            .unwrap_or(TransferCharacteristics::Bt709)
+    }
+    pub fn matrix_coefficients(&self) -> MatrixCoefficients {
+        self.matrix_coefficients
+            .unwrap_or(MatrixCoefficients::Bt709)
+    }
+    pub fn video_full_range_flag(&self) -> bool {
+        self.video_full_range_flag.unwrap_or(false)
+    }
+
+    /// Whether the optional elements were given (they are either all present or all absent).
+    pub(crate) fn has_optional_elements(&self) -> bool {
+        self.chroma_subsampling.is_some()
+    }
+
+    /// Creates a value from the payload of a `vpcC` box (i.e. the full box version and flags,
+    /// followed by the `VPCodecConfigurationRecord`).  All the optional elements are populated
+    /// from the record.
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::{Codec, Vp09};
+    /// let vpcc = [
+    ///     0x01, 0x00, 0x00, 0x00, 0x00, 0x1f, 0x80, 0x02, 0x02, 0x02, 0x00, 0x00,
+    /// ];
+    /// let vp09 = Vp09::from_vpcc(&vpcc).unwrap();
+    /// assert_eq!(Codec::Vp09(vp09).to_string(), "vp09.00.31.08.00.02.02.02.00");
+    /// ```
+    pub fn from_vpcc(vpcc: &[u8]) -> Result<Vp09, CodecError> {
+        if vpcc.len() < 12 {
+            return Err(CodecError::ConfigurationRecordTooShort {
+                expected: 12,
+                got: vpcc.len(),
+            });
+        }
+        if vpcc[0] != 1 {
+            return Err(CodecError::UnsupportedConfigurationVersion(vpcc[0]));
+        }
+        let chroma_subsampling = (vpcc[6] >> 1) & 0b111;
+        let chroma_subsampling =
+            Vp9ChromaSubsampling::from_value(chroma_subsampling).ok_or_else(|| {
+                CodecError::InvalidComponent(format_args!("{:02}", chroma_subsampling).into())
+            })?;
+        Ok(Vp09 {
+            profile: vpcc[4],
+            level: vpcc[5],
+            bit_depth: vpcc[6] >> 4,
+            chroma_subsampling: Some(chroma_subsampling),
+            color_primaries: Some(vpcc[7].into()),
+            transfer_characteristics: Some(vpcc[8].into()),
+            matrix_coefficients: Some(vpcc[9].into()),
+            video_full_range_flag: Some(vpcc[6] & 1 != 0),
+        })
+    }
+}
+impl FromStr for Vp09 {
+    type Err = CodecError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut i = value.split('.');
+        let mut next = || {
+            i.next()
+                .ok_or_else(|| CodecError::ExpectedHierarchySeparator(value.into()))
+        };
+        let profile = parse_decimal_element(next()?, 2)?;
+        let level = parse_decimal_element(next()?, 2)?;
+        let bit_depth = parse_decimal_element(next()?, 2)?;
+        let mut vp09 = Vp09::new(profile, level, bit_depth);
+        if let Some(chroma_subsampling) = i.next() {
+            let mut next = || {
+                i.next()
+                    .ok_or_else(|| CodecError::ExpectedHierarchySeparator(value.into()))
+            };
+            let v = parse_decimal_element(chroma_subsampling, 2)?;
+            vp09.chroma_subsampling =
+                Some(Vp9ChromaSubsampling::from_value(v).ok_or_else(|| {
+                    CodecError::InvalidComponent(format_args!("{:02}", v).into())
+                })?);
+            vp09.color_primaries = Some(parse_decimal_element(next()?, 2)?.into());
+            vp09.transfer_characteristics = Some(parse_decimal_element(next()?, 2)?.into());
+            vp09.matrix_coefficients = Some(parse_decimal_element(next()?, 2)?.into());
+            vp09.video_full_range_flag = Some(match parse_decimal_element(next()?, 2)? {
+                0 => false,
+                1 => true,
+                v => {
+                    return Err(CodecError::InvalidComponent(
+                        format_args!("{:02}", v).into(),
+                    ))
+                }
+            });
+        }
+        if i.next().is_some() {
+            return Err(CodecError::InvalidComponent(value.into()));
+        }
+        Ok(vp09)
+    }
+}
+impl fmt::Display for Vp09 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:02}.{:02}.{:02}",
+            self.profile, self.level, self.bit_depth
+        )?;
+        if self.has_optional_elements() {
+            write!(
+                f,
+                ".{:02}.{:02}.{:02}.{:02}.{:02}",
+                u8::from(self.chroma_subsampling()),
+                u8::from(self.color_primaries()),
+                u8::from(self.transfer_characteristics()),
+                u8::from(self.matrix_coefficients()),
+                self.video_full_range_flag() as u8,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// The VP9 `chromaSubsampling` value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Vp9ChromaSubsampling {
+    /// 4:2:0, with chroma samples positioned vertically between luma samples
+    Yuv420Vertical,
+    /// 4:2:0, with chroma samples co-located with luma samples
+    Yuv420Colocated,
+    Yuv422,
+    Yuv444,
+}
+impl Vp9ChromaSubsampling {
+    pub(crate) fn from_value(v: u8) -> Option<Vp9ChromaSubsampling> {
+        match v {
+            0 => Some(Vp9ChromaSubsampling::Yuv420Vertical),
+            1 => Some(Vp9ChromaSubsampling::Yuv420Colocated),
+            2 => Some(Vp9ChromaSubsampling::Yuv422),
+            3 => Some(Vp9ChromaSubsampling::Yuv444),
+            _ => None,
+        }
+    }
+}
+impl From<Vp9ChromaSubsampling> for u8 {
+    fn from(v: Vp9ChromaSubsampling) -> Self {
+        match v {
+            Vp9ChromaSubsampling::Yuv420Vertical => 0,
+            Vp9ChromaSubsampling::Yuv420Colocated => 1,
+            Vp9ChromaSubsampling::Yuv422 => 2,
+            Vp9ChromaSubsampling::Yuv444 => 3,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Codec;
+    use assert_matches::*;
+
+    fn vp09(codec: &str) -> Vp09 {
+        match Codec::from_str(codec) {
+            Ok(Codec::Vp09(vp09)) => vp09,
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parse() {
+        let v = vp09("vp09.00.10.08");
+        assert_eq!(v.profile(), 0);
+        assert_eq!(v.level(), 10);
+        assert_eq!(v.bit_depth(), 8);
+        assert_eq!(
+            v.chroma_subsampling(),
+            Vp9ChromaSubsampling::Yuv420Colocated
+        );
+        assert_eq!(v.color_primaries(), ColorPrimaries::Bt709);
+        assert!(!v.video_full_range_flag());
+
+        let v = vp09("vp09.01.20.08.03.01.01.01.00");
+        assert_eq!(v.chroma_subsampling(), Vp9ChromaSubsampling::Yuv444);
+        assert_eq!(v.matrix_coefficients(), MatrixCoefficients::Bt709);
+    }
+
+    #[test]
+    fn roundtrip() {
+        for s in &[
+            "vp09.00.10.08",
+            "vp09.02.10.10.01.09.16.09.01",
+            "vp09.03.62.12.00.01.01.01.00",
+        ] {
+            assert_eq!(*s, Codec::from_str(s).unwrap().to_string());
+        }
+    }
+
+    #[test]
+    fn bad_vp09() {
+        for s in &[
+            "vp09",
+            "vp09.00",
+            "vp09.00.10",
+            "vp09.0.10.08",
+            "vp09.00.10.8",
+            "vp09.00.10.08.04",
+            "vp09.01.20.08.03",
+            "vp09.02.10.10.01.09.16",
+            "vp09.02.10.10.01.09.16.09",
+            "vp09.00.10.08.01.01.01.01.02",
+            "vp09.00.10.08.01.01.01.01.00.00",
+            "vp09.00.10.08.01.1.01.01.00",
+            "vp09.00.+1.08",
+        ] {
+            assert_matches!(Codec::from_str(s), Err(_), "{}", s);
+        }
+    }
+
+    #[test]
+    fn from_vpcc() {
+        let vpcc = [
+            0x01, 0x00, 0x00, 0x00, 0x02, 0x33, 0xa3, 0x09, 0x10, 0x09, 0x00, 0x00,
+        ];
+        let v = Vp09::from_vpcc(&vpcc).unwrap();
+        assert_eq!(v.bit_depth(), 10);
+        assert_eq!(
+            v.chroma_subsampling(),
+            Vp9ChromaSubsampling::Yuv420Colocated
+        );
+        assert_eq!(Codec::Vp09(v).to_string(), "vp09.02.51.10.01.09.16.09.01");
+        assert_matches!(
+            Vp09::from_vpcc(&vpcc[..11]),
+            Err(CodecError::ConfigurationRecordTooShort {
+                expected: 12,
+                got: 11
+            })
+        );
+        assert_matches!(
+            Vp09::from_vpcc(&[0; 12]),
+            Err(CodecError::UnsupportedConfigurationVersion(0))
+        );
+    }
+}
@@ -0,0 +1,154 @@
+//! Checks against the codec string rules of the W3C _WebCodecs Codec Registry_.
+
+use crate::{Codec, CodecError};
+use std::str::FromStr;
+
+/// The `mp4a` values listed by the AAC and MP3 WebCodecs registrations
+const MP4A_CODECS: [&str; 9] = [
+    "mp4a.40.2",
+    "mp4a.40.02",
+    "mp4a.40.5",
+    "mp4a.40.05",
+    "mp4a.40.29",
+    "mp4a.40.42",
+    "mp4a.67",
+    "mp4a.69",
+    "mp4a.6B",
+];
+
+/// Codec strings which WebCodecs accepts as-is, and which have no sub-elements
+const EXACT_CODECS: [&str; 14] = [
+    "flac", "mp3", "opus", "vorbis", "ulaw", "alaw", "pcm-u8", "pcm-s16", "pcm-s24", "pcm-s32",
+    "pcm-f32", "ac-3", "ec-3", "vp8",
+];
+
+impl Codec {
+    /// Answers whether the given string is a codec string registered for use with WebCodecs.
+    ///
+    /// The WebCodecs registry is stricter than RFC 6381: values are case-sensitive (so `opus`
+    /// rather than `Opus`), only particular `mp4a` values are listed, and `avc1` / `avc3`
+    /// values must take exactly the `avc1.PPCCLL` form.
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::Codec;
+    /// assert!(Codec::is_valid_webcodecs_string("avc1.42001E"));
+    /// assert!(Codec::is_valid_webcodecs_string("opus"));
+    /// assert!(!Codec::is_valid_webcodecs_string("Opus"));
+    /// assert!(!Codec::is_valid_webcodecs_string("avc1.4D40"));
+    /// ```
+    pub fn is_valid_webcodecs_string(codec: &str) -> bool {
+        Codec::parse_webcodecs(codec).is_ok()
+    }
+
+    /// Parses a codec string, checking that it is also valid for WebCodecs (see
+    /// [`Codec::is_valid_webcodecs_string()`]).
+    ///
+    /// Registered values which this crate doesn't otherwise model, like `vp8` or `pcm-s16`, give
+    /// a [`Codec::Other`] value.  Strings which are valid RFC 6381 codecs, but not valid for
+    /// WebCodecs, give [`CodecError::UnsupportedByWebCodecs`].
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::{Codec, CodecError};
+    /// assert!(matches!(Codec::parse_webcodecs("flac"), Ok(Codec::Flac)));
+    /// assert!(matches!(
+    ///     Codec::parse_webcodecs("mp4a.40.34"),
+    ///     Err(CodecError::UnsupportedByWebCodecs(_))
+    /// ));
+    /// ```
+    pub fn parse_webcodecs(codec: &str) -> Result<Codec, CodecError> {
+        let unsupported = || CodecError::UnsupportedByWebCodecs(codec.into());
+        if EXACT_CODECS.contains(&codec) {
+            return Ok(match codec {
+                "flac" => Codec::Flac,
+                "mp3" => Codec::Mp3,
+                "opus" => Codec::Opus,
+                #[cfg(feature = "dolby")]
+                "ac-3" => Codec::Ac3,
+                #[cfg(feature = "dolby")]
+                "ec-3" => Codec::Eac3,
+                _ => Codec::Other(codec.into()),
+            });
+        }
+        if MP4A_CODECS.contains(&codec) {
+            return Codec::from_str(codec);
+        }
+        let (fourcc, rest) = codec.split_once('.').ok_or_else(unsupported)?;
+        match fourcc {
+            "avc1" | "avc3" => {
+                if rest.len() != 6 || !rest.bytes().all(|b| b.is_ascii_hexdigit()) {
+                    return Err(unsupported());
+                }
+                let avc = format!("avc1.{}", rest);
+                match Codec::from_str(&avc)? {
+                    Codec::Avc1(_) if fourcc == "avc3" => Ok(Codec::Other(codec.into())),
+                    result => Ok(result),
+                }
+            }
+            "hvc1" | "hev1" | "av01" | "vp09" => Codec::from_str(codec),
+            _ => Err(unsupported()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Codec, CodecError};
+    use assert_matches::*;
+
+    #[test]
+    fn valid() {
+        for s in &[
+            "avc1.64001f",
+            "avc3.42E01E",
+            "hev1.1.6.L93.B0",
+            "hvc1.2.4.L153.B0.0.0",
+            "av01.0.04M.08",
+            "av01.0.04M.10.0.110.09.16.09.0",
+            "vp09.00.10.08",
+            "vp09.02.10.10.01.09.16.09.01",
+            "vp8",
+            "mp4a.40.02",
+            "mp4a.6B",
+            "pcm-f32",
+            "ulaw",
+            "ec-3",
+        ] {
+            assert!(Codec::is_valid_webcodecs_string(s), "{}", s);
+        }
+        assert_matches!(Codec::parse_webcodecs("avc3.42E01E"), Ok(Codec::Other(s)) if s.as_str() == "avc3.42E01E");
+        assert_matches!(Codec::parse_webcodecs("mp4a.40.02"), Ok(Codec::Mp4a(_)));
+        assert_matches!(Codec::parse_webcodecs("opus"), Ok(Codec::Opus));
+    }
+
+    #[test]
+    fn invalid() {
+        for s in &[
+            "avc1.66.30",
+            "avc1.4D40",
+            "AVC1.4D401E",
+            "avc3.4D40GG",
+            "vp9",
+            "Opus",
+            "fLaC",
+            "mp4a.40",
+            "mp4a.40.34",
+            "mp4a.6b.1",
+            "vvc1.1.L51.CQA",
+            "pcm-s8",
+            "alac",
+        ] {
+            assert!(!Codec::is_valid_webcodecs_string(s), "{}", s);
+        }
+        #[cfg(feature = "vp9")]
+        assert!(!Codec::is_valid_webcodecs_string("vp09.00.10"));
+        assert_matches!(
+            Codec::parse_webcodecs("Opus"),
+            Err(CodecError::UnsupportedByWebCodecs(s)) if s == "Opus"
+        );
+        #[cfg(feature = "vp9")]
+        assert_matches!(
+            Codec::parse_webcodecs("vp09.00.10"),
+            Err(CodecError::ExpectedHierarchySeparator(_))
+        );
+    }
+}
@@ -0,0 +1,280 @@
+//! Mapping between codec values and Matroska / WebM track entries, enabled by the `webm` cargo
+//! feature.
+
+#[cfg(feature = "av1")]
+use crate::Av01;
+use crate::{Avc1, Codec, CodecError, Mp4a};
+#[cfg(feature = "hevc")]
+use crate::{Hevc, HevcSampleEntry};
+#[cfg(feature = "vp9")]
+use crate::{Vp09, Vp9ChromaSubsampling};
+use mpeg4_audio_const::AudioObjectType;
+
+/// The legacy `A_AAC/...` codec IDs, which identify the AAC flavour without needing any
+/// `CodecPrivate` data
+const LEGACY_AAC_CODEC_IDS: [(&str, &str); 9] = [
+    ("A_AAC/MPEG4/MAIN", "mp4a.40.1"),
+    ("A_AAC/MPEG4/LC", "mp4a.40.2"),
+    ("A_AAC/MPEG4/SSR", "mp4a.40.3"),
+    ("A_AAC/MPEG4/LTP", "mp4a.40.4"),
+    ("A_AAC/MPEG4/LC/SBR", "mp4a.40.5"),
+    ("A_AAC/MPEG2/MAIN", "mp4a.66"),
+    ("A_AAC/MPEG2/LC", "mp4a.67"),
+    ("A_AAC/MPEG2/SSR", "mp4a.68"),
+    ("A_AAC/MPEG2/LC/SBR", "mp4a.67"),
+];
+
+impl Codec {
+    /// Creates a value from the `CodecID` and `CodecPrivate` elements of a Matroska or WebM
+    /// track entry.
+    ///
+    /// Where the `CodecPrivate` data is a codec configuration record (as for `V_AV1`,
+    /// `V_MPEG4/ISO/AVC` and `V_MPEGH/ISO/HEVC`, or the `AudioSpecificConfig` of `A_AAC`), the
+    /// sub-elements of the codec string are taken from it.  For `V_VP9`, the profile, level and
+    /// bit depth are taken from the codec features held in `CodecPrivate`, and where the
+    /// chroma subsampling is also given, all the optional elements are included with default
+    /// values for the rest.  HEVC tracks are given the `hvc1` sample entry code.
+    ///
+    /// `V_VP8` and `A_VORBIS` give the `vp8` and `vorbis` values used in WebM codecs
+    /// parameters, as [`Codec::Other`] values.  The legacy codec IDs like
+    /// `A_AAC/MPEG4/LC/SBR`, which identify the AAC object type themselves, are supported
+    /// without any `CodecPrivate` data.
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::Codec;
+    /// assert_eq!(Codec::from_matroska("A_OPUS", &[]).unwrap().to_string(), "Opus");
+    /// // profile 0, level 3.1, 8-bit
+    /// # #[cfg(feature = "vp9")]
+    /// let private = [1, 1, 0, 2, 1, 31, 3, 1, 8];
+    /// # #[cfg(feature = "vp9")]
+    /// let vp9 = Codec::from_matroska("V_VP9", &private).unwrap();
+    /// # #[cfg(feature = "vp9")]
+    /// assert_eq!(vp9.to_string(), "vp09.00.31.08");
+    /// ```
+    pub fn from_matroska(codec_id: &str, codec_private: &[u8]) -> Result<Codec, CodecError> {
+        match codec_id {
+            #[cfg(feature = "vp9")]
+            "V_VP9" => Ok(Codec::Vp09(vp9_features(codec_private)?)),
+            #[cfg(feature = "av1")]
+            "V_AV1" => Ok(Codec::Av01(Av01::from_av1c(codec_private)?)),
+            "V_MPEG4/ISO/AVC" => Ok(Codec::Avc1(Avc1::from_avcc(codec_private)?)),
+            #[cfg(feature = "hevc")]
+            "V_MPEGH/ISO/HEVC" => Ok(Codec::Hevc(Hevc::from_hvcc(
+                HevcSampleEntry::Hvc1,
+                codec_private,
+            )?)),
+            "V_VP8" => Ok(Codec::Other("vp8".into())),
+            "A_OPUS" => Ok(Codec::Opus),
+            "A_VORBIS" => Ok(Codec::Other("vorbis".into())),
+            "A_FLAC" => Ok(Codec::Flac),
+            "A_AAC" => Ok(Codec::Mp4a(Mp4a::from_audio_specific_config(
+                codec_private,
+            )?)),
+            #[cfg(feature = "dolby")]
+            "A_AC3" => Ok(Codec::Ac3),
+            #[cfg(feature = "dolby")]
+            "A_EAC3" => Ok(Codec::Eac3),
+            "A_MPEG/L3" => Ok(Codec::Mp4a(Mp4a::Mpeg1Audio)),
+            "A_ALAC" => Ok(Codec::Alac),
+            #[cfg(feature = "text")]
+            "S_TEXT/WEBVTT" => Ok(Codec::Wvtt),
+            _ => match LEGACY_AAC_CODEC_IDS.iter().find(|(id, _)| *id == codec_id) {
+                Some((_, codec)) => codec.parse(),
+                None => Err(CodecError::UnsupportedSampleEntry(codec_id.into())),
+            },
+        }
+    }
+
+    /// The Matroska `CodecID` for tracks using this codec, or `None` if there is no Matroska
+    /// mapping for it.
+    ///
+    /// AAC flavours all map to `A_AAC` (the legacy IDs like `A_AAC/MPEG4/LC` are deprecated),
+    /// and the MP3 forms of `mp4a` map to `A_MPEG/L3`.
+    ///
+    /// ```rust
+    /// # use rfc6381_codec::Codec;
+    /// # use std::str::FromStr;
+    /// # #[cfg(feature = "hevc")]
+    /// let codec = Codec::from_str("hev1.1.6.L93.B0").unwrap();
+    /// # #[cfg(feature = "hevc")]
+    /// assert_eq!(codec.matroska_codec_id(), Some("V_MPEGH/ISO/HEVC"));
+    /// assert_eq!(Codec::aac_lc().matroska_codec_id(), Some("A_AAC"));
+    /// ```
+    pub fn matroska_codec_id(&self) -> Option<&'static str> {
+        Some(match self {
+            Codec::Avc1(_) => "V_MPEG4/ISO/AVC",
+            #[cfg(feature = "hevc")]
+            Codec::Hevc(_) => "V_MPEGH/ISO/HEVC",
+            #[cfg(feature = "av1")]
+            Codec::Av01(_) => "V_AV1",
+            #[cfg(feature = "vp9")]
+            Codec::Vp09(_) => "V_VP9",
+            Codec::Mp4a(mp4a) if mp4a.is_mp3() => "A_MPEG/L3",
+            Codec::Mp4a(Mp4a::Mpeg4Audio {
+                audio_object_type: Some(aot),
+            }) if is_aac(*aot) => "A_AAC",
+            Codec::Mp4a(Mp4a::Mpeg2Aac { .. }) => "A_AAC",
+            Codec::Mp3 => "A_MPEG/L3",
+            Codec::Opus => "A_OPUS",
+            Codec::Flac => "A_FLAC",
+            Codec::Alac => "A_ALAC",
+            #[cfg(feature = "dolby")]
+            Codec::Ac3 => "A_AC3",
+            #[cfg(feature = "dolby")]
+            Codec::Eac3 => "A_EAC3",
+            #[cfg(feature = "text")]
+            Codec::Wvtt => "S_TEXT/WEBVTT",
+            Codec::Other(val) if val.as_str() == "vp8" => "V_VP8",
+            Codec::Other(val) if val.as_str() == "vorbis" => "A_VORBIS",
+            _ => return None,
+        })
+    }
+}
+
+fn is_aac(aot: AudioObjectType) -> bool {
+    matches!(
+        aot,
+        AudioObjectType::AAC_MAIN
+            | AudioObjectType::AAC_LC
+            | AudioObjectType::AAC_SSR
+            | AudioObjectType::AAC_LTP
+            | AudioObjectType::SBR
+            | AudioObjectType::PS
+    )
+}
+
+/// Reads the VP9 codec feature metadata, which is a sequence of ID, length and value fields
+#[cfg(feature = "vp9")]
+fn vp9_features(codec_private: &[u8]) -> Result<Vp09, CodecError> {
+    let mut profile = None;
+    let mut level = None;
+    let mut bit_depth = None;
+    let mut chroma_subsampling = None;
+    let mut data = codec_private;
+    while let [id, len, rest @ ..] = data {
+        let len = usize::from(*len);
+        let value = rest
+            .get(..len)
+            .ok_or(CodecError::ConfigurationRecordTooShort {
+                expected: codec_private.len() - rest.len() + len,
+                got: codec_private.len(),
+            })?;
+        let field = match id {
+            1 => &mut profile,
+            2 => &mut level,
+            3 => &mut bit_depth,
+            4 => &mut chroma_subsampling,
+            _ => &mut None,
+        };
+        *field = value.first().copied();
+        data = &rest[len..];
+    }
+    let level = level.ok_or_else(|| CodecError::UnsupportedSampleEntry("V_VP9".into()))?;
+    let mut vp09 = Vp09::new(profile.unwrap_or(0), level, bit_depth.unwrap_or(8));
+    if let Some(v) = chroma_subsampling {
+        // the optional elements must all be given, so the ones CodecPrivate doesn't carry take
+        // their default values
+        vp09.chroma_subsampling = Some(
+            Vp9ChromaSubsampling::from_value(v)
+                .ok_or_else(|| CodecError::InvalidComponent(format_args!("{:02}", v).into()))?,
+        );
+        vp09.color_primaries = Some(vp09.color_primaries());
+        vp09.transfer_characteristics = Some(vp09.transfer_characteristics());
+        vp09.matrix_coefficients = Some(vp09.matrix_coefficients());
+        vp09.video_full_range_flag = Some(vp09.video_full_range_flag());
+    }
+    Ok(vp09)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::*;
+
+    fn codec(codec_id: &str, codec_private: &[u8]) -> String {
+        Codec::from_matroska(codec_id, codec_private)
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn tracks() {
+        #[cfg(feature = "vp9")]
+        assert_eq!(
+            codec("V_VP9", &[2, 1, 40, 3, 1, 10, 1, 1, 2]),
+            "vp09.02.40.10"
+        );
+        #[cfg(feature = "vp9")]
+        assert_eq!(
+            codec("V_VP9", &[1, 1, 1, 2, 1, 20, 3, 1, 8, 4, 1, 3]),
+            "vp09.01.20.08.03.01.01.01.00"
+        );
+        #[cfg(feature = "av1")]
+        assert_eq!(codec("V_AV1", &[0x81, 0x04, 0x0c, 0x00]), "av01.0.04M.08");
+        assert_eq!(
+            codec("V_MPEG4/ISO/AVC", &[0x01, 0x4d, 0x40, 0x1e]),
+            "avc1.4D401E"
+        );
+        assert_eq!(codec("A_AAC", &[0x12, 0x10]), "mp4a.40.2");
+        assert_eq!(codec("A_VORBIS", &[]), "vorbis");
+        assert_eq!(codec("A_FLAC", &[]), "fLaC");
+        assert_eq!(codec("A_AAC/MPEG4/LC/SBR", &[]), "mp4a.40.5");
+        assert_eq!(codec("A_AAC/MPEG2/MAIN", &[]), "mp4a.66");
+        #[cfg(feature = "text")]
+        assert_eq!(codec("S_TEXT/WEBVTT", &[]), "wvtt");
+    }
+
+    #[test]
+    fn codec_ids() {
+        let id = |codec: &str| codec.parse::<Codec>().unwrap().matroska_codec_id();
+        assert_eq!(id("avc1.4D401E"), Some("V_MPEG4/ISO/AVC"));
+        #[cfg(feature = "av1")]
+        assert_eq!(id("av01.0.04M.08"), Some("V_AV1"));
+        #[cfg(feature = "vp9")]
+        assert_eq!(id("vp09.00.31.08"), Some("V_VP9"));
+        assert_eq!(id("mp4a.40.29"), Some("A_AAC"));
+        assert_eq!(id("mp4a.67"), Some("A_AAC"));
+        assert_eq!(id("mp4a.6B"), Some("A_MPEG/L3"));
+        assert_eq!(id("mp4a.40.34"), Some("A_MPEG/L3"));
+        assert_eq!(id("mp4a.40.42"), None);
+        #[cfg(feature = "dolby")]
+        assert_eq!(id("ec-3"), Some("A_EAC3"));
+        assert_eq!(
+            Codec::Other("vp8".into()).matroska_codec_id(),
+            Some("V_VP8")
+        );
+        assert_eq!(id("vvc1.1.L51.CQA"), None);
+        for id in &["V_VP8", "A_OPUS", "A_VORBIS", "A_FLAC", "A_ALAC"] {
+            let codec = Codec::from_matroska(id, &[]).unwrap();
+            assert_eq!(codec.matroska_codec_id(), Some(*id));
+        }
+        #[cfg(feature = "dolby")]
+        assert_eq!(
+            Codec::from_matroska("A_AC3", &[])
+                .unwrap()
+                .matroska_codec_id(),
+            Some("A_AC3")
+        );
+    }
+
+    #[test]
+    fn bad_tracks() {
+        // no level given
+        assert_matches!(
+            Codec::from_matroska("V_VP9", &[1, 1, 0]),
+            Err(CodecError::UnsupportedSampleEntry(_))
+        );
+        #[cfg(feature = "vp9")]
+        assert_matches!(
+            Codec::from_matroska("V_VP9", &[2, 1, 31, 3, 2, 8]),
+            Err(CodecError::ConfigurationRecordTooShort {
+                expected: 7,
+                got: 6
+            })
+        );
+        assert_matches!(
+            Codec::from_matroska("S_TEXT/UTF8", &[]),
+            Err(CodecError::UnsupportedSampleEntry(_))
+        );
+    }
+}